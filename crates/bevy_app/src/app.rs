@@ -1,10 +1,12 @@
-use crate::app_builder::AppBuilder;
+use crate::{app_builder::AppBuilder, plugin::PluginRecord};
 use bevy_ecs::{
-    schedule::{Schedule, Stage},
+    schedule::{Schedule, Stage, SystemStage},
     world::World,
 };
+use bevy_utils::tracing::debug;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use std::borrow::Cow;
 
 #[allow(clippy::needless_doctest_main)]
 /// Containers of app logic and data
@@ -12,6 +14,9 @@ use bevy_utils::tracing::info_span;
 /// App store the ECS World, Resources, Schedule, and Executor. They also store the "run" function of the App, which
 /// by default executes the App schedule once. Apps are constructed using the builder pattern.
 ///
+/// An App can own named [SubApp]s, each with its own World and Schedule, which are updated after the App's own
+/// Schedule runs.
+///
 /// ## Example
 /// Here is a simple "Hello World" Bevy app:
 /// ```
@@ -32,6 +37,31 @@ pub struct App {
     pub world: World,
     pub runner: Box<dyn Fn(App)>,
     pub schedule: Schedule,
+    sub_apps: Vec<SubApp>,
+    /// The records of the plugins added to the App, in the order they were added.
+    pub(crate) plugins: Vec<(String, PluginRecord)>,
+    /// The records of the plugins currently being built, innermost last.
+    pub(crate) recording: Vec<(String, PluginRecord)>,
+    // Declared last so that hooks, and anything they own, are dropped after the World and Schedule.
+    update_hooks: Vec<UpdateHook>,
+}
+
+type UpdateHook = Box<dyn FnMut(&mut App)>;
+
+/// An [App] owned by another App. Each time the owner updates, the sub-app's extract function copies data from the
+/// owner's World into the sub-app's World, then the sub-app updates. The sub-app's runner is not used.
+pub struct SubApp {
+    name: Cow<'static, str>,
+    pub app: App,
+    extract: ExtractFn,
+}
+
+type ExtractFn = Box<dyn FnMut(&mut World, &mut World)>;
+
+impl SubApp {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 impl Default for App {
@@ -40,6 +70,10 @@ impl Default for App {
             world: Default::default(),
             schedule: Default::default(),
             runner: Box::new(run_once),
+            sub_apps: Vec::new(),
+            plugins: Vec::new(),
+            recording: Vec::new(),
+            update_hooks: Vec::new(),
         }
     }
 }
@@ -53,8 +87,124 @@ impl App {
         AppBuilder::default()
     }
 
+    /// Runs the App's update hooks, then runs the App's Schedule once, then extracts and updates each sub-app in
+    /// the order they were added.
     pub fn update(&mut self) {
+        if !self.update_hooks.is_empty() {
+            let mut hooks = std::mem::take(&mut self.update_hooks);
+            for hook in hooks.iter_mut() {
+                hook(self);
+            }
+            // Keep hooks added by other hooks.
+            hooks.append(&mut self.update_hooks);
+            self.update_hooks = hooks;
+        }
         self.schedule.run(&mut self.world);
+        for sub_app in self.sub_apps.iter_mut() {
+            #[cfg(feature = "trace")]
+            let sub_app_span = info_span!("sub_app", name = &*sub_app.name);
+            #[cfg(feature = "trace")]
+            let _sub_app_guard = sub_app_span.enter();
+
+            (sub_app.extract)(&mut self.world, &mut sub_app.app.world);
+            sub_app.app.update();
+        }
+    }
+
+    /// Adds a sub-app. `extract` receives the World of this App and the World of the sub-app, and is the only
+    /// point at which data moves between them.
+    ///
+    /// # Panics
+    /// Panics if a sub-app with the same name was already added.
+    pub fn add_sub_app(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        app: App,
+        extract: impl FnMut(&mut World, &mut World) + 'static,
+    ) {
+        let name = name.into();
+        if self.sub_apps.iter().any(|sub_app| sub_app.name == name) {
+            panic!("Sub-app {} already exists.", name);
+        }
+        self.sub_apps.push(SubApp {
+            name,
+            app,
+            extract: Box::new(extract),
+        });
+    }
+
+    pub fn sub_app(&self, name: &str) -> Option<&App> {
+        self.sub_apps
+            .iter()
+            .find(|sub_app| sub_app.name == name)
+            .map(|sub_app| &sub_app.app)
+    }
+
+    pub fn sub_app_mut(&mut self, name: &str) -> Option<&mut App> {
+        self.sub_apps
+            .iter_mut()
+            .find(|sub_app| sub_app.name == name)
+            .map(|sub_app| &mut sub_app.app)
+    }
+
+    pub fn remove_sub_app(&mut self, name: &str) -> Option<App> {
+        let index = self
+            .sub_apps
+            .iter()
+            .position(|sub_app| sub_app.name == name)?;
+        Some(self.sub_apps.remove(index).app)
+    }
+
+    pub fn sub_apps(&self) -> impl Iterator<Item = &SubApp> {
+        self.sub_apps.iter()
+    }
+
+    /// Adds a function that runs with exclusive access to the App at the start of every update, before the
+    /// Schedule runs. Unlike systems, hooks can change the Schedule itself, for example to unload plugins.
+    pub fn add_update_hook(&mut self, hook: impl FnMut(&mut App) + 'static) {
+        self.update_hooks.push(Box::new(hook));
+    }
+
+    /// Returns the record of the most recently added plugin with the given name. Only plugins added directly to
+    /// the App, or through a [PluginGroup](crate::PluginGroup), have their own record; the additions of plugins
+    /// they add themselves are part of their record.
+    pub fn plugin_record(&self, name: &str) -> Option<&PluginRecord> {
+        self.plugins
+            .iter()
+            .rev()
+            .find(|(plugin, _)| plugin == name)
+            .map(|(_, record)| record)
+    }
+
+    /// Returns `true` if a plugin with the given name was added to the App, or is being built.
+    pub fn is_plugin_added(&self, name: &str) -> bool {
+        self.plugins
+            .iter()
+            .chain(self.recording.iter())
+            .any(|(plugin, record)| plugin == name || record.plugins.iter().any(|p| p == name))
+    }
+
+    /// Removes the systems, stages and resources the most recently added plugin with the given name added to the
+    /// App, see [PluginRecord]. Returns the plugin's record, or [None] if no plugin with that name was added.
+    pub fn unload_plugin(&mut self, name: &str) -> Option<PluginRecord> {
+        let index = self
+            .plugins
+            .iter()
+            .rposition(|(plugin, _)| plugin == name)?;
+        let (_, record) = self.plugins.remove(index);
+        for (stage_label, system_id) in record.systems.iter() {
+            if let Some(stage) = self.schedule.get_stage_mut::<SystemStage>(&**stage_label) {
+                stage.remove_systems(&[*system_id]);
+            }
+        }
+        for stage_label in record.stages.iter() {
+            self.schedule.remove_stage(&**stage_label);
+        }
+        for resource in record.resources.iter() {
+            self.world.remove_resource_by_id(*resource);
+        }
+        debug!("unloaded plugin: {}", name);
+        Some(record)
     }
 
     pub fn run(mut self) {
@@ -71,3 +221,120 @@ impl App {
 /// An event that indicates the app should exit. This will fully exit the app process.
 #[derive(Debug, Clone)]
 pub struct AppExit;
+
+#[cfg(test)]
+mod tests {
+    use crate::{App, AppBuilder, CoreStage, Plugin};
+    use bevy_ecs::{
+        schedule::{StageLabel, SystemSet, SystemStage},
+        system::{IntoSystem, Res, ResMut},
+    };
+
+    struct Frame(u32);
+    struct ExtractedFrames(Vec<u32>);
+
+    fn advance_frame(mut frame: ResMut<Frame>) {
+        frame.0 += 1;
+    }
+
+    fn record_frame(frame: Res<Frame>, mut frames: ResMut<ExtractedFrames>) {
+        frames.0.push(frame.0);
+    }
+
+    #[test]
+    fn sub_app() {
+        let mut sub_app = AppBuilder::empty();
+        sub_app
+            .add_stage(CoreStage::Update, SystemStage::parallel())
+            .insert_resource(ExtractedFrames(Vec::new()))
+            .add_system(record_frame.system());
+
+        let mut app = App::build();
+        app.insert_resource(Frame(0))
+            .add_system(advance_frame.system())
+            .add_sub_app("render", sub_app.app, |main_world, sub_world| {
+                let frame = main_world.get_resource::<Frame>().unwrap().0;
+                sub_world.insert_resource(Frame(frame * 10));
+            });
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        assert!(app.world.get_resource::<ExtractedFrames>().is_none());
+        let render = app.sub_app("render").unwrap();
+        assert_eq!(
+            render.world.get_resource::<ExtractedFrames>().unwrap().0,
+            vec![10, 20]
+        );
+        assert_eq!(app.sub_apps().next().unwrap().name(), "render");
+        assert!(app.remove_sub_app("render").is_some());
+        assert!(app.sub_app("render").is_none());
+    }
+
+    #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+    struct FrameStage;
+
+    struct FramePlugin;
+
+    impl Plugin for FramePlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            app.insert_resource(Frame(0))
+                .add_system_set(SystemSet::new().with_system(advance_frame.system()))
+                .add_plugin(ExtractPlugin);
+        }
+    }
+
+    struct ExtractPlugin;
+
+    impl Plugin for ExtractPlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            app.insert_resource(ExtractedFrames(Vec::new()))
+                .add_stage_after(CoreStage::Update, FrameStage, SystemStage::parallel())
+                .add_system_to_stage(FrameStage, record_frame.system());
+        }
+    }
+
+    #[test]
+    fn unload_plugin() {
+        let mut app = App::build();
+        app.add_plugin(FramePlugin);
+        let mut app = app.app;
+        app.update();
+        app.update();
+        assert_eq!(
+            app.world.get_resource::<ExtractedFrames>().unwrap().0,
+            vec![1, 2]
+        );
+
+        let name = std::any::type_name::<FramePlugin>();
+        let record = app.plugin_record(name).unwrap();
+        assert_eq!(record.stages.len(), 1);
+        assert_eq!(record.systems.len(), 2);
+        assert_eq!(record.resources.len(), 2);
+        assert!(app
+            .plugin_record(std::any::type_name::<ExtractPlugin>())
+            .is_none());
+
+        assert!(app.unload_plugin(name).is_some());
+        assert!(app.unload_plugin(name).is_none());
+        assert!(app.world.get_resource::<Frame>().is_none());
+        assert!(app.world.get_resource::<ExtractedFrames>().is_none());
+        assert!(app.schedule.get_stage::<SystemStage>(&FrameStage).is_none());
+
+        app.world.insert_resource(Frame(5));
+        app.update();
+        assert_eq!(app.world.get_resource::<Frame>().unwrap().0, 5);
+    }
+
+    #[test]
+    fn update_hooks() {
+        let mut app = App::default();
+        app.world.insert_resource(Frame(0));
+        app.add_update_hook(|app| {
+            app.world.get_resource_mut::<Frame>().unwrap().0 += 1;
+        });
+        app.update();
+        app.update();
+        assert_eq!(app.world.get_resource::<Frame>().unwrap().0, 2);
+    }
+}
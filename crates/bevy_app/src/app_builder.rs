@@ -1,7 +1,10 @@
 use crate::{
     app::{App, AppExit},
-    event::Events,
-    plugin::Plugin,
+    event::{EventRetention, Events},
+    plugin::{
+        check_dependencies, check_provided_resources, Plugin, PluginDependencies, PluginError,
+        PluginRecord,
+    },
     CoreStage, PluginGroup, PluginGroupBuilder, StartupStage,
 };
 use bevy_ecs::{
@@ -13,6 +16,7 @@ use bevy_ecs::{
     world::{FromWorld, World},
 };
 use bevy_utils::tracing::debug;
+use std::{any::TypeId, borrow::Cow};
 
 /// Configure [App]s using the builder pattern
 pub struct AppBuilder {
@@ -61,7 +65,52 @@ impl AppBuilder {
         self
     }
 
+    /// Adds a sub-app with its own World and Schedule, see [App::add_sub_app].
+    pub fn add_sub_app(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        app: App,
+        extract: impl FnMut(&mut World, &mut World) + 'static,
+    ) -> &mut Self {
+        self.app.add_sub_app(name, app, extract);
+        self
+    }
+
+    /// Returns the sub-app with the given name, for example to add systems to it.
+    ///
+    /// # Panics
+    /// Panics if there is no sub-app with that name.
+    pub fn sub_app_mut(&mut self, name: &str) -> &mut App {
+        self.app
+            .sub_app_mut(name)
+            .unwrap_or_else(|| panic!("Sub-app {} does not exist.", name))
+    }
+
+    /// Calls `func` with the record of the plugin being built, if any.
+    fn record(&mut self, func: impl FnOnce(&mut PluginRecord)) {
+        if let Some((_, record)) = self.app.recording.last_mut() {
+            func(record);
+        }
+    }
+
+    /// Records the resource of type `T` as added by the plugin being built, if it did not exist before.
+    fn record_resource<T: 'static>(&mut self, existed: bool) {
+        if existed {
+            return;
+        }
+        let component_id = self
+            .app
+            .world
+            .components()
+            .get_resource_id(TypeId::of::<T>());
+        if let Some(component_id) = component_id {
+            self.record(|record| record.resources.push(component_id));
+        }
+    }
+
     pub fn add_stage<S: Stage>(&mut self, label: impl StageLabel, stage: S) -> &mut Self {
+        let boxed_label = label.dyn_clone();
+        self.record(|record| record.stages.push(boxed_label));
         self.app.schedule.add_stage(label, stage);
         self
     }
@@ -72,6 +121,8 @@ impl AppBuilder {
         label: impl StageLabel,
         stage: S,
     ) -> &mut Self {
+        let boxed_label = label.dyn_clone();
+        self.record(|record| record.stages.push(boxed_label));
         self.app.schedule.add_stage_after(target, label, stage);
         self
     }
@@ -82,6 +133,8 @@ impl AppBuilder {
         label: impl StageLabel,
         stage: S,
     ) -> &mut Self {
+        let boxed_label = label.dyn_clone();
+        self.record(|record| record.stages.push(boxed_label));
         self.app.schedule.add_stage_before(target, label, stage);
         self
     }
@@ -145,6 +198,9 @@ impl AppBuilder {
         stage_label: impl StageLabel,
         system: impl Into<SystemDescriptor>,
     ) -> &mut Self {
+        let system = system.into();
+        let record_entry = (stage_label.dyn_clone(), system.system_id());
+        self.record(|record| record.systems.push(record_entry));
         self.app.schedule.add_system_to_stage(stage_label, system);
         self
     }
@@ -154,6 +210,15 @@ impl AppBuilder {
         stage_label: impl StageLabel,
         system_set: SystemSet,
     ) -> &mut Self {
+        let boxed_label = stage_label.dyn_clone();
+        let system_ids = system_set.system_ids().collect::<Vec<_>>();
+        self.record(|record| {
+            record.systems.extend(
+                system_ids
+                    .into_iter()
+                    .map(|system_id| (boxed_label.clone(), system_id)),
+            )
+        });
         self.app
             .schedule
             .add_system_set_to_stage(stage_label, system_set);
@@ -210,6 +275,39 @@ impl AppBuilder {
         })
     }
 
+    pub fn on_state_pause<T: Clone + Component>(
+        &mut self,
+        stage: impl StageLabel,
+        state: T,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        self.stage(stage, |stage: &mut StateStage<T>| {
+            stage.on_state_pause(state, system)
+        })
+    }
+
+    pub fn on_state_resume<T: Clone + Component>(
+        &mut self,
+        stage: impl StageLabel,
+        state: T,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        self.stage(stage, |stage: &mut StateStage<T>| {
+            stage.on_state_resume(state, system)
+        })
+    }
+
+    pub fn on_state_inactive<T: Clone + Component>(
+        &mut self,
+        stage: impl StageLabel,
+        state: T,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        self.stage(stage, |stage: &mut StateStage<T>| {
+            stage.on_state_inactive(state, system)
+        })
+    }
+
     pub fn add_default_stages(&mut self) -> &mut Self {
         self.add_stage(
             CoreStage::Startup,
@@ -232,7 +330,16 @@ impl AppBuilder {
     where
         T: Component,
     {
-        self.insert_resource(Events::<T>::default())
+        self.add_event_with_retention::<T>(EventRetention::default())
+    }
+
+    /// Like [AppBuilder::add_event], but with the given [EventRetention]. Use [EventRetention::UntilRead] for
+    /// events read by systems that might not run every frame.
+    pub fn add_event_with_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Component,
+    {
+        self.insert_resource(Events::<T>::new(retention))
             .add_system_to_stage(CoreStage::Event, Events::<T>::update_system.system())
     }
 
@@ -241,7 +348,9 @@ impl AppBuilder {
     where
         T: Component,
     {
+        let existed = self.app.world.contains_resource::<T>();
         self.app.world.insert_resource(resource);
+        self.record_resource::<T>(existed);
         self
     }
 
@@ -249,7 +358,9 @@ impl AppBuilder {
     where
         T: 'static,
     {
+        let existed = self.app.world.get_non_send_resource::<T>().is_some();
         self.app.world.insert_non_send(resource);
+        self.record_resource::<T>(existed);
         self
     }
 
@@ -274,7 +385,7 @@ impl AppBuilder {
         // See perf comment in init_resource
         if self.app.world.get_non_send_resource::<R>().is_none() {
             let resource = R::from_world(self.world_mut());
-            self.app.world.insert_non_send(resource);
+            self.insert_non_send_resource(resource);
         }
         self
     }
@@ -284,15 +395,79 @@ impl AppBuilder {
         self
     }
 
+    /// Adds a plugin and builds it.
+    ///
+    /// # Panics
+    /// Panics if the plugin was already added, or if its [dependencies](Plugin::dependencies) are not satisfied.
+    /// See [AppBuilder::try_add_plugin].
     pub fn add_plugin<T>(&mut self, plugin: T) -> &mut Self
     where
         T: Plugin,
     {
+        if let Err(err) = self.try_add_plugin(plugin) {
+            panic!("{}", err);
+        }
+        self
+    }
+
+    /// Like [AppBuilder::add_plugin], but returns an error instead of panicking.
+    pub fn try_add_plugin<T>(&mut self, plugin: T) -> Result<&mut Self, PluginError>
+    where
+        T: Plugin,
+    {
+        self.try_build_plugin(&plugin)?;
+        Ok(self)
+    }
+
+    /// Like [AppBuilder::add_plugin], for plugins that are only known at runtime, such as dynamically loaded ones.
+    pub fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
+        if let Err(err) = self.try_add_boxed_plugin(plugin) {
+            panic!("{}", err);
+        }
+        self
+    }
+
+    /// Like [AppBuilder::add_boxed_plugin], but returns an error instead of panicking.
+    ///
+    /// Dependencies are checked before the plugin is built, except for the resources it
+    /// [provides](PluginDependencies::provides_resource). When [PluginError::ResourceNotProvided] is returned, the
+    /// plugin was already built and can be removed again with [App::unload_plugin].
+    pub fn try_add_boxed_plugin(
+        &mut self,
+        plugin: Box<dyn Plugin>,
+    ) -> Result<&mut Self, PluginError> {
+        self.try_build_plugin(&*plugin)?;
+        Ok(self)
+    }
+
+    fn try_build_plugin(&mut self, plugin: &dyn Plugin) -> Result<(), PluginError> {
+        let mut dependencies = PluginDependencies::default();
+        plugin.dependencies(&mut dependencies);
+        check_dependencies(&self.app, plugin.name(), &dependencies, &[])?;
+        self.build_plugin(plugin);
+        check_provided_resources(&self.app, plugin.name(), &dependencies)
+    }
+
+    /// Builds `plugin`, recording what it adds to the App. See [PluginRecord].
+    pub(crate) fn build_plugin(&mut self, plugin: &dyn Plugin) {
         debug!("added plugin: {}", plugin.name());
+        self.app
+            .recording
+            .push((plugin.name().to_string(), PluginRecord::default()));
         plugin.build(self);
-        self
+        let (name, record) = self.app.recording.pop().unwrap();
+        if let Some((_, parent)) = self.app.recording.last_mut() {
+            parent.extend(record);
+            parent.plugins.push(name);
+        } else {
+            self.app.plugins.push((name, record));
+        }
     }
 
+    /// Adds a group of plugins, ordered so that each plugin is built after the plugins of the group it depends on.
+    ///
+    /// # Panics
+    /// Panics if the dependencies of the plugins are not satisfied. See [PluginGroupBuilder::try_finish].
     pub fn add_plugins<T: PluginGroup>(&mut self, mut group: T) -> &mut Self {
         let mut plugin_group_builder = PluginGroupBuilder::default();
         group.build(&mut plugin_group_builder);
@@ -1,9 +1,18 @@
 use bevy_ecs::{
     component::Component,
     system::{Local, Res, ResMut, SystemParam},
+    world::{FromWorld, World},
 };
 use bevy_utils::tracing::trace;
-use std::{fmt, marker::PhantomData};
+use std::{
+    collections::VecDeque,
+    fmt,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+};
 
 /// An `EventId` uniquely identifies an event.
 ///
@@ -45,10 +54,16 @@ struct EventInstance<T> {
     pub event: T,
 }
 
-#[derive(Debug)]
-enum State {
-    A,
-    B,
+/// Determines when [Events::update] drops events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventRetention {
+    /// Events are dropped by the second [Events::update] call after they were sent.
+    #[default]
+    TwoUpdates,
+    /// Like [EventRetention::TwoUpdates], except that events are also kept until every reader obtained from
+    /// [Events::get_tracked_reader], including every [EventReader], has read them. Readers that never read, for
+    /// example because their system never runs, keep events alive until they are dropped.
+    UntilRead,
 }
 
 /// An event collection that represents the events that occurred within the last two [Events::update] calls. Events can be cheaply read using
@@ -85,34 +100,31 @@ enum State {
 ///
 /// # Details
 ///
-/// [Events] is implemented using a double buffer. Each call to [Events::update] swaps buffers and clears out the oldest buffer.
-/// [EventReader]s that read at least once per update will never drop events. [EventReader]s that read once within two updates might
-/// still receive some events. [EventReader]s that read after two updates are guaranteed to drop all events that occurred before those updates.
+/// By default, each call to [Events::update] drops the events sent before the previous call. [EventReader]s that read at least once per update
+/// will never drop events. [EventReader]s that read once within two updates might still receive some events. [EventReader]s that read after two
+/// updates are guaranteed to drop all events that occurred before those updates, and can find out how many with [EventReader::missed_events].
 ///
-/// The buffers in [Events] will grow indefinitely if [Events::update] is never called.
+/// Readers that don't run every update, for example because they are gated by run criteria, should use events created with
+/// [EventRetention::UntilRead], which are only dropped once every reader has read them.
+///
+/// The events in [Events] will grow indefinitely if [Events::update] is never called.
 ///
 /// An alternative call pattern would be to call [Events::update] manually across frames to control when events are cleared. However
 /// this complicates consumption
 #[derive(Debug)]
 pub struct Events<T> {
-    events_a: Vec<EventInstance<T>>,
-    events_b: Vec<EventInstance<T>>,
-    a_start_event_count: usize,
-    b_start_event_count: usize,
+    events: VecDeque<EventInstance<T>>,
+    /// The number of events sent before the last [Events::update] call.
+    update_event_count: usize,
     event_count: usize,
-    state: State,
+    retention: EventRetention,
+    /// The read positions of tracked readers.
+    cursors: Vec<Weak<AtomicUsize>>,
 }
 
 impl<T> Default for Events<T> {
     fn default() -> Self {
-        Events {
-            a_start_event_count: 0,
-            b_start_event_count: 0,
-            event_count: 0,
-            events_a: Vec::new(),
-            events_b: Vec::new(),
-            state: State::A,
-        }
+        Self::new(EventRetention::default())
     }
 }
 
@@ -127,10 +139,24 @@ fn map_instance_event<T>(event_instance: &EventInstance<T>) -> &T {
 /// Reads events of type `T` in order and tracks which events have already been read.
 #[derive(SystemParam)]
 pub struct EventReader<'a, T: Component> {
-    last_event_count: Local<'a, (usize, PhantomData<T>)>,
+    reader: Local<'a, EventReaderCursor<T>>,
     events: Res<'a, Events<T>>,
 }
 
+/// The read position of an [EventReader]. It is tracked by the [Events] resource if it exists when the system
+/// is initialized.
+#[doc(hidden)]
+pub struct EventReaderCursor<T>(ManualEventReader<T>);
+
+impl<T: Component> FromWorld for EventReaderCursor<T> {
+    fn from_world(world: &mut World) -> Self {
+        EventReaderCursor(match world.get_resource_mut::<Events<T>>() {
+            Some(mut events) => events.get_tracked_reader(),
+            None => ManualEventReader::default(),
+        })
+    }
+}
+
 /// Sends events of type `T`.
 #[derive(SystemParam)]
 pub struct EventWriter<'a, T: Component> {
@@ -147,8 +173,33 @@ impl<'a, T: Component> EventWriter<'a, T> {
     }
 }
 
+/// Removes events of type `T` as they are read, so that each event is handled only once no matter how many
+/// systems consume it. Events drained this way are counted as missed by [EventReader]s that had not read them yet.
+#[derive(SystemParam)]
+pub struct EventConsumer<'a, T: Component> {
+    events: ResMut<'a, Events<T>>,
+}
+
+impl<'a, T: Component> EventConsumer<'a, T> {
+    /// Removes all events and returns them in the order they were sent.
+    pub fn drain(&mut self) -> impl DoubleEndedIterator<Item = T> + '_ {
+        self.events.drain()
+    }
+
+    /// Like [`drain`](Self::drain), except also returning the [`EventId`] of the events.
+    pub fn drain_with_id(&mut self) -> impl DoubleEndedIterator<Item = (T, EventId<T>)> + '_ {
+        self.events.drain_with_id()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
 pub struct ManualEventReader<T> {
     last_event_count: usize,
+    /// Shares `last_event_count` with the [Events] this reader is tracked by.
+    cursor: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<T>,
 }
 
@@ -156,6 +207,7 @@ impl<T> Default for ManualEventReader<T> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            cursor: None,
             _marker: Default::default(),
         }
     }
@@ -164,7 +216,7 @@ impl<T> Default for ManualEventReader<T> {
 impl<T> ManualEventReader<T> {
     /// See [`EventReader::iter`]
     pub fn iter<'a>(&mut self, events: &'a Events<T>) -> impl DoubleEndedIterator<Item = &'a T> {
-        internal_event_reader(&mut self.last_event_count, events).map(|(e, _)| e)
+        self.read(events).map(|(e, _)| e)
     }
 
     /// See [`EventReader::iter_with_id`]
@@ -172,57 +224,35 @@ impl<T> ManualEventReader<T> {
         &mut self,
         events: &'a Events<T>,
     ) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
-        internal_event_reader(&mut self.last_event_count, events)
+        self.read(events)
     }
-}
 
-/// Like [`iter_with_id`](EventReader::iter_with_id) except not emitting any traces for read messages.
-fn internal_event_reader<'a, T>(
-    last_event_count: &mut usize,
-    events: &'a Events<T>,
-) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
-    // if the reader has seen some of the events in a buffer, find the proper index offset.
-    // otherwise read all events in the buffer
-    let a_index = if *last_event_count > events.a_start_event_count {
-        *last_event_count - events.a_start_event_count
-    } else {
-        0
-    };
-    let b_index = if *last_event_count > events.b_start_event_count {
-        *last_event_count - events.b_start_event_count
-    } else {
-        0
-    };
-    *last_event_count = events.event_count;
-    match events.state {
-        State::A => events
-            .events_b
-            .get(b_index..)
-            .unwrap_or_else(|| &[])
-            .iter()
-            .map(map_instance_event_with_id)
-            .chain(
-                events
-                    .events_a
-                    .get(a_index..)
-                    .unwrap_or_else(|| &[])
-                    .iter()
-                    .map(map_instance_event_with_id),
-            ),
-        State::B => events
-            .events_a
-            .get(a_index..)
-            .unwrap_or_else(|| &[])
-            .iter()
-            .map(map_instance_event_with_id)
-            .chain(
-                events
-                    .events_b
-                    .get(b_index..)
-                    .unwrap_or_else(|| &[])
-                    .iter()
-                    .map(map_instance_event_with_id),
-            ),
+    /// See [`EventReader::missed_events`]
+    pub fn missed_events(&self, events: &Events<T>) -> usize {
+        events
+            .oldest_event_count()
+            .saturating_sub(self.last_event_count)
+    }
+
+    /// Like [`iter_with_id`](Self::iter_with_id) except not emitting any traces for read messages.
+    fn read<'a>(
+        &mut self,
+        events: &'a Events<T>,
+    ) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
+        // if the reader has seen some of the events, skip them. otherwise read all events
+        let index = self
+            .last_event_count
+            .saturating_sub(events.oldest_event_count())
+            .min(events.events.len());
+        self.set_last_event_count(events.event_count);
+        events.events.range(index..).map(map_instance_event_with_id)
+    }
+
+    fn set_last_event_count(&mut self, last_event_count: usize) {
+        self.last_event_count = last_event_count;
+        if let Some(cursor) = &self.cursor {
+            cursor.store(last_event_count, Ordering::Relaxed);
+        }
     }
 }
 
@@ -235,11 +265,17 @@ impl<'a, T: Component> EventReader<'a, T> {
 
     /// Like [`iter`](Self::iter), except also returning the [`EventId`] of the events.
     pub fn iter_with_id(&mut self) -> impl DoubleEndedIterator<Item = (&T, EventId<T>)> {
-        internal_event_reader(&mut self.last_event_count.0, &self.events).map(|(event, id)| {
+        self.reader.0.read(&self.events).map(|(event, id)| {
             trace!("EventReader::iter() -> {}", id);
             (event, id)
         })
     }
+
+    /// Returns the number of events that were dropped or consumed before this EventReader read them. Call it
+    /// before [`iter`](Self::iter), which marks every event as read.
+    pub fn missed_events(&self) -> usize {
+        self.reader.0.missed_events(&self.events)
+    }
 }
 
 impl<T: Component> Events<T> {
@@ -251,46 +287,58 @@ impl<T: Component> Events<T> {
         };
         trace!("Events::send() -> {}", event_id);
 
-        let event_instance = EventInstance { event, event_id };
-
-        match self.state {
-            State::A => self.events_a.push(event_instance),
-            State::B => self.events_b.push(event_instance),
-        }
-
+        self.events.push_back(EventInstance { event, event_id });
         self.event_count += 1;
     }
 
     /// Gets a new [ManualEventReader]. This will include all events already in the event buffers.
     pub fn get_reader(&self) -> ManualEventReader<T> {
-        ManualEventReader {
-            last_event_count: 0,
-            _marker: PhantomData,
-        }
+        ManualEventReader::default()
     }
 
     /// Gets a new [ManualEventReader]. This will ignore all events already in the event buffers. It will read all future events.
     pub fn get_reader_current(&self) -> ManualEventReader<T> {
         ManualEventReader {
             last_event_count: self.event_count,
+            cursor: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets a new [ManualEventReader] that includes all events already in the event buffers. With
+    /// [EventRetention::UntilRead], events are kept until this reader has read them or is dropped.
+    pub fn get_tracked_reader(&mut self) -> ManualEventReader<T> {
+        let cursor = Arc::new(AtomicUsize::new(0));
+        self.cursors.push(Arc::downgrade(&cursor));
+        ManualEventReader {
+            last_event_count: 0,
+            cursor: Some(cursor),
             _marker: PhantomData,
         }
     }
 
-    /// Swaps the event buffers and clears the oldest event buffer. In general, this should be called once per frame/update.
+    /// Drops the events that are no longer retained, see [EventRetention]. In general, this should be called once per frame/update.
     pub fn update(&mut self) {
-        match self.state {
-            State::A => {
-                self.events_b = Vec::new();
-                self.state = State::B;
-                self.b_start_event_count = self.event_count;
+        let mut keep_from = self.update_event_count;
+        self.cursors.retain(|cursor| cursor.strong_count() > 0);
+        if self.retention == EventRetention::UntilRead {
+            if let Some(oldest_unread) = self
+                .cursors
+                .iter()
+                .filter_map(Weak::upgrade)
+                .map(|cursor| cursor.load(Ordering::Relaxed))
+                .min()
+            {
+                keep_from = oldest_unread.min(self.update_event_count);
             }
-            State::B => {
-                self.events_a = Vec::new();
-                self.state = State::A;
-                self.a_start_event_count = self.event_count;
+        }
+        while let Some(instance) = self.events.front() {
+            if instance.event_id.id >= keep_from {
+                break;
             }
+            self.events.pop_front();
         }
+        self.update_event_count = self.event_count;
     }
 
     /// A system that calls [Events::update] once per frame.
@@ -300,25 +348,23 @@ impl<T: Component> Events<T> {
 
     /// Removes all events.
     pub fn clear(&mut self) {
-        self.events_a.clear();
-        self.events_b.clear();
+        self.events.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
     }
 
     /// Creates a draining iterator that removes all events.
-    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
-        let map = |i: EventInstance<T>| i.event;
-        match self.state {
-            State::A => self
-                .events_b
-                .drain(..)
-                .map(map)
-                .chain(self.events_a.drain(..).map(map)),
-            State::B => self
-                .events_a
-                .drain(..)
-                .map(map)
-                .chain(self.events_b.drain(..).map(map)),
-        }
+    pub fn drain(&mut self) -> impl DoubleEndedIterator<Item = T> + '_ {
+        self.events.drain(..).map(|instance| instance.event)
+    }
+
+    /// Like [`drain`](Self::drain), except also returning the [`EventId`] of the events.
+    pub fn drain_with_id(&mut self) -> impl DoubleEndedIterator<Item = (T, EventId<T>)> + '_ {
+        self.events
+            .drain(..)
+            .map(|instance| (instance.event, instance.event_id))
     }
 
     pub fn extend<I>(&mut self, events: I)
@@ -336,11 +382,33 @@ impl<T: Component> Events<T> {
     /// If events happen outside that window, they will not be handled. For example, any events that happen after this call and before
     /// the next `update()` call will be dropped.
     pub fn iter_current_update_events(&self) -> impl DoubleEndedIterator<Item = &T> {
-        match self.state {
-            State::A => self.events_a.iter().map(map_instance_event),
-            State::B => self.events_b.iter().map(map_instance_event),
+        let index = self
+            .update_event_count
+            .saturating_sub(self.oldest_event_count())
+            .min(self.events.len());
+        self.events.range(index..).map(map_instance_event)
+    }
+}
+
+impl<T> Events<T> {
+    pub fn new(retention: EventRetention) -> Self {
+        Events {
+            events: VecDeque::new(),
+            update_event_count: 0,
+            event_count: 0,
+            retention,
+            cursors: Vec::new(),
         }
     }
+
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// The id of the oldest event still stored, or the id of the next event if there are none.
+    fn oldest_event_count(&self) -> usize {
+        self.event_count - self.events.len()
+    }
 }
 
 #[cfg(test)]
@@ -442,6 +510,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_missed_events() {
+        let mut events = Events::<TestEvent>::default();
+        let mut reader = events.get_reader();
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.send(TestEvent { i: 1 });
+        events.update();
+        events.send(TestEvent { i: 2 });
+
+        assert_eq!(reader.missed_events(&events), 1);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 1 }, TestEvent { i: 2 }]
+        );
+        assert_eq!(reader.missed_events(&events), 0);
+    }
+
+    #[test]
+    fn test_until_read_retention() {
+        let mut events = Events::<TestEvent>::new(EventRetention::UntilRead);
+        let mut reader_a = events.get_tracked_reader();
+        let mut reader_b = events.get_tracked_reader();
+        let event_0 = TestEvent { i: 0 };
+        let event_1 = TestEvent { i: 1 };
+
+        events.send(event_0);
+        for _ in 0..3 {
+            events.update();
+        }
+        assert_eq!(get_events(&events, &mut reader_a), vec![event_0]);
+
+        events.send(event_1);
+        for _ in 0..3 {
+            events.update();
+        }
+        assert_eq!(
+            get_events(&events, &mut reader_b),
+            vec![event_0, event_1],
+            "reader_b keeps events alive until it reads them"
+        );
+        assert_eq!(reader_b.missed_events(&events), 0);
+
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut reader_a),
+            vec![event_1],
+            "events read by reader_b are kept until reader_a reads them"
+        );
+
+        drop(reader_b);
+        events.send(event_0);
+        get_events(&events, &mut reader_a);
+        events.update();
+        events.update();
+        assert!(events.is_empty(), "dropped readers don't keep events alive");
+    }
+
+    #[test]
+    fn test_until_read_retention_keeps_two_updates() {
+        let mut events = Events::<TestEvent>::new(EventRetention::UntilRead);
+        let mut tracked_reader = events.get_tracked_reader();
+        let mut reader = events.get_reader();
+        let event_0 = TestEvent { i: 0 };
+
+        events.send(event_0);
+        assert_eq!(get_events(&events, &mut tracked_reader), vec![event_0]);
+        events.update();
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![event_0],
+            "events read by every tracked reader are still kept for two updates"
+        );
+    }
+
+    #[test]
+    fn test_dropped_tracked_readers_are_pruned() {
+        let mut events = Events::<TestEvent>::default();
+        let _reader_a = events.get_tracked_reader();
+        let reader_b = events.get_tracked_reader();
+        drop(reader_b);
+        events.update();
+        assert_eq!(events.cursors.len(), 1);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut events = Events::<TestEvent>::default();
+        let mut reader = events.get_reader();
+        events.send(TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+
+        let drained = events.drain_with_id().collect::<Vec<_>>();
+        assert_eq!(drained[1].0, TestEvent { i: 1 });
+        assert_eq!(drained[1].1.id, 1);
+        assert!(events.is_empty());
+        assert_eq!(reader.missed_events(&events), 2);
+        assert_eq!(get_events(&events, &mut reader), vec![]);
+
+        events.send(TestEvent { i: 2 });
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 2 }]);
+    }
+
     fn get_events(
         events: &Events<TestEvent>,
         reader: &mut ManualEventReader<TestEvent>,
@@ -17,8 +17,8 @@ pub mod prelude {
     pub use crate::{
         app::App,
         app_builder::AppBuilder,
-        event::{EventReader, EventWriter},
-        CoreStage, DynamicPlugin, Plugin, PluginGroup, StartupStage,
+        event::{EventConsumer, EventReader, EventWriter},
+        CoreStage, DynamicPlugin, Plugin, PluginDependencies, PluginGroup, StartupStage,
     };
 }
 
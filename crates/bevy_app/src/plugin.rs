@@ -1,14 +1,196 @@
-use crate::AppBuilder;
-use std::any::Any;
+use crate::{App, AppBuilder};
+use bevy_ecs::{
+    component::{Component, ComponentId},
+    schedule::StageLabel,
+    system::SystemId,
+    world::World,
+};
+use std::{any::Any, fmt::Debug};
+use thiserror::Error;
 
 /// A collection of Bevy App logic and configuration
 ///
 /// Plugins use [AppBuilder] to configure an [App](crate::App). When an [App](crate::App) registers a plugin, the plugin's [Plugin::build] function is run.
+///
+/// A plugin can declare the plugins it depends on and the resources it provides or consumes in
+/// [Plugin::dependencies]. They are checked when the plugin is added, and used to order the plugins of a
+/// [PluginGroup](crate::PluginGroup).
 pub trait Plugin: Any + Send + Sync {
     fn build(&self, app: &mut AppBuilder);
     fn name(&self) -> &str {
         std::any::type_name::<Self>()
     }
+    /// Declares the dependencies of this plugin. By default a plugin has none.
+    fn dependencies(&self, _dependencies: &mut PluginDependencies) {}
+}
+
+/// The dependencies a [Plugin] declares.
+///
+/// Plugins are identified by name, so a dependency on plugin `T` is only found if `T` keeps the default
+/// [Plugin::name].
+#[derive(Debug, Default)]
+pub struct PluginDependencies {
+    pub(crate) required: Vec<&'static str>,
+    pub(crate) optional: Vec<&'static str>,
+    pub(crate) provides: Vec<ResourceDependency>,
+    pub(crate) consumes: Vec<ResourceDependency>,
+}
+
+impl PluginDependencies {
+    /// The plugin cannot be added unless `T` was added before it, or is added with it in the same
+    /// [PluginGroup](crate::PluginGroup).
+    pub fn requires<T: Plugin>(&mut self) -> &mut Self {
+        self.required.push(std::any::type_name::<T>());
+        self
+    }
+
+    /// If `T` is added with the plugin in the same [PluginGroup](crate::PluginGroup), it is built first.
+    pub fn optionally_after<T: Plugin>(&mut self) -> &mut Self {
+        self.optional.push(std::any::type_name::<T>());
+        self
+    }
+
+    /// The plugin inserts the resource `T` when it is built.
+    pub fn provides_resource<T: Component>(&mut self) -> &mut Self {
+        self.provides.push(ResourceDependency::of::<T>());
+        self
+    }
+
+    /// The plugin needs the resource `T` to exist when it is built, or when its systems run. Plugins of the
+    /// same [PluginGroup](crate::PluginGroup) that provide `T` are built first.
+    pub fn consumes_resource<T: Component>(&mut self) -> &mut Self {
+        self.consumes.push(ResourceDependency::of::<T>());
+        self
+    }
+}
+
+/// A resource a [Plugin] provides or consumes.
+#[derive(Clone, Copy)]
+pub struct ResourceDependency {
+    name: &'static str,
+    exists: fn(&World) -> bool,
+}
+
+impl ResourceDependency {
+    pub fn of<T: Component>() -> Self {
+        ResourceDependency {
+            name: std::any::type_name::<T>(),
+            exists: |world| world.contains_resource::<T>(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn exists(&self, world: &World) -> bool {
+        (self.exists)(world)
+    }
+}
+
+impl Debug for ResourceDependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl PartialEq for ResourceDependency {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+/// An error that occurs when adding plugins with inconsistent dependencies.
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("Plugin {0} was already added.")]
+    DuplicatePlugin(String),
+    #[error("Plugin {plugin} requires plugin {dependency}, which was not added.")]
+    MissingDependency { plugin: String, dependency: String },
+    #[error("Plugin {plugin} consumes resource {resource}, which does not exist and is not provided by any plugin.")]
+    MissingResource { plugin: String, resource: String },
+    #[error("Plugin {plugin} declares it provides resource {resource}, but did not insert it.")]
+    ResourceNotProvided { plugin: String, resource: String },
+    #[error("Plugins {0:?} depend on each other.")]
+    DependencyCycle(Vec<String>),
+}
+
+/// Checks that the dependencies of the plugin `name` are satisfied by the plugins and resources of `app`, together
+/// with the plugins of the group `name` is added with, if any.
+pub(crate) fn check_dependencies(
+    app: &App,
+    name: &str,
+    dependencies: &PluginDependencies,
+    group: &[(&str, &PluginDependencies)],
+) -> Result<(), PluginError> {
+    if app.is_plugin_added(name) {
+        return Err(PluginError::DuplicatePlugin(name.to_string()));
+    }
+    for dependency in dependencies.required.iter() {
+        if !app.is_plugin_added(dependency) && !group.iter().any(|(plugin, _)| plugin == dependency)
+        {
+            return Err(PluginError::MissingDependency {
+                plugin: name.to_string(),
+                dependency: dependency.to_string(),
+            });
+        }
+    }
+    for resource in dependencies.consumes.iter() {
+        if !resource.exists(&app.world)
+            && !group
+                .iter()
+                .any(|(_, dependencies)| dependencies.provides.contains(resource))
+        {
+            return Err(PluginError::MissingResource {
+                plugin: name.to_string(),
+                resource: resource.name().to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the plugin `name` inserted the resources it declares it provides.
+pub(crate) fn check_provided_resources(
+    app: &App,
+    name: &str,
+    dependencies: &PluginDependencies,
+) -> Result<(), PluginError> {
+    match dependencies
+        .provides
+        .iter()
+        .find(|resource| !resource.exists(&app.world))
+    {
+        Some(resource) => Err(PluginError::ResourceNotProvided {
+            plugin: name.to_string(),
+            resource: resource.name().to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// What a plugin added to an [App](crate::App) while it was built, used by [App::unload_plugin](crate::App::unload_plugin).
+///
+/// Stages, systems and resources added through [AppBuilder] are recorded, including those added by plugins the
+/// plugin adds itself. Startup systems, startup stages, state systems and anything added to the
+/// [World](bevy_ecs::world::World) directly are not, and stay in the App when the plugin is unloaded. Resources
+/// are only recorded if they did not exist before the plugin added them.
+#[derive(Debug, Default, Clone)]
+pub struct PluginRecord {
+    pub stages: Vec<Box<dyn StageLabel>>,
+    pub systems: Vec<(Box<dyn StageLabel>, SystemId)>,
+    pub resources: Vec<ComponentId>,
+    /// The names of the plugins added while the plugin was built.
+    pub plugins: Vec<String>,
+}
+
+impl PluginRecord {
+    pub fn extend(&mut self, other: PluginRecord) {
+        self.stages.extend(other.stages);
+        self.systems.extend(other.systems);
+        self.resources.extend(other.resources);
+        self.plugins.extend(other.plugins);
+    }
 }
 
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
@@ -1,5 +1,8 @@
-use crate::{AppBuilder, Plugin};
-use bevy_utils::{tracing::debug, HashMap};
+use crate::{
+    plugin::{check_dependencies, check_provided_resources, PluginDependencies, PluginError},
+    AppBuilder, Plugin,
+};
+use bevy_utils::{HashMap, HashSet};
 use std::any::TypeId;
 
 pub trait PluginGroup {
@@ -96,14 +99,267 @@ impl PluginGroupBuilder {
         self
     }
 
+    /// Adds the enabled plugins to `app`.
+    ///
+    /// # Panics
+    /// Panics if the dependencies of the plugins are not satisfied. See [PluginGroupBuilder::try_finish].
     pub fn finish(self, app: &mut AppBuilder) {
+        if let Err(err) = self.try_finish(app) {
+            panic!("{}", err);
+        }
+    }
+
+    /// Adds the enabled plugins to `app`. Each plugin is built after the plugins of the group it requires or
+    /// optionally depends on, and after those that provide the resources it consumes. Otherwise plugins are built
+    /// in the order they were added to the group.
+    ///
+    /// The dependencies of all plugins are checked before any plugin is built, except for resources a plugin
+    /// declares it provides, which are checked after it is built.
+    pub fn try_finish(self, app: &mut AppBuilder) -> Result<(), PluginError> {
+        let mut plugins = Vec::with_capacity(self.order.len());
+        let mut added = HashSet::default();
         for ty in self.order.iter() {
-            if let Some(entry) = self.plugins.get(ty) {
-                if entry.enabled {
-                    debug!("added plugin: {}", entry.plugin.name());
-                    entry.plugin.build(app);
-                }
+            let entry = &self.plugins[ty];
+            if !added.insert(*ty) {
+                return Err(PluginError::DuplicatePlugin(
+                    entry.plugin.name().to_string(),
+                ));
             }
+            if entry.enabled {
+                let mut dependencies = PluginDependencies::default();
+                entry.plugin.dependencies(&mut dependencies);
+                plugins.push((&*entry.plugin, dependencies));
+            }
+        }
+
+        let group = plugins
+            .iter()
+            .map(|(plugin, dependencies)| (plugin.name(), dependencies))
+            .collect::<Vec<_>>();
+        for (name, dependencies) in group.iter() {
+            check_dependencies(&app.app, name, dependencies, &group)?;
         }
+
+        for index in sort_plugins(&group)? {
+            let (plugin, dependencies) = &plugins[index];
+            app.build_plugin(*plugin);
+            check_provided_resources(&app.app, plugin.name(), dependencies)?;
+        }
+        Ok(())
+    }
+}
+
+/// Orders the plugins of a group so that each plugin comes after the plugins it depends on, keeping the order of
+/// the group where possible. Returns the indices of the plugins in build order.
+fn sort_plugins(group: &[(&str, &PluginDependencies)]) -> Result<Vec<usize>, PluginError> {
+    let depends_on = |(_, dependencies): &(&str, &PluginDependencies), other: usize| {
+        let (other_name, other_dependencies) = group[other];
+        dependencies
+            .required
+            .iter()
+            .chain(dependencies.optional.iter())
+            .any(|dependency| *dependency == other_name)
+            || dependencies
+                .consumes
+                .iter()
+                .any(|resource| other_dependencies.provides.contains(resource))
+    };
+
+    let mut sorted = Vec::with_capacity(group.len());
+    let mut remaining = (0..group.len()).collect::<Vec<_>>();
+    while !remaining.is_empty() {
+        let next = remaining.iter().position(|&index| {
+            remaining
+                .iter()
+                .all(|&other| other == index || !depends_on(&group[index], other))
+        });
+        match next {
+            Some(position) => sorted.push(remaining.remove(position)),
+            None => {
+                return Err(PluginError::DependencyCycle(
+                    remaining
+                        .iter()
+                        .map(|&index| group[index].0.to_string())
+                        .collect(),
+                ))
+            }
+        }
+    }
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{App, AppBuilder, Plugin, PluginDependencies, PluginError, PluginGroupBuilder};
+
+    struct BuildOrder(Vec<&'static str>);
+
+    struct Config;
+
+    fn push(app: &mut AppBuilder, name: &'static str) {
+        app.world_mut()
+            .get_resource_mut::<BuildOrder>()
+            .unwrap()
+            .0
+            .push(name);
+    }
+
+    struct ConfigPlugin;
+
+    impl Plugin for ConfigPlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            push(app, "config");
+            app.insert_resource(Config);
+        }
+
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.provides_resource::<Config>();
+        }
+    }
+
+    struct BasePlugin;
+
+    impl Plugin for BasePlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            push(app, "base");
+        }
+    }
+
+    struct GamePlugin;
+
+    impl Plugin for GamePlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            push(app, "game");
+        }
+
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies
+                .requires::<BasePlugin>()
+                .consumes_resource::<Config>();
+        }
+    }
+
+    struct DebugPlugin;
+
+    impl Plugin for DebugPlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            push(app, "debug");
+        }
+
+        fn dependencies(&self, dependencies: &mut PluginDependencies) {
+            dependencies.optionally_after::<GamePlugin>();
+        }
+    }
+
+    fn app() -> AppBuilder {
+        let mut app = App::build();
+        app.insert_resource(BuildOrder(Vec::new()));
+        app
+    }
+
+    #[test]
+    fn sorted_by_dependencies() {
+        let mut app = app();
+        let mut group = PluginGroupBuilder::default();
+        group
+            .add(DebugPlugin)
+            .add(GamePlugin)
+            .add(BasePlugin)
+            .add(ConfigPlugin);
+        group.try_finish(&mut app).unwrap();
+        assert_eq!(
+            app.world().get_resource::<BuildOrder>().unwrap().0,
+            vec!["base", "config", "game", "debug"]
+        );
+        assert!(app.app.is_plugin_added(std::any::type_name::<GamePlugin>()));
+    }
+
+    #[test]
+    fn missing_dependencies() {
+        let mut app = app();
+        assert!(matches!(
+            app.try_add_plugin(GamePlugin),
+            Err(PluginError::MissingDependency { .. })
+        ));
+        app.add_plugin(BasePlugin);
+        assert!(matches!(
+            app.try_add_plugin(GamePlugin),
+            Err(PluginError::MissingResource { .. })
+        ));
+        app.add_plugin(ConfigPlugin);
+        assert!(app.try_add_plugin(GamePlugin).is_ok());
+        assert!(matches!(
+            app.try_add_plugin(GamePlugin),
+            Err(PluginError::DuplicatePlugin(_))
+        ));
+
+        let mut group = PluginGroupBuilder::default();
+        group.add(ConfigPlugin);
+        assert!(matches!(
+            group.try_finish(&mut app),
+            Err(PluginError::DuplicatePlugin(_))
+        ));
+        assert_eq!(
+            app.world().get_resource::<BuildOrder>().unwrap().0,
+            vec!["base", "config", "game"]
+        );
+    }
+
+    #[test]
+    fn boxed_plugin_not_providing_resources() {
+        struct BrokenConfigPlugin;
+
+        impl Plugin for BrokenConfigPlugin {
+            fn build(&self, app: &mut AppBuilder) {
+                push(app, "broken config");
+            }
+
+            fn dependencies(&self, dependencies: &mut PluginDependencies) {
+                dependencies.provides_resource::<Config>();
+            }
+        }
+
+        let mut app = app();
+        assert!(matches!(
+            app.try_add_boxed_plugin(Box::new(BrokenConfigPlugin)),
+            Err(PluginError::ResourceNotProvided { .. })
+        ));
+        // the plugin was built before its provided resources were checked
+        let name = std::any::type_name::<BrokenConfigPlugin>();
+        assert!(app.app.is_plugin_added(name));
+        assert!(app.app.unload_plugin(name).is_some());
+        assert!(!app.app.is_plugin_added(name));
+        assert!(app.try_add_boxed_plugin(Box::new(ConfigPlugin)).is_ok());
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        struct FirstPlugin;
+
+        impl Plugin for FirstPlugin {
+            fn build(&self, _app: &mut AppBuilder) {}
+
+            fn dependencies(&self, dependencies: &mut PluginDependencies) {
+                dependencies.requires::<OtherPlugin>();
+            }
+        }
+
+        struct OtherPlugin;
+
+        impl Plugin for OtherPlugin {
+            fn build(&self, _app: &mut AppBuilder) {}
+
+            fn dependencies(&self, dependencies: &mut PluginDependencies) {
+                dependencies.requires::<FirstPlugin>();
+            }
+        }
+
+        let mut app = app();
+        let mut group = PluginGroupBuilder::default();
+        group.add(FirstPlugin).add(OtherPlugin);
+        assert!(matches!(
+            group.try_finish(&mut app),
+            Err(PluginError::DependencyCycle(plugins)) if plugins.len() == 2
+        ));
     }
 }
@@ -3,6 +3,7 @@ mod float_ord;
 mod label;
 mod name;
 mod task_pool_options;
+mod test_app;
 mod time;
 
 pub use bytes::*;
@@ -10,6 +11,7 @@ pub use float_ord::*;
 pub use label::*;
 pub use name::*;
 pub use task_pool_options::DefaultTaskPoolOptions;
+pub use test_app::TestApp;
 pub use time::*;
 
 pub mod prelude {
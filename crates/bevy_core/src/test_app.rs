@@ -0,0 +1,185 @@
+use crate::TimeUpdateStrategy;
+use bevy_app::{App, AppBuilder, Events};
+use bevy_ecs::{component::Component, query::WorldQuery, world::World};
+use bevy_utils::Duration;
+use std::fmt::Debug;
+
+/// Drives an [App] frame by frame without a runner or a window, for tests.
+///
+/// [Time](crate::Time) advances by a fixed delta each frame instead of following the system clock, so timers and
+/// fixed timesteps behave the same on every run. Input and window events can be sent like any other event, and are
+/// read by systems during the next frame.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_core::{CorePlugin, TestApp, Time};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Debug, PartialEq)]
+/// struct Elapsed(f64);
+///
+/// fn track_elapsed(time: Res<Time>, mut elapsed: ResMut<Elapsed>) {
+///     elapsed.0 += time.delta_seconds_f64();
+/// }
+///
+/// let mut app = TestApp::new(
+///     App::build()
+///         .add_plugin(CorePlugin)
+///         .insert_resource(Elapsed(0.0))
+///         .add_system(track_elapsed.system()),
+/// );
+/// app.set_delta(bevy_utils::Duration::from_millis(500)).step(4);
+/// app.assert_resource_eq(Elapsed(2.0));
+/// ```
+pub struct TestApp {
+    pub app: App,
+}
+
+impl TestApp {
+    /// The delta [Time](crate::Time) advances by each frame unless changed with [TestApp::set_delta].
+    pub const DEFAULT_DELTA: Duration = Duration::from_nanos(16_666_667);
+
+    /// Takes the app out of `builder`. Startup systems run during the first step.
+    pub fn new(builder: &mut AppBuilder) -> Self {
+        let mut app = std::mem::take(&mut builder.app);
+        app.world
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Self::DEFAULT_DELTA));
+        TestApp { app }
+    }
+
+    /// Sets how much [Time](crate::Time) advances by each frame.
+    pub fn set_delta(&mut self, delta: Duration) -> &mut Self {
+        self.set_time_strategy(TimeUpdateStrategy::ManualDuration(delta))
+    }
+
+    pub fn set_time_strategy(&mut self, strategy: TimeUpdateStrategy) -> &mut Self {
+        self.app.world.insert_resource(strategy);
+        self
+    }
+
+    /// Updates the app `frames` times.
+    pub fn step(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.app.update();
+        }
+        self
+    }
+
+    /// Sends an event, such as an input or window event, to be read during the next step.
+    ///
+    /// # Panics
+    /// Panics if events of type `T` were not added to the app.
+    pub fn send_event<T: Component>(&mut self, event: T) -> &mut Self {
+        self.app
+            .world
+            .get_resource_mut::<Events<T>>()
+            .unwrap_or_else(|| panic!("Events<{}> were not added.", std::any::type_name::<T>()))
+            .send(event);
+        self
+    }
+
+    /// Returns the events of type `T` that are still stored, oldest first.
+    pub fn events<T: Component + Clone>(&self) -> Vec<T> {
+        let events = self.resource::<Events<T>>();
+        events.get_reader().iter(events).cloned().collect()
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    /// # Panics
+    /// Panics if the resource does not exist.
+    pub fn resource<T: Component>(&self) -> &T {
+        self.app
+            .world
+            .get_resource::<T>()
+            .unwrap_or_else(|| panic!("Resource {} does not exist.", std::any::type_name::<T>()))
+    }
+
+    /// # Panics
+    /// Panics if the resource does not exist.
+    pub fn resource_mut<T: Component>(&mut self) -> bevy_ecs::world::Mut<T> {
+        self.app
+            .world
+            .get_resource_mut::<T>()
+            .unwrap_or_else(|| panic!("Resource {} does not exist.", std::any::type_name::<T>()))
+    }
+
+    /// Returns the number of entities matching the query `Q`.
+    pub fn count<Q: WorldQuery>(&mut self) -> usize {
+        let world = &mut self.app.world;
+        world.query::<Q>().iter_mut(world).count()
+    }
+
+    pub fn assert_resource_eq<T: Component + PartialEq + Debug>(&self, expected: T) {
+        assert_eq!(
+            self.resource::<T>(),
+            &expected,
+            "unexpected value of resource {}",
+            std::any::type_name::<T>()
+        );
+    }
+
+    pub fn assert_count<Q: WorldQuery>(&mut self, expected: usize) {
+        assert_eq!(
+            self.count::<Q>(),
+            expected,
+            "unexpected number of entities matching {}",
+            std::any::type_name::<Q>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CorePlugin, FixedTimestep, TestApp};
+    use bevy_app::{App, AppExit, EventReader};
+    use bevy_ecs::{
+        schedule::SystemSet,
+        system::{Commands, IntoSystem, ResMut},
+    };
+    use bevy_utils::Duration;
+
+    #[derive(Debug, PartialEq)]
+    struct Ticks(u32);
+
+    struct Spawned;
+
+    fn tick(mut ticks: ResMut<Ticks>) {
+        ticks.0 += 1;
+    }
+
+    fn spawn_on_exit(mut commands: Commands, mut exits: EventReader<AppExit>) {
+        for _ in exits.iter() {
+            commands.spawn((Spawned,));
+        }
+    }
+
+    #[test]
+    fn fixed_timestep_is_deterministic() {
+        let mut app = TestApp::new(
+            App::build()
+                .add_plugin(CorePlugin)
+                .insert_resource(Ticks(0))
+                .add_system_set(
+                    SystemSet::new()
+                        .with_run_criteria(FixedTimestep::step(0.125))
+                        .with_system(tick.system()),
+                )
+                .add_system(spawn_on_exit.system()),
+        );
+
+        app.set_delta(Duration::from_millis(250)).step(4);
+        app.assert_resource_eq(Ticks(8));
+
+        app.send_event(AppExit).send_event(AppExit);
+        assert_eq!(app.events::<AppExit>().len(), 2);
+        app.assert_count::<&Spawned>(0);
+        app.step(1);
+        app.assert_count::<&Spawned>(2);
+    }
+}
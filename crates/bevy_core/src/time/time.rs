@@ -1,4 +1,4 @@
-use bevy_ecs::system::ResMut;
+use bevy_ecs::system::{Res, ResMut};
 use bevy_utils::{Duration, Instant};
 
 /// Tracks elapsed time since the last update and since the App has started
@@ -25,13 +25,35 @@ impl Default for Time {
     }
 }
 
+/// Determines how the time system of the [CorePlugin](crate::CorePlugin) updates [Time] each frame. Without this
+/// resource, [Time] follows the system clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeUpdateStrategy {
+    /// [Time] is updated with [Instant::now].
+    #[default]
+    Automatic,
+    /// [Time] is updated to the given instant.
+    ManualInstant(Instant),
+    /// [Time] advances by the given duration each frame, regardless of the system clock. Used for deterministic
+    /// tests and replays.
+    ManualDuration(Duration),
+}
+
 impl Time {
     pub fn update(&mut self) {
         let now = Instant::now();
         self.update_with_instant(now);
     }
 
-    pub(crate) fn update_with_instant(&mut self, instant: Instant) {
+    /// Advances by `delta` from the last update, or from startup for the first update, instead of reading the
+    /// system clock. Unlike [Time::update], the first call also sets [Time::delta].
+    pub fn advance_by(&mut self, delta: Duration) {
+        let last_update = *self.last_update.get_or_insert(self.startup);
+        self.update_with_instant(last_update + delta);
+    }
+
+    /// Updates as if the current time was `instant`.
+    pub fn update_with_instant(&mut self, instant: Instant) {
         if let Some(last_update) = self.last_update {
             self.delta = instant - last_update;
             self.delta_seconds_f64 = self.delta.as_secs_f64();
@@ -84,8 +106,12 @@ impl Time {
     }
 }
 
-pub(crate) fn time_system(mut time: ResMut<Time>) {
-    time.update();
+pub(crate) fn time_system(mut time: ResMut<Time>, strategy: Option<Res<TimeUpdateStrategy>>) {
+    match strategy.as_deref() {
+        None | Some(TimeUpdateStrategy::Automatic) => time.update(),
+        Some(TimeUpdateStrategy::ManualInstant(instant)) => time.update_with_instant(*instant),
+        Some(TimeUpdateStrategy::ManualDuration(duration)) => time.advance_by(*duration),
+    }
 }
 
 #[cfg(test)]
@@ -144,4 +170,25 @@ mod tests {
         );
         assert_eq!(time.delta_seconds(), time.delta().as_secs_f32());
     }
+
+    #[test]
+    fn advance_by_test() {
+        let start_instant = Instant::now();
+        let mut time = Time {
+            startup: start_instant,
+            ..Default::default()
+        };
+
+        time.advance_by(Duration::from_millis(250));
+        assert_eq!(time.delta(), Duration::from_millis(250));
+        assert_eq!(time.seconds_since_startup(), 0.25);
+
+        time.advance_by(Duration::from_millis(500));
+        assert_eq!(time.delta_seconds_f64(), 0.5);
+        assert_eq!(time.seconds_since_startup(), 0.75);
+        assert_eq!(
+            time.last_update(),
+            Some(start_instant + Duration::from_millis(750))
+        );
+    }
 }
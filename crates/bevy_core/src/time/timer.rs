@@ -1,6 +1,6 @@
 use crate::Stopwatch;
 use bevy_ecs::reflect::ReflectComponent;
-use bevy_reflect::Reflect;
+use bevy_reflect::{reflect_methods, Reflect, ReflectMethods};
 use bevy_utils::Duration;
 
 /// Tracks elapsed time. Enters the finished state once `duration` is reached.
@@ -10,7 +10,7 @@ use bevy_utils::Duration;
 ///
 /// Paused timers will not have elapsed time increased.
 #[derive(Clone, Debug, Default, Reflect)]
-#[reflect(Component)]
+#[reflect(Component, Methods)]
 pub struct Timer {
     stopwatch: Stopwatch,
     duration: Duration,
@@ -19,6 +19,7 @@ pub struct Timer {
     times_finished: u32,
 }
 
+#[reflect_methods]
 impl Timer {
     /// Creates a new timer with a given duration.
     ///
@@ -463,4 +464,24 @@ mod tests {
         t.tick(Duration::from_secs_f32(0.5));
         assert_eq!(t.times_finished(), 0);
     }
+
+    #[test]
+    fn reflect_methods() {
+        use bevy_reflect::FromType;
+
+        let methods = <ReflectMethods as FromType<Timer>>::from_type();
+        let mut t = Timer::from_seconds(1.0, false);
+        methods
+            .call(
+                "tick",
+                &mut t,
+                vec![Box::new(Duration::from_secs_f32(1.5)) as Box<dyn Reflect>],
+            )
+            .unwrap();
+        assert!(t.finished());
+        methods.call("reset", &mut t, Vec::new()).unwrap();
+        assert!(!t.finished());
+        let elapsed = methods.call_ref("elapsed_secs", &t, Vec::new()).unwrap();
+        assert_eq!(*elapsed.unwrap().downcast_ref::<f32>().unwrap(), 0.0);
+    }
 }
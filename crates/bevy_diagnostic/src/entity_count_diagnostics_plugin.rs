@@ -1,4 +1,4 @@
-use bevy_app::{AppBuilder, Plugin};
+use bevy_app::{AppBuilder, Plugin, PluginDependencies};
 use bevy_ecs::{
     system::{IntoExclusiveSystem, IntoSystem, ResMut},
     world::World,
@@ -15,6 +15,10 @@ impl Plugin for EntityCountDiagnosticsPlugin {
         app.add_startup_system(Self::setup_system.system())
             .add_system(Self::diagnostic_system.exclusive_system());
     }
+
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.consumes_resource::<Diagnostics>();
+    }
 }
 
 impl EntityCountDiagnosticsPlugin {
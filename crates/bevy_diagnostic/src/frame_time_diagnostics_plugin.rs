@@ -17,6 +17,10 @@ impl Plugin for FrameTimeDiagnosticsPlugin {
             .insert_resource(FrameTimeDiagnosticsState { frame_count: 0.0 })
             .add_system(Self::diagnostic_system.system());
     }
+
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.consumes_resource::<Diagnostics>();
+    }
 }
 
 impl FrameTimeDiagnosticsPlugin {
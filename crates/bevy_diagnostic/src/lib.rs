@@ -2,10 +2,14 @@ mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod schedule_profiler_diagnostics_plugin;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use schedule_profiler_diagnostics_plugin::{
+    ScheduleProfilerDiagnosticsPlugin, ScheduleProfilerDiagnosticsState,
+};
 
 use bevy_app::prelude::*;
 
@@ -17,4 +21,8 @@ impl Plugin for DiagnosticsPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<Diagnostics>();
     }
+
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.provides_resource::<Diagnostics>();
+    }
 }
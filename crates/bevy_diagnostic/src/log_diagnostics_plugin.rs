@@ -48,6 +48,10 @@ impl Plugin for LogDiagnosticsPlugin {
             app.add_system_to_stage(CoreStage::PostUpdate, Self::log_diagnostics_system.system());
         }
     }
+
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.consumes_resource::<Diagnostics>();
+    }
 }
 
 impl LogDiagnosticsPlugin {
@@ -0,0 +1,118 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::{prelude::*, AppExit};
+use bevy_ecs::{
+    schedule::{ChromeTraceWriter, ScheduleProfiler},
+    system::{IntoSystem, ResMut},
+};
+use bevy_log::warn;
+use bevy_utils::HashMap;
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+/// Adds per-system "run_time" and "wait_time" diagnostics and per-stage run time diagnostics to an App, measured
+/// by a [ScheduleProfiler]. Optionally writes every span to a chrome trace file.
+///
+/// Diagnostics are named `system/<name>/run_time`, `system/<name>/wait_time` and `stage/<label>`, and are added
+/// the first time the system or stage is seen. The trace file is closed when an [AppExit] event is sent, or when
+/// the [ScheduleProfilerDiagnosticsState] resource is dropped.
+#[derive(Default)]
+pub struct ScheduleProfilerDiagnosticsPlugin {
+    /// If set, a trace in the chrome trace event format is written to this path.
+    pub trace_path: Option<PathBuf>,
+}
+
+pub struct ScheduleProfilerDiagnosticsState {
+    ids: HashMap<String, DiagnosticId>,
+    trace: Option<ChromeTraceWriter<BufWriter<File>>>,
+}
+
+impl Plugin for ScheduleProfilerDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let trace = self.trace_path.as_ref().and_then(|path| {
+            match File::create(path).and_then(|file| ChromeTraceWriter::new(BufWriter::new(file))) {
+                Ok(trace) => Some(trace),
+                Err(err) => {
+                    warn!("failed to create trace file {}: {}", path.display(), err);
+                    None
+                }
+            }
+        });
+        app.insert_resource(ScheduleProfiler::default())
+            .insert_resource(ScheduleProfilerDiagnosticsState {
+                ids: Default::default(),
+                trace,
+            })
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system.system());
+    }
+
+    fn dependencies(&self, dependencies: &mut PluginDependencies) {
+        dependencies.consumes_resource::<Diagnostics>();
+    }
+}
+
+impl ScheduleProfilerDiagnosticsPlugin {
+    /// Aggregates the spans recorded since the last run into diagnostics, then clears the profiler. Spans of
+    /// systems that run after this one, including the stage it runs in, are measured in the next frame.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut profiler: ResMut<ScheduleProfiler>,
+        mut state: ResMut<ScheduleProfilerDiagnosticsState>,
+        mut app_exit_events: EventReader<AppExit>,
+    ) {
+        let state = &mut *state;
+        let mut measurements = HashMap::<String, f64>::default();
+        for span in profiler.system_spans() {
+            let run_time = (span.end - span.start).as_secs_f64();
+            *measurements
+                .entry(format!("system/{}/run_time", span.name))
+                .or_default() += run_time;
+            *measurements
+                .entry(format!("system/{}/wait_time", span.name))
+                .or_default() += span.wait.as_secs_f64();
+        }
+        for span in profiler.stage_spans() {
+            *measurements
+                .entry(format!("stage/{}", span.label))
+                .or_default() += (span.end - span.start).as_secs_f64();
+        }
+
+        for (name, value) in measurements {
+            let id = match state.ids.get(&name) {
+                Some(id) => *id,
+                None => {
+                    let id = DiagnosticId::default();
+                    diagnostics.add(Diagnostic::new(id, name.clone(), 20).with_suffix("s"));
+                    state.ids.insert(name, id);
+                    id
+                }
+            };
+            diagnostics.add_measurement(id, value);
+        }
+
+        if let Some(trace) = state.trace.as_mut() {
+            if let Err(err) = trace.write_spans(&profiler).and_then(|_| trace.flush()) {
+                warn!("failed to write trace: {}", err);
+                state.trace = None;
+            }
+        }
+        if app_exit_events.iter().next().is_some() {
+            state.finish_trace();
+        }
+        profiler.clear();
+    }
+}
+
+impl ScheduleProfilerDiagnosticsState {
+    fn finish_trace(&mut self) {
+        if let Some(trace) = self.trace.take() {
+            if let Err(err) = trace.finish() {
+                warn!("failed to finish trace: {}", err);
+            }
+        }
+    }
+}
+
+impl Drop for ScheduleProfilerDiagnosticsState {
+    fn drop(&mut self) {
+        self.finish_trace();
+    }
+}
@@ -1,25 +1,288 @@
+use crossbeam_channel::{Receiver, TryRecvError};
 use libloading::{Library, Symbol};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
-use bevy_app::{AppBuilder, CreatePlugin, Plugin};
+use bevy_app::{App, AppBuilder, CreatePlugin, Plugin, PluginError};
+use bevy_ecs::reflect::ReflectResource;
+use bevy_reflect::{Reflect, TypeRegistryArc};
+use bevy_utils::tracing::{info, warn};
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// Dynamically links a plugin a the given path. The plugin must export the [CreatePlugin] function.
 pub fn dynamically_load_plugin(path: &str) -> (Library, Box<dyn Plugin>) {
+    try_dynamically_load_plugin(path).unwrap()
+}
+
+fn try_dynamically_load_plugin<P: AsRef<OsStr>>(
+    path: P,
+) -> Result<(Library, Box<dyn Plugin>), libloading::Error> {
+    let lib = unsafe { Library::new(path)? };
+    let plugin = create_plugin(&lib)?;
+    Ok((lib, plugin))
+}
+
+/// Creates a new instance of the plugin exported by `lib`.
+fn create_plugin(lib: &Library) -> Result<Box<dyn Plugin>, libloading::Error> {
     unsafe {
-        let lib = Library::new(path).unwrap();
-        let func: Symbol<CreatePlugin> = lib.get(b"_create_plugin").unwrap();
-        let plugin = Box::from_raw(func());
-        (lib, plugin)
+        let func: Symbol<CreatePlugin> = lib.get(b"_create_plugin")?;
+        Ok(Box::from_raw(func()))
     }
 }
 
 pub trait DynamicPluginExt {
+    /// Loads the plugin at `path` and adds it to the app. The library stays loaded until the process exits.
     fn load_plugin(&mut self, path: &str) -> &mut Self;
+
+    /// Loads the plugin at `path` and adds it to the app, then reloads it whenever the library file changes.
+    ///
+    /// The library is copied to a temporary file before it is loaded, so that it can be rebuilt while the app runs.
+    /// The directory of the library is watched for changes. At the start of the first update after the file
+    /// changes, the new library is loaded, the old plugin is unloaded with [App::unload_plugin] and the new plugin
+    /// is built. If the new plugin's [dependencies](Plugin::dependencies) are not satisfied, a warning is logged and
+    /// the old plugin is built again. Resources the plugin added keep their state across the reload if their type
+    /// is registered with `#[reflect(Resource)]`; other resources are created again by the new plugin.
+    ///
+    /// Hot reloading has the same requirements as [DynamicPluginExt::load_plugin]: the library must be built with
+    /// the same compiler and Bevy version as the app. In addition:
+    /// * only what the plugin added through [AppBuilder] is unloaded, see [bevy_app::PluginRecord]. Components the
+    ///   plugin's systems added to entities are left in place, and must not be of types defined in the plugin.
+    /// * the plugin must register its reflected types again when it is built, as the registrations of the old
+    ///   library point to code from the old library.
+    ///
+    /// # Safety
+    /// Old libraries are never unloaded, as the world may still hold values whose drop glue, vtables or
+    /// function pointers live in them, such as components added by the plugin's systems or type registrations.
+    /// Each reload therefore keeps one more library loaded until the process exits.
+    fn load_hot_reloadable_plugin(&mut self, path: &str) -> &mut Self;
 }
 
 impl DynamicPluginExt for AppBuilder {
     fn load_plugin(&mut self, path: &str) -> &mut Self {
-        let (_lib, plugin) = dynamically_load_plugin(path);
-        plugin.build(self);
+        let (lib, plugin) = dynamically_load_plugin(path);
+        self.add_boxed_plugin(plugin);
+        // The plugin's systems and resources run code from the library for as long as the app exists.
+        std::mem::forget(lib);
+        self
+    }
+
+    fn load_hot_reloadable_plugin(&mut self, path: &str) -> &mut Self {
+        let path = PathBuf::from(path);
+        let (library_copy, library, plugin) = load_copy(&path, 0)
+            .unwrap_or_else(|err| panic!("failed to load plugin {}: {}", path.display(), err));
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut watcher: RecommendedWatcher = Watcher::new_immediate(move |res| {
+            sender.send(res).expect("Watch event send failure.");
+        })
+        .expect("Failed to create filesystem watcher.");
+        // Builds usually replace the library instead of writing to it, so its directory is watched instead.
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        watcher
+            .watch(directory, RecursiveMode::NonRecursive)
+            .unwrap_or_else(|err| panic!("failed to watch {}: {}", directory.display(), err));
+        let mut reloader = HotReloader {
+            plugin_name: plugin.name().to_string(),
+            path,
+            watcher,
+            receiver,
+            generation: 0,
+            libraries: vec![library],
+            library_copy,
+        };
+        self.add_boxed_plugin(plugin);
+        self.app
+            .add_update_hook(move |app| reloader.reload_if_changed(app));
         self
     }
 }
+
+/// The state of a hot reloadable plugin, owned by the update hook that reloads it.
+struct HotReloader {
+    path: PathBuf,
+    // Events stop being sent once the watcher is dropped.
+    #[allow(dead_code)]
+    watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    generation: usize,
+    plugin_name: String,
+    /// Every library loaded so far, the current one last. They are never unloaded, see
+    /// [DynamicPluginExt::load_hot_reloadable_plugin].
+    libraries: Vec<Library>,
+    library_copy: PathBuf,
+}
+
+impl HotReloader {
+    fn reload_if_changed(&mut self, app: &mut App) {
+        if !self.changed() {
+            return;
+        }
+        // If the library is still being written, loading fails and is tried again when it changes again.
+        self.generation += 1;
+        let (library_copy, library, plugin) = match load_copy(&self.path, self.generation) {
+            Ok(loaded) => loaded,
+            Err(err) => {
+                warn!("failed to reload plugin {}: {}", self.path.display(), err);
+                return;
+            }
+        };
+
+        let state = save_state(app, &self.plugin_name);
+        app.unload_plugin(&self.plugin_name);
+        let plugin_name = plugin.name().to_string();
+        let mut app_builder = AppBuilder {
+            app: std::mem::take(app),
+        };
+        let result = app_builder.try_add_boxed_plugin(plugin).map(|_| ());
+        if let Err(err) = result {
+            warn!(
+                "failed to reload plugin {}, keeping the previous version: {}",
+                self.path.display(),
+                err
+            );
+            if let PluginError::ResourceNotProvided { .. } = err {
+                app_builder.app.unload_plugin(&plugin_name);
+            }
+            self.restore_previous(&mut app_builder);
+            *app = app_builder.app;
+            restore_state(app, state);
+
+            // The new plugin may have been built, so its library stays loaded too. The current library stays last.
+            let current = self.libraries.len() - 1;
+            self.libraries.insert(current, library);
+            if let Err(err) = fs::remove_file(&library_copy) {
+                warn!("failed to remove {}: {}", library_copy.display(), err);
+            }
+            return;
+        }
+        *app = app_builder.app;
+        restore_state(app, state);
+        self.plugin_name = plugin_name;
+
+        // The world may still reference code from the old library, so it stays loaded.
+        self.libraries.push(library);
+        let old_copy = std::mem::replace(&mut self.library_copy, library_copy);
+        if let Err(err) = fs::remove_file(&old_copy) {
+            warn!("failed to remove {}: {}", old_copy.display(), err);
+        }
+        info!("reloaded plugin {}", self.plugin_name);
+    }
+
+    /// Returns true if the library file changed since the last call.
+    fn changed(&self) -> bool {
+        let mut changed = false;
+        loop {
+            let event = match self.receiver.try_recv() {
+                Ok(Ok(event)) => event,
+                Ok(Err(err)) => {
+                    warn!("failed to watch plugin {}: {}", self.path.display(), err);
+                    continue;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+            };
+            if let EventKind::Create(_) | EventKind::Modify(_) = event.kind {
+                changed |= event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name() == self.path.file_name());
+            }
+        }
+        changed
+    }
+
+    /// Builds the plugin of the current library again, after building the plugin of a new library failed.
+    fn restore_previous(&self, app_builder: &mut AppBuilder) {
+        let result = create_plugin(self.libraries.last().unwrap())
+            .map_err(|err| err.to_string())
+            .and_then(|plugin| {
+                app_builder
+                    .try_add_boxed_plugin(plugin)
+                    .map(|_| ())
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!("failed to restore plugin {}: {}", self.plugin_name, err);
+        }
+    }
+}
+
+impl Drop for HotReloader {
+    fn drop(&mut self) {
+        // The reloader may be dropped before the world, which can still reference code from any of the libraries.
+        std::mem::forget(std::mem::take(&mut self.libraries));
+        let _ = fs::remove_file(&self.library_copy);
+    }
+}
+
+/// Copies the library at `path` to a temporary file unique to this process and `generation`, then loads it.
+fn load_copy(
+    path: &Path,
+    generation: usize,
+) -> Result<(PathBuf, Library, Box<dyn Plugin>), String> {
+    let mut file_name = path
+        .file_stem()
+        .ok_or_else(|| "not a file".to_string())?
+        .to_os_string();
+    file_name.push(format!("-{}-{}", std::process::id(), generation));
+    let mut copy = std::env::temp_dir().join(file_name);
+    if let Some(extension) = path.extension() {
+        copy.set_extension(extension);
+    }
+    fs::copy(path, &copy).map_err(|err| err.to_string())?;
+    match try_dynamically_load_plugin(&copy) {
+        Ok((library, plugin)) => Ok((copy, library, plugin)),
+        Err(err) => {
+            let _ = fs::remove_file(&copy);
+            Err(err.to_string())
+        }
+    }
+}
+
+/// Returns the reflected values of the resources the plugin added, by type name.
+fn save_state(app: &App, plugin_name: &str) -> Vec<(String, Box<dyn Reflect>)> {
+    let (record, type_registry) = match (
+        app.plugin_record(plugin_name),
+        app.world.get_resource::<TypeRegistryArc>(),
+    ) {
+        (Some(record), Some(type_registry)) => (record, type_registry.read()),
+        _ => return Vec::new(),
+    };
+    record
+        .resources
+        .iter()
+        .filter_map(|component_id| {
+            let name = app.world.components().get_info(*component_id)?.name();
+            let reflect_resource = type_registry
+                .get_with_name(name)?
+                .data::<ReflectResource>()?;
+            let value = reflect_resource.reflect_resource(&app.world)?.clone_value();
+            Some((name.to_string(), value))
+        })
+        .collect()
+}
+
+fn restore_state(app: &mut App, state: Vec<(String, Box<dyn Reflect>)>) {
+    let type_registry = match app.world.get_resource::<TypeRegistryArc>() {
+        Some(type_registry) => type_registry.clone(),
+        None => return,
+    };
+    let type_registry = type_registry.read();
+    for (name, value) in state {
+        match type_registry
+            .get_with_name(&name)
+            .and_then(|registration| registration.data::<ReflectResource>())
+        {
+            Some(reflect_resource) => reflect_resource.insert_resource(&mut app.world, &*value),
+            None => warn!(
+                "resource {} is no longer registered with #[reflect(Resource)], its state was lost",
+                name
+            ),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 pub use bevy_ecs_macros::Bundle;
 
 use crate::{
-    component::{Component, ComponentFlags, ComponentId, Components, StorageType, TypeInfo},
+    archetype::ComponentStatus,
+    component::{Component, ComponentId, ComponentTicks, Components, StorageType, TypeInfo},
     entity::Entity,
     storage::{SparseSetIndex, SparseSets, Table},
 };
@@ -123,30 +124,64 @@ impl BundleInfo {
         entity: Entity,
         table: &Table,
         table_row: usize,
-        bundle_flags: &[ComponentFlags],
+        bundle_status: &[ComponentStatus],
         bundle: T,
+        change_tick: u32,
     ) {
         // NOTE: get_components calls this closure on each component in "bundle order". bundle_info.component_ids are also in "bundle order"
         let mut bundle_component = 0;
         bundle.get_components(|component_ptr| {
-            // SAFE: component_id was initialized by get_dynamic_bundle_info
-            let component_id = *self.component_ids.get_unchecked(bundle_component);
-            let flags = *bundle_flags.get_unchecked(bundle_component);
-            match self.storage_types[bundle_component] {
-                StorageType::Table => {
-                    let column = table.get_column(component_id).unwrap();
-                    column.set_unchecked(table_row, component_ptr);
-                    column.get_flags_unchecked_mut(table_row).insert(flags);
-                }
-                StorageType::SparseSet => {
-                    let sparse_set = sparse_sets.get_mut(component_id).unwrap();
-                    sparse_set.insert(entity, component_ptr, flags);
-                }
-            }
+            self.write_component(
+                sparse_sets,
+                entity,
+                table,
+                table_row,
+                bundle_status,
+                bundle_component,
+                component_ptr,
+                change_tick,
+            );
             bundle_component += 1;
         });
     }
 
+    /// Moves the value at `component_ptr` into the storage of the component at index `bundle_component` of this bundle.
+    /// # Safety
+    /// table row must exist, entity must be valid, `bundle_component` must be in bounds and `component_ptr` must point
+    /// to a value of that component, which the caller must not drop afterwards
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) unsafe fn write_component(
+        &self,
+        sparse_sets: &mut SparseSets,
+        entity: Entity,
+        table: &Table,
+        table_row: usize,
+        bundle_status: &[ComponentStatus],
+        bundle_component: usize,
+        component_ptr: *mut u8,
+        change_tick: u32,
+    ) {
+        // SAFE: component_id was initialized by get_dynamic_bundle_info
+        let component_id = *self.component_ids.get_unchecked(bundle_component);
+        match self.storage_types[bundle_component] {
+            StorageType::Table => {
+                let column = table.get_column(component_id).unwrap();
+                column.set_unchecked(table_row, component_ptr);
+                let column_ticks = column.get_ticks_unchecked_mut(table_row);
+                match bundle_status.get_unchecked(bundle_component) {
+                    ComponentStatus::Added => *column_ticks = ComponentTicks::new(change_tick),
+                    ComponentStatus::Mutated => column_ticks.set_changed(change_tick),
+                }
+                column.mark_changed(change_tick, change_tick);
+            }
+            StorageType::SparseSet => {
+                let sparse_set = sparse_sets.get_mut(component_id).unwrap();
+                sparse_set.insert(entity, component_ptr, change_tick);
+            }
+        }
+    }
+
     #[inline]
     pub fn id(&self) -> BundleId {
         self.id
@@ -167,6 +202,7 @@ impl BundleInfo {
 pub struct Bundles {
     bundle_infos: Vec<BundleInfo>,
     bundle_ids: HashMap<TypeId, BundleId>,
+    dynamic_bundle_ids: HashMap<Vec<ComponentId>, BundleId>,
 }
 
 impl Bundles {
@@ -196,6 +232,29 @@ impl Bundles {
         // SAFE: index either exists, or was initialized
         unsafe { self.bundle_infos.get_unchecked(id.0) }
     }
+
+    /// Initializes a bundle made of the given components, which don't need to have a Rust type. This is used to
+    /// insert and remove components by [ComponentId].
+    /// # Panics
+    /// Panics if `component_ids` contains duplicates or components that are not registered in `components`
+    pub(crate) fn init_dynamic_info<'a>(
+        &'a mut self,
+        components: &Components,
+        component_ids: &[ComponentId],
+    ) -> &'a BundleInfo {
+        let bundle_infos = &mut self.bundle_infos;
+        let id = self
+            .dynamic_bundle_ids
+            .entry(component_ids.to_vec())
+            .or_insert_with(|| {
+                let id = BundleId(bundle_infos.len());
+                let bundle_info = initialize_dynamic_bundle(component_ids, id, components);
+                bundle_infos.push(bundle_info);
+                id
+            });
+        // SAFE: index either exists, or was initialized
+        unsafe { self.bundle_infos.get_unchecked(id.0) }
+    }
 }
 
 fn initialize_bundle(
@@ -228,3 +287,35 @@ fn initialize_bundle(
         storage_types,
     }
 }
+
+fn initialize_dynamic_bundle(
+    component_ids: &[ComponentId],
+    id: BundleId,
+    components: &Components,
+) -> BundleInfo {
+    let storage_types = component_ids
+        .iter()
+        .map(|component_id| {
+            components
+                .get_info(*component_id)
+                .unwrap_or_else(|| panic!("Component {:?} does not exist", component_id))
+                .storage_type()
+        })
+        .collect();
+
+    let mut deduped = component_ids.to_vec();
+    deduped.sort();
+    deduped.dedup();
+    if deduped.len() != component_ids.len() {
+        panic!(
+            "Dynamic bundle {:?} has duplicate components",
+            component_ids
+        );
+    }
+
+    BundleInfo {
+        id,
+        component_ids: component_ids.to_vec(),
+        storage_types,
+    }
+}
@@ -2,12 +2,13 @@ mod type_info;
 
 pub use type_info::*;
 
-use crate::storage::SparseSetIndex;
-use bitflags::bitflags;
+use crate::{entity::Entity, storage::SparseSetIndex, world::World};
 use std::{
     alloc::Layout,
     any::{Any, TypeId},
     collections::hash_map::Entry,
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
 };
 use thiserror::Error;
 
@@ -26,6 +27,42 @@ impl Default for StorageType {
     }
 }
 
+/// A function that runs synchronously, with full [World] access, when a component is added to or removed
+/// from an entity. It receives the affected entity and the id of the component that triggered it.
+pub type ComponentHook = fn(&mut World, Entity, ComponentId);
+
+/// The lifecycle hooks of a component. See [ComponentDescriptor::with_on_add],
+/// [ComponentDescriptor::with_on_insert] and [ComponentDescriptor::with_on_remove].
+#[derive(Clone, Copy, Default)]
+pub struct ComponentHooks {
+    /// Runs after the component is inserted on an entity that did not already have it.
+    pub on_add: Option<ComponentHook>,
+    /// Runs after the component is inserted on an entity, whether it replaced an existing value or not.
+    /// This runs after `on_add`.
+    pub on_insert: Option<ComponentHook>,
+    /// Runs before the component is removed from an entity, either explicitly or because the entity
+    /// was despawned. The component can still be read from the entity while this runs.
+    pub on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Returns true if no hooks are set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.on_add.is_none() && self.on_insert.is_none() && self.on_remove.is_none()
+    }
+}
+
+impl fmt::Debug for ComponentHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentHooks")
+            .field("on_add", &self.on_add.is_some())
+            .field("on_insert", &self.on_insert.is_some())
+            .field("on_remove", &self.on_remove.is_some())
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct ComponentInfo {
     name: String,
@@ -36,6 +73,7 @@ pub struct ComponentInfo {
     layout: Layout,
     drop: unsafe fn(*mut u8),
     storage_type: StorageType,
+    hooks: ComponentHooks,
 }
 
 impl ComponentInfo {
@@ -74,6 +112,16 @@ impl ComponentInfo {
         self.is_send_and_sync
     }
 
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    #[inline]
+    pub fn hooks_mut(&mut self) -> &mut ComponentHooks {
+        &mut self.hooks
+    }
+
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo {
             id,
@@ -83,6 +131,7 @@ impl ComponentInfo {
             is_send_and_sync: descriptor.is_send_and_sync,
             drop: descriptor.drop,
             layout: descriptor.layout,
+            hooks: descriptor.hooks,
         }
     }
 }
@@ -121,6 +170,7 @@ pub struct ComponentDescriptor {
     type_id: Option<TypeId>,
     layout: Layout,
     drop: unsafe fn(*mut u8),
+    hooks: ComponentHooks,
 }
 
 impl ComponentDescriptor {
@@ -132,9 +182,56 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: TypeInfo::drop_ptr::<T>,
+            hooks: ComponentHooks::default(),
+        }
+    }
+
+    /// Creates a descriptor for a component that is only known at runtime, such as a component defined by a
+    /// scripting language. Dynamic components have no [TypeId], so they can only be accessed by [ComponentId].
+    /// Registering the same name twice creates two distinct components.
+    /// # Safety
+    /// Values of this component must be `Send + Sync`. `layout` must match the values that are inserted
+    /// and `drop` must be safe to call on a pointer to any of those values.
+    pub unsafe fn new_dynamic(
+        name: impl Into<String>,
+        storage_type: StorageType,
+        layout: Layout,
+        drop: unsafe fn(*mut u8),
+    ) -> Self {
+        Self {
+            name: name.into(),
+            storage_type,
+            is_send_and_sync: true,
+            type_id: None,
+            layout,
+            drop,
+            hooks: ComponentHooks::default(),
         }
     }
 
+    /// Sets the hook that runs after this component is inserted on an entity that did not already have it.
+    pub fn with_on_add(mut self, hook: ComponentHook) -> Self {
+        self.hooks.on_add = Some(hook);
+        self
+    }
+
+    /// Sets the hook that runs after this component is inserted on an entity.
+    pub fn with_on_insert(mut self, hook: ComponentHook) -> Self {
+        self.hooks.on_insert = Some(hook);
+        self
+    }
+
+    /// Sets the hook that runs before this component is removed from an entity.
+    pub fn with_on_remove(mut self, hook: ComponentHook) -> Self {
+        self.hooks.on_remove = Some(hook);
+        self
+    }
+
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
     #[inline]
     pub fn storage_type(&self) -> StorageType {
         self.storage_type
@@ -160,6 +257,7 @@ impl From<TypeInfo> for ComponentDescriptor {
             type_id: Some(type_info.type_id()),
             drop: type_info.drop(),
             layout: type_info.layout(),
+            hooks: ComponentHooks::default(),
         }
     }
 }
@@ -223,6 +321,26 @@ impl Components {
         self.components.get(id.0)
     }
 
+    /// Removes the hooks of every component, so that they can be put back with [Components::restore_hooks].
+    pub(crate) fn take_hooks(&mut self) -> Vec<ComponentHooks> {
+        self.components
+            .iter_mut()
+            .map(|info| std::mem::take(&mut info.hooks))
+            .collect()
+    }
+
+    /// Puts back hooks removed with [Components::take_hooks].
+    pub(crate) fn restore_hooks(&mut self, hooks: Vec<ComponentHooks>) {
+        for (info, hooks) in self.components.iter_mut().zip(hooks) {
+            info.hooks = hooks;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_info_mut(&mut self, id: ComponentId) -> Option<&mut ComponentInfo> {
+        self.components.get_mut(id.0)
+    }
+
     /// # Safety
     /// `id` must be a valid [ComponentId]
     #[inline]
@@ -288,9 +406,97 @@ impl Components {
     }
 }
 
-bitflags! {
-    pub struct ComponentFlags: u8 {
-        const ADDED = 1;
-        const MUTATED = 2;
+/// The number of ticks a change can be behind the world's change tick before [check_tick] clamps it.
+/// Older changes are still detected as changes, so systems that run less often than this still see them.
+pub(crate) const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
+/// The number of change ticks after which ticks stored in the [World] are checked for overflow.
+pub(crate) const CHECK_TICK_THRESHOLD: u32 = 518_400_000;
+
+/// Records when a component was added and when it was last changed, in [World] change ticks.
+#[derive(Copy, Clone, Debug)]
+pub struct ComponentTicks {
+    pub(crate) added: u32,
+    pub(crate) changed: u32,
+}
+
+impl ComponentTicks {
+    #[inline]
+    pub(crate) fn new(change_tick: u32) -> Self {
+        Self {
+            added: change_tick,
+            changed: change_tick,
+        }
+    }
+
+    /// Returns true if the component was added after `last_change_tick`. `change_tick` is the current change tick.
+    #[inline]
+    pub fn is_added(&self, last_change_tick: u32, change_tick: u32) -> bool {
+        is_tick_newer(self.added, last_change_tick, change_tick)
+    }
+
+    /// Returns true if the component was added or mutated after `last_change_tick`. `change_tick` is the current
+    /// change tick.
+    #[inline]
+    pub fn is_changed(&self, last_change_tick: u32, change_tick: u32) -> bool {
+        is_tick_newer(self.changed, last_change_tick, change_tick)
+    }
+
+    #[inline]
+    pub fn added_tick(&self) -> u32 {
+        self.added
+    }
+
+    #[inline]
+    pub fn changed_tick(&self) -> u32 {
+        self.changed
+    }
+
+    /// Marks the component as changed at `change_tick`.
+    #[inline]
+    pub fn set_changed(&mut self, change_tick: u32) {
+        self.changed = change_tick;
+    }
+
+    pub(crate) fn check_ticks(&mut self, change_tick: u32) {
+        check_tick(&mut self.added, change_tick);
+        check_tick(&mut self.changed, change_tick);
+    }
+}
+
+/// Raises `summary` to `tick` if `tick` is newer. Table columns keep such a summary of the newest `changed` tick of
+/// their components, which lets change detection filters skip tables without any changes.
+///
+/// Systems with disjoint queries can write the same column in parallel at different change ticks, so the summary is
+/// only replaced if it is still older than `tick`.
+#[inline]
+pub(crate) fn mark_changed_summary(summary: &AtomicU32, tick: u32, change_tick: u32) {
+    let mut summary_tick = summary.load(Ordering::Relaxed);
+    while summary_tick != tick && is_tick_newer(tick, summary_tick, change_tick) {
+        match summary.compare_exchange_weak(
+            summary_tick,
+            tick,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => return,
+            Err(current) => summary_tick = current,
+        }
+    }
+}
+
+/// Ticks are compared by their distance to `change_tick`, so they keep working when the change tick wraps around.
+#[inline]
+pub(crate) fn is_tick_newer(tick: u32, last_change_tick: u32, change_tick: u32) -> bool {
+    let tick_age = change_tick.wrapping_sub(tick);
+    let last_change_age = change_tick.wrapping_sub(last_change_tick);
+    tick_age < last_change_age
+}
+
+/// Clamps `last_change_tick` to at most [MAX_CHANGE_AGE] ticks behind `change_tick`. Without this, a tick that is
+/// never updated would eventually look newer than `change_tick` once the change tick wraps around.
+pub(crate) fn check_tick(last_change_tick: &mut u32, change_tick: u32) {
+    let tick_age = change_tick.wrapping_sub(*last_change_tick);
+    if tick_age > MAX_CHANGE_AGE {
+        *last_change_tick = change_tick.wrapping_sub(MAX_CHANGE_AGE);
     }
 }
@@ -327,6 +327,22 @@ impl Entities {
             .map_or(true, |meta| meta.generation == entity.generation)
     }
 
+    /// Returns true if `entity` was reserved with `reserve_entity` or `reserve_entities` and has not been flushed yet.
+    pub fn is_reserved(&self, entity: Entity) -> bool {
+        let free_cursor = self.free_cursor.load(Ordering::Relaxed);
+        if (entity.id as usize) < self.meta.len() {
+            // ids handed out from the freelist are the ones past `free_cursor` in `pending`
+            let reserved_start = free_cursor.max(0) as usize;
+            self.meta[entity.id as usize].generation == entity.generation
+                && reserved_start < self.pending.len()
+                && self.pending[reserved_start..].contains(&entity.id)
+        } else {
+            // new ids are handed out past the end of `meta`, as far as `free_cursor` went negative
+            entity.generation == 0
+                && (entity.id as i64) < self.meta.len() as i64 - free_cursor.min(0)
+        }
+    }
+
     pub fn clear(&mut self) {
         self.meta.clear();
         self.pending.clear();
@@ -429,6 +445,87 @@ impl Entities {
         }
     }
 
+    /// Returns the generation of every entity id along with the ids that are free to be reused, in the order
+    /// they are handed out again. Reserved entities that have not been flushed yet are treated as free.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) fn allocator_state(&self) -> (Vec<u32>, Vec<u32>) {
+        let generations = self.meta.iter().map(|meta| meta.generation).collect();
+        (generations, self.pending.clone())
+    }
+
+    /// Resets the allocator to a state returned by `allocator_state`. Entities that are alive both now and in that
+    /// state keep their location, every other entity that is alive in that state is initialized using the supplied
+    /// function. Entities that are alive now but not in that state must already have been freed.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) fn restore_allocator_state(
+        &mut self,
+        generations: &[u32],
+        pending: &[u32],
+        mut init: impl FnMut(Entity, &mut EntityLocation),
+    ) {
+        self.verify_flushed();
+
+        let mut was_free = vec![false; self.meta.len()];
+        for &id in self.pending.iter() {
+            was_free[id as usize] = true;
+        }
+        let mut is_free = vec![false; generations.len()];
+        for &id in pending.iter() {
+            is_free[id as usize] = true;
+        }
+
+        let old_meta = std::mem::take(&mut self.meta);
+        let mut is_kept = vec![false; generations.len()];
+        for (id, &generation) in generations.iter().enumerate() {
+            self.meta.push(match old_meta.get(id) {
+                Some(meta) if !was_free[id] && !is_free[id] && meta.generation == generation => {
+                    is_kept[id] = true;
+                    *meta
+                }
+                _ => EntityMeta {
+                    generation,
+                    ..EntityMeta::EMPTY
+                },
+            });
+        }
+        self.pending.clear();
+        self.pending.extend_from_slice(pending);
+        *self.free_cursor.get_mut() = self.pending.len() as i64;
+        self.len = (self.meta.len() - self.pending.len()) as u32;
+
+        for (id, meta) in self.meta.iter_mut().enumerate() {
+            if !is_free[id] && !is_kept[id] {
+                init(
+                    Entity {
+                        id: id as u32,
+                        generation: meta.generation,
+                    },
+                    &mut meta.location,
+                );
+            }
+        }
+    }
+
+    /// Resets the generations of the free entities and the order they are handed out again to a state returned by
+    /// `allocator_state`, undoing allocations made since `restore_allocator_state` was called with the same state.
+    /// Entities allocated in the meantime must already have been freed.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) fn restore_free_list(&mut self, generations: &[u32], pending: &[u32]) {
+        self.verify_flushed();
+
+        self.meta.truncate(generations.len());
+        for &id in pending.iter() {
+            self.meta[id as usize] = EntityMeta {
+                generation: generations[id as usize],
+                ..EntityMeta::EMPTY
+            };
+        }
+        self.pending.clear();
+        self.pending.extend_from_slice(pending);
+        *self.free_cursor.get_mut() = self.pending.len() as i64;
+        self.len = (self.meta.len() - self.pending.len()) as u32;
+    }
+
     #[inline]
     pub fn len(&self) -> u32 {
         self.len
@@ -486,4 +583,25 @@ mod tests {
         e.flush(|_, _| {});
         assert_eq!(e.len(), 1);
     }
+
+    #[test]
+    fn is_reserved() {
+        let mut e = Entities::default();
+        let alive = e.alloc();
+        let freed = e.alloc();
+        e.free(freed);
+
+        let reused = e.reserve_entity();
+        let new = e.reserve_entity();
+        assert_eq!(reused.id(), freed.id());
+        assert!(e.is_reserved(reused));
+        assert!(e.is_reserved(new));
+        assert!(!e.is_reserved(alive));
+        assert!(!e.is_reserved(freed));
+        assert!(!e.is_reserved(Entity::new(new.id() + 1)));
+
+        e.flush(|_, _| {});
+        assert!(!e.is_reserved(reused));
+        assert!(!e.is_reserved(new));
+    }
 }
@@ -5,6 +5,7 @@ pub mod entity;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relation;
 pub mod schedule;
 pub mod storage;
 pub mod system;
@@ -12,18 +13,22 @@ pub mod world;
 
 pub mod prelude {
     #[cfg(feature = "bevy_reflect")]
-    pub use crate::reflect::ReflectComponent;
+    pub use crate::reflect::{ReflectComponent, ReflectResource};
     pub use crate::{
         bundle::Bundle,
         entity::Entity,
-        query::{Added, Changed, Flags, Mutated, Or, QueryState, With, WithBundle, Without},
+        query::{
+            Added, ChangeTrackers, Changed, Mutated, Or, QueryState, With, WithBundle, Without,
+        },
+        relation::Relation,
         schedule::{
-            AmbiguitySetLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            Schedule, Stage, StageLabel, State, StateStage, SystemLabel, SystemStage,
+            apply_buffers, AmbiguitySetLabel, ExclusiveSystemDescriptorCoercion,
+            ParallelSystemDescriptorCoercion, RunCriteriaCombinators, RunCriteriaLabel, Schedule,
+            Stage, StageLabel, State, StateStage, SystemLabel, SystemStage,
         },
         system::{
             Commands, In, IntoChainSystem, IntoExclusiveSystem, IntoSystem, Local, NonSend,
-            NonSendMut, Query, QuerySet, RemovedComponents, Res, ResMut, System,
+            NonSendMut, ParallelCommands, Query, QuerySet, RemovedComponents, Res, ResMut, System,
         },
         world::{FromWorld, Mut, World},
     };
@@ -35,7 +40,9 @@ mod tests {
         bundle::Bundle,
         component::{Component, ComponentDescriptor, StorageType, TypeInfo},
         entity::Entity,
-        query::{Added, Changed, FilterFetch, Flags, Mutated, Or, With, Without, WorldQuery},
+        query::{
+            Added, ChangeTrackers, Changed, FilterFetch, Mutated, Or, With, Without, WorldQuery,
+        },
         world::{Mut, World},
     };
     use bevy_tasks::TaskPool;
@@ -708,6 +715,8 @@ mod tests {
         let e2 = world.spawn().insert_bundle((A(0), B(0))).id();
         let e3 = world.spawn().insert_bundle((A(0), B(0))).id();
         world.spawn().insert_bundle((A(0), B));
+        // components that are added and mutated since the last clear only count as added
+        world.clear_trackers();
 
         for (i, mut a) in world.query::<&mut A>().iter_mut(&mut world).enumerate() {
             if i % 2 == 0 {
@@ -766,6 +775,7 @@ mod tests {
         assert!(get_filtered::<Mutated<A>>(&mut world).is_empty());
         assert_eq!(get_filtered::<Added<A>>(&mut world), vec![e4]);
 
+        world.clear_trackers();
         world.entity_mut(e4).insert(A(1));
         assert_eq!(get_filtered::<Mutated<A>>(&mut world), vec![e4]);
 
@@ -801,12 +811,38 @@ mod tests {
         assert_eq!(e_mut.get::<A>().unwrap(), &A(0));
     }
 
+    #[test]
+    fn reserve_entities_from_shared_world() {
+        let mut world = World::default();
+        let despawned = world.spawn().id();
+        world.despawn(despawned);
+        let reserved = world.reserve_entities(3).collect::<Vec<_>>();
+        let e = world.reserve_entity();
+        assert!(world.get_entity(e).is_none());
+
+        // reserved entities are materialized without components once they are accessed mutably
+        world.entity_mut(e).insert(A(0));
+        assert_eq!(world.get::<A>(e).unwrap(), &A(0));
+        for entity in reserved.iter() {
+            assert!(world.get_entity(*entity).is_some());
+            assert!(world
+                .entity(*entity)
+                .archetype()
+                .components()
+                .next()
+                .is_none());
+        }
+        assert_eq!(world.entities().len(), 4);
+        assert!(reserved.iter().all(|entity| *entity != despawned));
+    }
+
     #[test]
     fn multiple_mutated_query() {
         let mut world = World::default();
         world.spawn().insert_bundle((A(0), B(0))).id();
         let e2 = world.spawn().insert_bundle((A(0), B(0))).id();
         world.spawn().insert_bundle((A(0), B(0)));
+        world.clear_trackers();
 
         for mut a in world.query::<&mut A>().iter_mut(&mut world) {
             a.0 += 1;
@@ -830,6 +866,7 @@ mod tests {
         let e2 = world.spawn().insert_bundle((A(0), B(0))).id();
         let _e3 = world.spawn().insert_bundle((A(0), B(0))).id();
         let e4 = world.spawn().insert(A(0)).id(); // ensure filters work for archetypes with only one of the Or filter items
+        world.clear_trackers();
 
         *world.entity_mut(e1).get_mut::<A>().unwrap() = A(1);
         *world.entity_mut(e2).get_mut::<B>().unwrap() = B(1);
@@ -1060,30 +1097,28 @@ mod tests {
     }
 
     #[test]
-    fn flags_query() {
+    fn change_trackers_query() {
         let mut world = World::default();
         let e1 = world.spawn().insert_bundle((A(0), B(0))).id();
         world.spawn().insert(B(0));
 
-        let mut flags_query = world.query::<Option<Flags<A>>>();
-        let flags = flags_query.iter(&world).collect::<Vec<_>>();
-        let a_flags = flags[0].as_ref().unwrap();
-        assert!(flags[1].is_none());
-        assert!(a_flags.added());
-        assert!(!a_flags.mutated());
-        assert!(a_flags.changed());
+        let mut trackers_query = world.query::<Option<ChangeTrackers<A>>>();
+        let trackers = trackers_query.iter(&world).collect::<Vec<_>>();
+        let a_trackers = trackers[0].as_ref().unwrap();
+        assert!(trackers[1].is_none());
+        assert!(a_trackers.is_added());
+        assert!(a_trackers.is_changed());
         world.clear_trackers();
-        let flags = flags_query.iter(&world).collect::<Vec<_>>();
-        let a_flags = flags[0].as_ref().unwrap();
-        assert!(!a_flags.added());
-        assert!(!a_flags.mutated());
-        assert!(!a_flags.changed());
+        let trackers = trackers_query.iter(&world).collect::<Vec<_>>();
+        let a_trackers = trackers[0].as_ref().unwrap();
+        assert!(!a_trackers.is_added());
+        assert!(!a_trackers.is_changed());
         *world.get_mut(e1).unwrap() = A(1);
-        let flags = flags_query.iter(&world).collect::<Vec<_>>();
-        let a_flags = flags[0].as_ref().unwrap();
-        assert!(!a_flags.added());
-        assert!(a_flags.mutated());
-        assert!(a_flags.changed());
+        let trackers = trackers_query.iter(&world).collect::<Vec<_>>();
+        let a_trackers = trackers[0].as_ref().unwrap();
+        assert!(!a_trackers.is_added());
+        assert!(a_trackers.is_changed());
+        assert!(a_trackers.changed_tick() > a_trackers.added_tick());
     }
 
     #[test]
@@ -1159,4 +1194,196 @@ mod tests {
         });
         assert_eq!(*world.get_resource::<i32>().unwrap(), 1);
     }
+
+    fn record(world: &mut World, entity: Entity, event: &'static str) {
+        world
+            .get_resource_mut::<Vec<(&'static str, Entity)>>()
+            .unwrap()
+            .push((event, entity));
+    }
+
+    fn register_hooked_a(world: &mut World, storage_type: StorageType) {
+        world.insert_resource(Vec::<(&'static str, Entity)>::new());
+        world
+            .register_component(
+                ComponentDescriptor::new::<A>(storage_type)
+                    .with_on_add(|world, entity, _| record(world, entity, "add"))
+                    .with_on_insert(|world, entity, _| record(world, entity, "insert"))
+                    .with_on_remove(|world, entity, _| {
+                        // the removed value can still be read while the hook runs
+                        assert!(world.get::<A>(entity).is_some());
+                        record(world, entity, "remove")
+                    }),
+            )
+            .unwrap();
+    }
+
+    fn take_events(world: &mut World) -> Vec<(&'static str, Entity)> {
+        std::mem::take(
+            &mut *world
+                .get_resource_mut::<Vec<(&'static str, Entity)>>()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn component_hooks() {
+        for storage_type in [StorageType::Table, StorageType::SparseSet].iter() {
+            let mut world = World::new();
+            register_hooked_a(&mut world, *storage_type);
+
+            let e = world.spawn().insert(A(0)).id();
+            assert_eq!(take_events(&mut world), vec![("add", e), ("insert", e)]);
+
+            world.entity_mut(e).insert(A(1));
+            assert_eq!(take_events(&mut world), vec![("insert", e)]);
+
+            world.entity_mut(e).insert(B(1));
+            assert_eq!(take_events(&mut world), vec![]);
+
+            assert_eq!(world.entity_mut(e).remove::<A>(), Some(A(1)));
+            assert_eq!(take_events(&mut world), vec![("remove", e)]);
+
+            world.entity_mut(e).insert_bundle((A(2), C));
+            assert_eq!(take_events(&mut world), vec![("add", e), ("insert", e)]);
+
+            world.entity_mut(e).remove_bundle_intersection::<(A, C)>();
+            assert_eq!(take_events(&mut world), vec![("remove", e)]);
+
+            world.entity_mut(e).insert(A(3));
+            take_events(&mut world);
+            assert!(world.despawn(e));
+            assert_eq!(take_events(&mut world), vec![("remove", e)]);
+        }
+    }
+
+    #[test]
+    fn component_hooks_spawn_batch() {
+        let mut world = World::new();
+        register_hooked_a(&mut world, StorageType::Table);
+        let entities = world
+            .spawn_batch((0..3).map(|i| (A(i), B(i))))
+            .collect::<Vec<_>>();
+        let expected = entities
+            .iter()
+            .flat_map(|e| vec![("add", *e), ("insert", *e)])
+            .collect::<Vec<_>>();
+        assert_eq!(take_events(&mut world), expected);
+    }
+
+    #[test]
+    fn component_hooks_modify_world() {
+        let mut world = World::new();
+        world.component_hooks_mut::<A>().on_add = Some(|world, entity, _| {
+            let value = world.get::<A>(entity).unwrap().0;
+            world.entity_mut(entity).insert(B(value));
+            world.spawn().insert(C);
+        });
+        world.component_hooks_mut::<A>().on_remove = Some(|world, entity, _| {
+            world.entity_mut(entity).remove::<B>();
+        });
+
+        let e = world.spawn().insert(A(1)).id();
+        assert_eq!(world.get::<B>(e).unwrap().0, 1);
+        let batch = world
+            .spawn_batch((2..4).map(|i| (A(i),)))
+            .collect::<Vec<_>>();
+        for (i, e) in batch.iter().enumerate() {
+            assert_eq!(world.get::<B>(*e).unwrap().0, i + 2);
+        }
+        assert_eq!(world.query::<&C>().iter(&world).count(), 3);
+
+        world.entity_mut(e).remove::<A>();
+        assert!(world.get::<B>(e).is_none());
+        assert!(world.despawn(batch[0]));
+        assert!(world.get_entity(batch[0]).is_none());
+    }
+
+    #[test]
+    fn dynamic_components() {
+        use crate::query::DynamicAccess;
+        use std::{
+            alloc::Layout,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        unsafe fn count_drop(_value: *mut u8) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let mut world = World::new();
+        // SAFE: u32 is Send + Sync and `count_drop` doesn't read the value
+        let (table, sparse) = unsafe {
+            (
+                world
+                    .register_component(ComponentDescriptor::new_dynamic(
+                        "table",
+                        StorageType::Table,
+                        Layout::new::<u32>(),
+                        count_drop,
+                    ))
+                    .unwrap(),
+                world
+                    .register_component(ComponentDescriptor::new_dynamic(
+                        "sparse",
+                        StorageType::SparseSet,
+                        Layout::new::<u32>(),
+                        count_drop,
+                    ))
+                    .unwrap(),
+            )
+        };
+        assert_eq!(world.components().get_info(table).unwrap().type_id(), None);
+
+        let mut entities = Vec::new();
+        for i in 0..3u32 {
+            let mut entity = world.spawn();
+            let (mut a, mut b) = (i, i * 10);
+            // SAFE: values are u32s that are not used after being moved into the world
+            unsafe {
+                entity.insert_by_id(table, (&mut a as *mut u32).cast::<u8>());
+                if i != 1 {
+                    entity.insert_by_id(sparse, (&mut b as *mut u32).cast::<u8>());
+                }
+            }
+            entity.insert(A(i as usize));
+            entities.push(entity.id());
+        }
+
+        let value = world.entity(entities[2]).get_by_id(sparse).unwrap();
+        // SAFE: the component is a u32
+        assert_eq!(unsafe { *value.cast::<u32>() }, 20);
+        assert!(world.entity(entities[1]).get_by_id(sparse).is_none());
+
+        let mut query =
+            world.query_dynamic(&[DynamicAccess::Read(table), DynamicAccess::Write(sparse)]);
+        for pointers in query.iter_mut(&mut world) {
+            assert_eq!(pointers.len(), 2);
+            // SAFE: both components are u32s, and `sparse` has write access
+            unsafe { *pointers[1].cast::<u32>() += *pointers[0].cast::<u32>() };
+        }
+        let values = query
+            .iter_mut(&mut world)
+            // SAFE: the component is a u32
+            .map(|pointers| unsafe { *pointers[1].cast::<u32>() })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![0, 22]);
+        let mut changed = world.query_filtered::<Entity, Mutated<A>>();
+        assert_eq!(changed.iter(&world).count(), 0);
+
+        assert!(world.entity_mut(entities[0]).remove_by_id(table));
+        assert!(!world.entity_mut(entities[0]).remove_by_id(table));
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+        assert_eq!(world.get::<A>(entities[0]), Some(&A(0)));
+        world.despawn(entities[2]);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            world
+                .query_dynamic(&[DynamicAccess::Read(table)])
+                .iter_mut(&mut world)
+                .count(),
+            1
+        );
+    }
 }
@@ -60,6 +60,19 @@ impl<T: SparseSetIndex> Access<T> {
         self.reads_all
     }
 
+    /// Returns the indices that are read but not written. Does not include everything if [Access::reads_all]
+    /// is set.
+    pub fn reads(&self) -> impl Iterator<Item = T> + '_ {
+        self.reads_and_writes
+            .difference(&self.writes)
+            .map(SparseSetIndex::get_sparse_set_index)
+    }
+
+    /// Returns the indices that are written.
+    pub fn writes(&self) -> impl Iterator<Item = T> + '_ {
+        self.writes.ones().map(SparseSetIndex::get_sparse_set_index)
+    }
+
     pub fn clear(&mut self) {
         self.reads_all = false;
         self.reads_and_writes.clear();
@@ -1,15 +1,16 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    component::{Component, ComponentFlags, ComponentId, StorageType},
+    component::{Component, ComponentId, ComponentTicks, StorageType},
     entity::Entity,
     query::{Access, FilteredAccess},
-    storage::{ComponentSparseSet, Table, Tables},
+    storage::{Column, ComponentSparseSet, Table, Tables},
     world::{Mut, World},
 };
 use bevy_ecs_macros::all_tuples;
 use std::{
     marker::PhantomData,
     ptr::{self, NonNull},
+    sync::atomic::AtomicU32,
 };
 
 pub trait WorldQuery {
@@ -24,7 +25,12 @@ pub trait Fetch<'w>: Sized {
     /// Creates a new instance of this fetch.
     /// # Safety
     /// `state` must have been initialized (via [FetchState::init]) using the same `world` passed in to this function.
-    unsafe fn init(world: &World, state: &Self::State) -> Self;
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self;
 
     /// Returns true if (and only if) every table of every archetype matched by this Fetch contains all of the matched components.
     /// This is used to select a more efficient "table iterator" for "dense" queries.
@@ -53,6 +59,15 @@ pub trait Fetch<'w>: Sized {
     /// # Safety
     /// Must always be called _after_ [Fetch::set_table]. `table_row` must be in the range of the current table
     unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item;
+
+    /// Returns false if no row of the current [Table] can match this [Fetch], which lets iterators skip the whole
+    /// table. This is used by change detection filters, which check the change summary of their column.
+    /// # Safety
+    /// Must always be called _after_ [Fetch::set_table].
+    #[inline]
+    unsafe fn table_may_match(&self) -> bool {
+        true
+    }
 }
 
 /// State used to construct a Fetch. This will be cached inside QueryState, so it is best to move as much data /
@@ -124,7 +139,12 @@ impl<'w> Fetch<'w> for EntityFetch {
         true
     }
 
-    unsafe fn init(_world: &World, _state: &Self::State) -> Self {
+    unsafe fn init(
+        _world: &World,
+        _state: &Self::State,
+        _last_change_tick: u32,
+        _change_tick: u32,
+    ) -> Self {
         Self {
             entities: std::ptr::null::<Entity>(),
         }
@@ -226,7 +246,12 @@ impl<'w, T: Component> Fetch<'w> for ReadFetch<T> {
         }
     }
 
-    unsafe fn init(world: &World, state: &Self::State) -> Self {
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        _last_change_tick: u32,
+        _change_tick: u32,
+    ) -> Self {
         let mut value = Self {
             storage_type: state.storage_type,
             table_components: NonNull::dangling(),
@@ -300,10 +325,13 @@ impl<T: Component> WorldQuery for &mut T {
 pub struct WriteFetch<T> {
     storage_type: StorageType,
     table_components: NonNull<T>,
-    table_flags: *mut ComponentFlags,
+    table_ticks: *mut ComponentTicks,
+    table_changed_summary: *const AtomicU32,
     entities: *const Entity,
     entity_table_rows: *const usize,
     sparse_set: *const ComponentSparseSet,
+    last_change_tick: u32,
+    change_tick: u32,
 }
 
 pub struct WriteState<T> {
@@ -364,14 +392,22 @@ impl<'w, T: Component> Fetch<'w> for WriteFetch<T> {
         }
     }
 
-    unsafe fn init(world: &World, state: &Self::State) -> Self {
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
         let mut value = Self {
             storage_type: state.storage_type,
             table_components: NonNull::dangling(),
             entities: ptr::null::<Entity>(),
             entity_table_rows: ptr::null::<usize>(),
             sparse_set: ptr::null::<ComponentSparseSet>(),
-            table_flags: ptr::null_mut::<ComponentFlags>(),
+            table_ticks: ptr::null_mut::<ComponentTicks>(),
+            table_changed_summary: ptr::null::<AtomicU32>(),
+            last_change_tick,
+            change_tick,
         };
         if state.storage_type == StorageType::SparseSet {
             value.sparse_set = world
@@ -397,7 +433,8 @@ impl<'w, T: Component> Fetch<'w> for WriteFetch<T> {
                 let table = tables.get_unchecked(archetype.table_id());
                 let column = table.get_column(state.component_id).unwrap();
                 self.table_components = column.get_ptr().cast::<T>();
-                self.table_flags = column.get_flags_mut_ptr();
+                self.table_ticks = column.get_ticks_mut_ptr();
+                self.table_changed_summary = &column.changed_tick;
             }
             StorageType::SparseSet => self.entities = archetype.entities().as_ptr(),
         }
@@ -407,7 +444,8 @@ impl<'w, T: Component> Fetch<'w> for WriteFetch<T> {
     unsafe fn set_table(&mut self, state: &Self::State, table: &Table) {
         let column = table.get_column(state.component_id).unwrap();
         self.table_components = column.get_ptr().cast::<T>();
-        self.table_flags = column.get_flags_mut_ptr();
+        self.table_ticks = column.get_ticks_mut_ptr();
+        self.table_changed_summary = &column.changed_tick;
     }
 
     #[inline]
@@ -417,15 +455,22 @@ impl<'w, T: Component> Fetch<'w> for WriteFetch<T> {
                 let table_row = *self.entity_table_rows.add(archetype_index);
                 Mut {
                     value: &mut *self.table_components.as_ptr().add(table_row),
-                    flags: &mut *self.table_flags.add(table_row),
+                    component_ticks: &mut *self.table_ticks.add(table_row),
+                    changed_summary: self.table_changed_summary.as_ref(),
+                    last_change_tick: self.last_change_tick,
+                    change_tick: self.change_tick,
                 }
             }
             StorageType::SparseSet => {
                 let entity = *self.entities.add(archetype_index);
-                let (component, flags) = (*self.sparse_set).get_with_flags(entity).unwrap();
+                let (component, component_ticks) =
+                    (*self.sparse_set).get_with_ticks(entity).unwrap();
                 Mut {
                     value: &mut *component.cast::<T>(),
-                    flags: &mut *flags,
+                    component_ticks: &mut *component_ticks,
+                    changed_summary: None,
+                    last_change_tick: self.last_change_tick,
+                    change_tick: self.change_tick,
                 }
             }
         }
@@ -435,7 +480,10 @@ impl<'w, T: Component> Fetch<'w> for WriteFetch<T> {
     unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
         Mut {
             value: &mut *self.table_components.as_ptr().add(table_row),
-            flags: &mut *self.table_flags.add(table_row),
+            component_ticks: &mut *self.table_ticks.add(table_row),
+            changed_summary: self.table_changed_summary.as_ref(),
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
         }
     }
 }
@@ -498,9 +546,14 @@ impl<'w, T: Fetch<'w>> Fetch<'w> for OptionFetch<T> {
         self.fetch.is_dense()
     }
 
-    unsafe fn init(world: &World, state: &Self::State) -> Self {
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
         Self {
-            fetch: T::init(world, &state.state),
+            fetch: T::init(world, &state.state, last_change_tick, change_tick),
             matches: false,
         }
     }
@@ -545,52 +598,61 @@ impl<'w, T: Fetch<'w>> Fetch<'w> for OptionFetch<T> {
     }
 }
 
-/// Flags on component `T` that happened since the start of the frame.
+/// Change trackers for component `T`, which expose when the component was added or last changed.
 #[derive(Clone)]
-pub struct Flags<T: Component> {
-    flags: ComponentFlags,
+pub struct ChangeTrackers<T: Component> {
+    pub(crate) component_ticks: ComponentTicks,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
     marker: PhantomData<T>,
 }
-impl<T: Component> std::fmt::Debug for Flags<T> {
+impl<T: Component> std::fmt::Debug for ChangeTrackers<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Flags")
-            .field("added", &self.added())
-            .field("mutated", &self.mutated())
+        f.debug_struct("ChangeTrackers")
+            .field("component_ticks", &self.component_ticks)
+            .field("last_change_tick", &self.last_change_tick)
+            .field("change_tick", &self.change_tick)
             .finish()
     }
 }
 
-impl<T: Component> Flags<T> {
-    /// Has this component been added since the start of the frame.
-    pub fn added(&self) -> bool {
-        self.flags.contains(ComponentFlags::ADDED)
+impl<T: Component> ChangeTrackers<T> {
+    /// Has this component been added since the last execution of this system.
+    pub fn is_added(&self) -> bool {
+        self.component_ticks
+            .is_added(self.last_change_tick, self.change_tick)
+    }
+
+    /// Has this component been changed since the last execution of this system.
+    pub fn is_changed(&self) -> bool {
+        self.component_ticks
+            .is_changed(self.last_change_tick, self.change_tick)
     }
 
-    /// Has this component been mutated since the start of the frame.
-    pub fn mutated(&self) -> bool {
-        self.flags.contains(ComponentFlags::MUTATED)
+    /// Returns the change tick at which this component was added.
+    pub fn added_tick(&self) -> u32 {
+        self.component_ticks.added_tick()
     }
 
-    /// Has this component been either mutated or added since the start of the frame.
-    pub fn changed(&self) -> bool {
-        self.flags
-            .intersects(ComponentFlags::ADDED | ComponentFlags::MUTATED)
+    /// Returns the change tick at which this component was last changed.
+    pub fn changed_tick(&self) -> u32 {
+        self.component_ticks.changed_tick()
     }
 }
 
-impl<T: Component> WorldQuery for Flags<T> {
-    type Fetch = FlagsFetch<T>;
-    type State = FlagsState<T>;
+impl<T: Component> WorldQuery for ChangeTrackers<T> {
+    type Fetch = ChangeTrackersFetch<T>;
+    type State = ChangeTrackersState<T>;
 }
 
-pub struct FlagsState<T> {
+pub struct ChangeTrackersState<T> {
     component_id: ComponentId,
     storage_type: StorageType,
     marker: PhantomData<T>,
 }
 
 // SAFE: component access and archetype component access are properly updated to reflect that T is read
-unsafe impl<T: Component> FetchState for FlagsState<T> {
+unsafe impl<T: Component> FetchState for ChangeTrackersState<T> {
     fn init(world: &mut World) -> Self {
         let component_info = world.components.get_or_insert_info::<T>();
         Self {
@@ -625,21 +687,23 @@ unsafe impl<T: Component> FetchState for FlagsState<T> {
     }
 }
 
-pub struct FlagsFetch<T> {
+pub struct ChangeTrackersFetch<T> {
     storage_type: StorageType,
-    table_flags: *const ComponentFlags,
+    table_ticks: *const ComponentTicks,
     entity_table_rows: *const usize,
     entities: *const Entity,
     sparse_set: *const ComponentSparseSet,
     marker: PhantomData<T>,
+    last_change_tick: u32,
+    change_tick: u32,
 }
 
-/// SAFE: access is read only  
-unsafe impl<T> ReadOnlyFetch for FlagsFetch<T> {}
+/// SAFE: access is read only
+unsafe impl<T> ReadOnlyFetch for ChangeTrackersFetch<T> {}
 
-impl<'w, T: Component> Fetch<'w> for FlagsFetch<T> {
-    type Item = Flags<T>;
-    type State = FlagsState<T>;
+impl<'w, T: Component> Fetch<'w> for ChangeTrackersFetch<T> {
+    type Item = ChangeTrackers<T>;
+    type State = ChangeTrackersState<T>;
 
     #[inline]
     fn is_dense(&self) -> bool {
@@ -649,14 +713,21 @@ impl<'w, T: Component> Fetch<'w> for FlagsFetch<T> {
         }
     }
 
-    unsafe fn init(world: &World, state: &Self::State) -> Self {
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
         let mut value = Self {
             storage_type: state.storage_type,
-            table_flags: ptr::null::<ComponentFlags>(),
+            table_ticks: ptr::null::<ComponentTicks>(),
             entities: ptr::null::<Entity>(),
             entity_table_rows: ptr::null::<usize>(),
             sparse_set: ptr::null::<ComponentSparseSet>(),
             marker: PhantomData,
+            last_change_tick,
+            change_tick,
         };
         if state.storage_type == StorageType::SparseSet {
             value.sparse_set = world
@@ -681,7 +752,7 @@ impl<'w, T: Component> Fetch<'w> for FlagsFetch<T> {
                 // SAFE: archetype tables always exist
                 let table = tables.get_unchecked(archetype.table_id());
                 let column = table.get_column(state.component_id).unwrap();
-                self.table_flags = column.get_flags_mut_ptr().cast::<ComponentFlags>();
+                self.table_ticks = column.get_ticks_mut_ptr().cast::<ComponentTicks>();
             }
             StorageType::SparseSet => self.entities = archetype.entities().as_ptr(),
         }
@@ -689,11 +760,11 @@ impl<'w, T: Component> Fetch<'w> for FlagsFetch<T> {
 
     #[inline]
     unsafe fn set_table(&mut self, state: &Self::State, table: &Table) {
-        self.table_flags = table
+        self.table_ticks = table
             .get_column(state.component_id)
             .unwrap()
-            .get_flags_mut_ptr()
-            .cast::<ComponentFlags>();
+            .get_ticks_mut_ptr()
+            .cast::<ComponentTicks>();
     }
 
     #[inline]
@@ -701,16 +772,20 @@ impl<'w, T: Component> Fetch<'w> for FlagsFetch<T> {
         match self.storage_type {
             StorageType::Table => {
                 let table_row = *self.entity_table_rows.add(archetype_index);
-                Flags {
-                    flags: *self.table_flags.add(table_row),
+                ChangeTrackers {
+                    component_ticks: *self.table_ticks.add(table_row),
                     marker: PhantomData,
+                    last_change_tick: self.last_change_tick,
+                    change_tick: self.change_tick,
                 }
             }
             StorageType::SparseSet => {
                 let entity = *self.entities.add(archetype_index);
-                Flags {
-                    flags: *(*self.sparse_set).get_flags(entity).unwrap(),
+                ChangeTrackers {
+                    component_ticks: *(*self.sparse_set).get_ticks(entity).unwrap(),
                     marker: PhantomData,
+                    last_change_tick: self.last_change_tick,
+                    change_tick: self.change_tick,
                 }
             }
         }
@@ -718,11 +793,259 @@ impl<'w, T: Component> Fetch<'w> for FlagsFetch<T> {
 
     #[inline]
     unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
-        Flags {
-            flags: *self.table_flags.add(table_row),
+        ChangeTrackers {
+            component_ticks: *self.table_ticks.add(table_row),
             marker: PhantomData,
+            last_change_tick: self.last_change_tick,
+            change_tick: self.change_tick,
+        }
+    }
+}
+
+/// The access a [DynamicComponents] query has to one of its components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicAccess {
+    Read(ComponentId),
+    Write(ComponentId),
+}
+
+impl DynamicAccess {
+    #[inline]
+    pub fn id(&self) -> ComponentId {
+        match self {
+            DynamicAccess::Read(id) | DynamicAccess::Write(id) => *id,
+        }
+    }
+}
+
+/// [WorldQuery] that fetches pointers to a list of components chosen at runtime, which is useful for components
+/// registered with [ComponentDescriptor::new_dynamic](crate::component::ComponentDescriptor::new_dynamic).
+/// Each item contains one pointer per [DynamicAccess] passed to [DynamicState::new], in the same order.
+/// Pointers of [DynamicAccess::Read] components must not be written to. Components with [DynamicAccess::Write]
+/// are marked as changed when they are fetched.
+///
+/// Build the query with [World::query_dynamic]. A [DynamicState] created by [FetchState::init] has no components.
+pub struct DynamicComponents;
+
+impl WorldQuery for DynamicComponents {
+    type Fetch = DynamicFetch;
+    type State = DynamicState;
+}
+
+pub struct DynamicState {
+    components: Vec<(DynamicAccess, StorageType)>,
+}
+
+impl DynamicState {
+    /// Creates the state of a query that accesses the given components.
+    /// # Panics
+    /// Panics if one of the components is not registered in `world`
+    pub fn new(world: &World, access: &[DynamicAccess]) -> Self {
+        let components = access
+            .iter()
+            .map(|access| {
+                let component_info = world
+                    .components()
+                    .get_info(access.id())
+                    .unwrap_or_else(|| panic!("Component {:?} does not exist", access.id()));
+                (*access, component_info.storage_type())
+            })
+            .collect();
+        Self { components }
+    }
+
+    /// Returns the access of each fetched component, in item order.
+    pub fn access(&self) -> impl Iterator<Item = DynamicAccess> + '_ {
+        self.components.iter().map(|(access, _)| *access)
+    }
+}
+
+// SAFE: component access and archetype component access are properly updated to reflect the read and written components
+unsafe impl FetchState for DynamicState {
+    fn init(_world: &mut World) -> Self {
+        Self {
+            components: Vec::new(),
+        }
+    }
+
+    fn update_component_access(&self, access: &mut FilteredAccess<ComponentId>) {
+        for (component_access, _) in self.components.iter() {
+            match *component_access {
+                DynamicAccess::Read(id) => {
+                    if access.access().has_write(id) {
+                        panic!("Dynamic read of {:?} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.", id);
+                    }
+                    access.add_read(id);
+                }
+                DynamicAccess::Write(id) => {
+                    if access.access().has_read(id) {
+                        panic!("Dynamic write of {:?} conflicts with a previous access in this query. Mutable component access must be unique.", id);
+                    }
+                    access.add_write(id);
+                }
+            }
+        }
+    }
+
+    fn update_archetype_component_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for (component_access, _) in self.components.iter() {
+            if let Some(archetype_component_id) =
+                archetype.get_archetype_component_id(component_access.id())
+            {
+                match component_access {
+                    DynamicAccess::Read(_) => access.add_read(archetype_component_id),
+                    DynamicAccess::Write(_) => access.add_write(archetype_component_id),
+                }
+            }
+        }
+    }
+
+    fn matches_archetype(&self, archetype: &Archetype) -> bool {
+        self.components
+            .iter()
+            .all(|(access, _)| archetype.contains(access.id()))
+    }
+
+    fn matches_table(&self, table: &Table) -> bool {
+        self.components
+            .iter()
+            .all(|(access, _)| table.has_column(access.id()))
+    }
+}
+
+struct DynamicComponentFetch {
+    mutable: bool,
+    storage_type: StorageType,
+    column: *const Column,
+    sparse_set: *const ComponentSparseSet,
+}
+
+pub struct DynamicFetch {
+    components: Vec<DynamicComponentFetch>,
+    entities: *const Entity,
+    entity_table_rows: *const usize,
+    change_tick: u32,
+}
+
+impl DynamicFetch {
+    /// # Safety
+    /// `table_row` (or `entity` for sparse set components) must be valid for the current table or archetype
+    #[inline]
+    unsafe fn fetch_component(
+        component: &DynamicComponentFetch,
+        table_row: usize,
+        entity: Entity,
+        change_tick: u32,
+    ) -> *mut u8 {
+        let (value, ticks) = match component.storage_type {
+            StorageType::Table => {
+                let column = &*component.column;
+                (
+                    column.get_unchecked(table_row),
+                    column.get_ticks_unchecked(table_row),
+                )
+            }
+            StorageType::SparseSet => (*component.sparse_set).get_with_ticks(entity).unwrap(),
+        };
+        if component.mutable {
+            (*ticks).set_changed(change_tick);
+            if component.storage_type == StorageType::Table {
+                (*component.column).mark_changed(change_tick, change_tick);
+            }
+        }
+        value
+    }
+}
+
+impl<'w> Fetch<'w> for DynamicFetch {
+    type Item = Vec<*mut u8>;
+    type State = DynamicState;
+
+    #[inline]
+    fn is_dense(&self) -> bool {
+        self.components
+            .iter()
+            .all(|component| component.storage_type == StorageType::Table)
+    }
+
+    unsafe fn init(
+        world: &World,
+        state: &Self::State,
+        _last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        let components = state
+            .components
+            .iter()
+            .map(|(access, storage_type)| DynamicComponentFetch {
+                mutable: matches!(access, DynamicAccess::Write(_)),
+                storage_type: *storage_type,
+                column: ptr::null::<Column>(),
+                sparse_set: match storage_type {
+                    StorageType::Table => ptr::null::<ComponentSparseSet>(),
+                    StorageType::SparseSet => {
+                        world.storages().sparse_sets.get(access.id()).unwrap()
+                            as *const ComponentSparseSet
+                    }
+                },
+            })
+            .collect();
+        Self {
+            components,
+            entities: ptr::null::<Entity>(),
+            entity_table_rows: ptr::null::<usize>(),
+            change_tick,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype(
+        &mut self,
+        state: &Self::State,
+        archetype: &Archetype,
+        tables: &Tables,
+    ) {
+        // SAFE: archetype tables always exist
+        let table = tables.get_unchecked(archetype.table_id());
+        self.set_table(state, table);
+        self.entities = archetype.entities().as_ptr();
+        self.entity_table_rows = archetype.entity_table_rows().as_ptr();
+    }
+
+    #[inline]
+    unsafe fn set_table(&mut self, state: &Self::State, table: &Table) {
+        self.entities = table.entities().as_ptr();
+        for (component, (access, _)) in self.components.iter_mut().zip(state.components.iter()) {
+            if component.storage_type == StorageType::Table {
+                component.column = table.get_column(access.id()).unwrap();
+            }
         }
     }
+
+    #[inline]
+    unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> Self::Item {
+        let table_row = *self.entity_table_rows.add(archetype_index);
+        let entity = *self.entities.add(archetype_index);
+        let change_tick = self.change_tick;
+        self.components
+            .iter()
+            .map(|component| Self::fetch_component(component, table_row, entity, change_tick))
+            .collect()
+    }
+
+    #[inline]
+    unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
+        let entity = *self.entities.add(table_row);
+        let change_tick = self.change_tick;
+        self.components
+            .iter()
+            .map(|component| Self::fetch_component(component, table_row, entity, change_tick))
+            .collect()
+    }
 }
 
 macro_rules! impl_tuple_fetch {
@@ -732,9 +1055,9 @@ macro_rules! impl_tuple_fetch {
             type Item = ($($name::Item,)*);
             type State = ($($name::State,)*);
 
-            unsafe fn init(_world: &World, state: &Self::State) -> Self {
+            unsafe fn init(_world: &World, state: &Self::State, _last_change_tick: u32, _change_tick: u32) -> Self {
                 let ($($name,)*) = state;
-                ($($name::init(_world, $name),)*)
+                ($($name::init(_world, $name, _last_change_tick, _change_tick),)*)
             }
 
 
@@ -769,6 +1092,12 @@ macro_rules! impl_tuple_fetch {
                 let ($($name,)*) = self;
                 ($($name.archetype_fetch(_archetype_index),)*)
             }
+
+            #[inline]
+            unsafe fn table_may_match(&self) -> bool {
+                let ($($name,)*) = self;
+                true $(&& $name.table_may_match())*
+            }
         }
 
         // SAFE: update_component_access and update_archetype_component_access are called for each item in the tuple
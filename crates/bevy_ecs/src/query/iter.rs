@@ -1,5 +1,6 @@
 use crate::{
     archetype::{ArchetypeId, Archetypes},
+    entity::Entity,
     query::{Fetch, FilterFetch, QueryState, WorldQuery},
     storage::{TableId, Tables},
     world::World,
@@ -26,9 +27,24 @@ impl<'w, 's, Q: WorldQuery, F: WorldQuery> QueryIter<'w, 's, Q, F>
 where
     F::Fetch: FilterFetch,
 {
-    pub(crate) unsafe fn new(world: &'w World, query_state: &'s QueryState<Q, F>) -> Self {
-        let fetch = <Q::Fetch as Fetch>::init(world, &query_state.fetch_state);
-        let filter = <F::Fetch as Fetch>::init(world, &query_state.filter_state);
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        query_state: &'s QueryState<Q, F>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        let fetch = <Q::Fetch as Fetch>::init(
+            world,
+            &query_state.fetch_state,
+            last_change_tick,
+            change_tick,
+        );
+        let filter = <F::Fetch as Fetch>::init(
+            world,
+            &query_state.filter_state,
+            last_change_tick,
+            change_tick,
+        );
         QueryIter {
             is_dense: fetch.is_dense() && filter.is_dense(),
             world,
@@ -59,8 +75,11 @@ where
                     if self.current_index == self.current_len {
                         let table_id = self.table_id_iter.next()?;
                         let table = self.tables.get_unchecked(*table_id);
-                        self.fetch.set_table(&self.query_state.fetch_state, table);
                         self.filter.set_table(&self.query_state.filter_state, table);
+                        if !self.filter.table_may_match() {
+                            continue;
+                        }
+                        self.fetch.set_table(&self.query_state.fetch_state, table);
                         self.current_len = table.len();
                         self.current_index = 0;
                         continue;
@@ -129,3 +148,205 @@ impl<'w, 's, Q: WorldQuery> ExactSizeIterator for QueryIter<'w, 's, Q, ()> {
             .sum()
     }
 }
+
+/// An iterator over query results in the order of their sort keys. Created by `iter_sorted_by_key` on
+/// [Query](crate::system::Query) and [QueryState].
+// NOTE: the buffer is boxed so that it can be handed back to the QueryState as a `Box<dyn Any>` without allocating
+#[allow(clippy::box_collection)]
+pub struct QuerySortedIter<'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static>
+where
+    F::Fetch: FilterFetch,
+{
+    world: &'w World,
+    query_state: &'s QueryState<Q, F>,
+    sorted: Option<Box<Vec<(K, Entity)>>>,
+    current_index: usize,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static>
+    QuerySortedIter<'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    #[allow(clippy::box_collection)]
+    pub(crate) fn new(
+        world: &'w World,
+        query_state: &'s QueryState<Q, F>,
+        sorted: Box<Vec<(K, Entity)>>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        QuerySortedIter {
+            world,
+            query_state,
+            sorted: Some(sorted),
+            current_index: 0,
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    #[inline]
+    fn sorted(&self) -> &[(K, Entity)] {
+        // the buffer is only taken on drop
+        self.sorted.as_ref().unwrap()
+    }
+
+    /// Returns the next query result before the sorted index `end`.
+    #[inline]
+    fn next_before(&mut self, end: usize) -> Option<<Q::Fetch as Fetch<'w>>::Item> {
+        while self.current_index < end {
+            let entity = self.sorted()[self.current_index].1;
+            self.current_index += 1;
+            // SAFE: the caller that created this iterator ensured the query's access is valid. every entity is
+            // only visited once, so results never alias
+            let item = unsafe {
+                self.query_state.get_unchecked_manual(
+                    self.world,
+                    entity,
+                    self.last_change_tick,
+                    self.change_tick,
+                )
+            };
+            if let Ok(item) = item {
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static> Iterator
+    for QuerySortedIter<'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    type Item = <Q::Fetch as Fetch<'w>>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.sorted().len();
+        self.next_before(end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.sorted().len() - self.current_index))
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static> Drop
+    for QuerySortedIter<'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    fn drop(&mut self) {
+        if let Some(sorted) = self.sorted.take() {
+            self.query_state.return_sort_buffer(sorted);
+        }
+    }
+}
+
+/// An iterator over groups of query results that share a key. Created by `iter_grouped_by` on
+/// [Query](crate::system::Query) and [QueryState].
+pub struct QueryGroupedIter<'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static>
+where
+    F::Fetch: FilterFetch,
+{
+    iter: QuerySortedIter<'w, 's, Q, F, K>,
+}
+
+impl<'w, 's, Q: WorldQuery, F: WorldQuery, K: Ord + Send + Sync + 'static>
+    QueryGroupedIter<'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    pub(crate) fn new(iter: QuerySortedIter<'w, 's, Q, F, K>) -> Self {
+        Self { iter }
+    }
+
+    /// Returns the next group of query results, in the order of their keys. Results that were not consumed from
+    /// the previous group are skipped.
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// struct Team(u32);
+    /// struct Health(u32);
+    ///
+    /// fn team_health(query: Query<(&Team, &Health)>) {
+    ///     let mut groups = query.iter_grouped_by(|(team, _)| team.0);
+    ///     while let Some(group) = groups.next_group() {
+    ///         let team = *group.key();
+    ///         let total: u32 = group.map(|(_, health)| health.0).sum();
+    ///         println!("team {} has {} health", team, total);
+    ///     }
+    /// }
+    /// # team_health.system();
+    /// ```
+    pub fn next_group(&mut self) -> Option<QueryGroup<'_, 'w, 's, Q, F, K>> {
+        let sorted = self.iter.sorted();
+        let start = self.iter.current_index;
+        let (key, _) = sorted.get(start)?;
+        let len = sorted[start..]
+            .iter()
+            .take_while(|(other, _)| other == key)
+            .count();
+        Some(QueryGroup {
+            iter: &mut self.iter,
+            start,
+            end: start + len,
+        })
+    }
+}
+
+/// A group of query results that share a key. See [QueryGroupedIter::next_group].
+pub struct QueryGroup<'a, 'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static>
+where
+    F::Fetch: FilterFetch,
+{
+    iter: &'a mut QuerySortedIter<'w, 's, Q, F, K>,
+    start: usize,
+    end: usize,
+}
+
+impl<'a, 'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static>
+    QueryGroup<'a, 'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    /// The key shared by the results in this group.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.iter.sorted()[self.start].0
+    }
+}
+
+impl<'a, 'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static> Iterator
+    for QueryGroup<'a, 'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    type Item = <Q::Fetch as Fetch<'w>>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_before(self.end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.iter.current_index))
+    }
+}
+
+impl<'a, 'w, 's, Q: WorldQuery, F: WorldQuery, K: Send + Sync + 'static> Drop
+    for QueryGroup<'a, 'w, 's, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    fn drop(&mut self) {
+        // skip the rest of the group, so the next group starts at its first result
+        self.iter.current_index = self.end;
+    }
+}
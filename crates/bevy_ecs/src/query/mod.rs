@@ -14,8 +14,11 @@ pub use state::*;
 mod tests {
     use crate::{
         component::{ComponentDescriptor, StorageType},
+        entity::Entity,
+        query::{Added, Changed},
         world::World,
     };
+    use std::any::TypeId;
 
     #[derive(Debug, Eq, PartialEq)]
     struct A(usize);
@@ -57,4 +60,105 @@ mod tests {
         let values = world.query::<&B>().iter(&world).collect::<Vec<&B>>();
         assert_eq!(values, vec![&B(3)]);
     }
+
+    #[test]
+    fn changed_filter_skips_unchanged_tables() {
+        let mut world = World::new();
+        let e1 = world.spawn().insert_bundle((A(1),)).id();
+        let e2 = world.spawn().insert_bundle((A(2), B(2))).id();
+        world.clear_trackers();
+
+        let a_id = world.components().get_id(TypeId::of::<A>()).unwrap();
+        let has_changes = |world: &World, entity: Entity| {
+            let table_id = world.entity(entity).archetype().table_id();
+            world
+                .storages()
+                .tables
+                .get(table_id)
+                .unwrap()
+                .get_column(a_id)
+                .unwrap()
+                .has_changes(world.last_change_tick(), world.read_change_tick())
+        };
+        assert!(!has_changes(&world, e1));
+        assert!(!has_changes(&world, e2));
+
+        world.entity_mut(e2).get_mut::<A>().unwrap().0 = 3;
+        assert!(!has_changes(&world, e1));
+        assert!(has_changes(&world, e2));
+        let changed = world
+            .query_filtered::<Entity, Changed<A>>()
+            .iter(&world)
+            .collect::<Vec<_>>();
+        assert_eq!(changed, vec![e2]);
+
+        // moving a changed component into another table carries its change over
+        world.entity_mut(e2).remove::<B>();
+        assert!(has_changes(&world, e2));
+        let mut changed = Vec::new();
+        world
+            .query_filtered::<Entity, Changed<A>>()
+            .for_each(&world, |entity| changed.push(entity));
+        assert_eq!(changed, vec![e2]);
+
+        world.clear_trackers();
+        assert!(!has_changes(&world, e1));
+        assert_eq!(
+            world
+                .query_filtered::<Entity, Added<A>>()
+                .iter(&world)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn sorted_query() {
+        let mut world = World::new();
+        world
+            .register_component(ComponentDescriptor::new::<B>(StorageType::SparseSet))
+            .unwrap();
+        let e1 = world.spawn().insert_bundle((A(3),)).id();
+        let e2 = world.spawn().insert_bundle((A(1), B(0))).id();
+        let e3 = world.spawn().insert_bundle((A(2),)).id();
+        let e4 = world.spawn().insert_bundle((A(1),)).id();
+
+        let mut query = world.query::<(Entity, &A)>();
+        let sorted = query
+            .iter_sorted_by_key(&world, |(_, a)| a.0)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        // equal keys are ordered by entity
+        assert_eq!(sorted, vec![e2, e4, e3, e1]);
+
+        // sorting again reuses the buffer and sees changes
+        for (_, mut a) in world
+            .query::<(Entity, &mut A)>()
+            .iter_sorted_by_key_mut(&mut world, |(entity, _)| *entity)
+        {
+            a.0 += 10;
+        }
+        let sorted = query
+            .iter_sorted_by_key(&world, |(_, a)| std::cmp::Reverse(a.0))
+            .map(|(_, a)| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![13, 12, 11, 11]);
+
+        let mut groups = query.iter_grouped_by(&world, |(_, a)| a.0);
+        let mut grouped = Vec::new();
+        while let Some(group) = groups.next_group() {
+            let key = *group.key();
+            grouped.push((key, group.map(|(entity, _)| entity).collect::<Vec<_>>()));
+        }
+        assert_eq!(
+            grouped,
+            vec![(11, vec![e2, e4]), (12, vec![e3]), (13, vec![e1])]
+        );
+        drop(groups);
+
+        // unconsumed groups are skipped
+        let mut groups = query.iter_grouped_by(&world, |(_, a)| a.0);
+        groups.next_group().unwrap().next();
+        assert_eq!(*groups.next_group().unwrap().key(), 12);
+    }
 }
@@ -3,14 +3,16 @@ use crate::{
     component::ComponentId,
     entity::Entity,
     query::{
-        Access, Fetch, FetchState, FilterFetch, FilteredAccess, QueryIter, ReadOnlyFetch,
-        WorldQuery,
+        Access, Fetch, FetchState, FilterFetch, FilteredAccess, QueryGroupedIter, QueryIter,
+        QuerySortedIter, ReadOnlyFetch, WorldQuery,
     },
     storage::TableId,
     world::{World, WorldId},
 };
 use bevy_tasks::TaskPool;
 use fixedbitset::FixedBitSet;
+use parking_lot::Mutex;
+use std::{any::Any, mem};
 use thiserror::Error;
 
 pub struct QueryState<Q: WorldQuery, F: WorldQuery = ()>
@@ -29,6 +31,9 @@ where
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    // NOTE: the buffer of the last sorted iteration is kept here so the next one can reuse its allocation. It is
+    // type erased because it holds the sort keys as well
+    sort_buffer: Mutex<Box<dyn Any + Send + Sync>>,
 }
 
 impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F>
@@ -37,6 +42,12 @@ where
 {
     pub fn new(world: &mut World) -> Self {
         let fetch_state = <Q::State as FetchState>::init(world);
+        Self::with_fetch_state(world, fetch_state)
+    }
+
+    /// Creates a [QueryState] from an already initialized fetch state. This is needed for queries whose state
+    /// cannot be derived from their type, such as [DynamicComponents](crate::query::DynamicComponents).
+    pub fn with_fetch_state(world: &mut World, fetch_state: Q::State) -> Self {
         let filter_state = <F::State as FetchState>::init(world);
         let mut component_access = Default::default();
         fetch_state.update_component_access(&mut component_access);
@@ -52,6 +63,7 @@ where
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            sort_buffer: Mutex::new(Box::new(())),
         };
         state.validate_world_and_update_archetypes(world);
         state
@@ -134,7 +146,12 @@ where
         entity: Entity,
     ) -> Result<<Q::Fetch as Fetch<'w>>::Item, QueryEntityError> {
         self.validate_world_and_update_archetypes(world);
-        self.get_unchecked_manual(world, entity)
+        self.get_unchecked_manual(
+            world,
+            entity,
+            world.last_change_tick(),
+            world.read_change_tick(),
+        )
     }
 
     /// # Safety
@@ -144,6 +161,8 @@ where
         &self,
         world: &'w World,
         entity: Entity,
+        last_change_tick: u32,
+        change_tick: u32,
     ) -> Result<<Q::Fetch as Fetch<'w>>::Item, QueryEntityError> {
         let location = world
             .entities
@@ -157,8 +176,10 @@ where
         }
         // SAFE: live entities always exist in an archetype
         let archetype = world.archetypes.get_unchecked(location.archetype_id);
-        let mut fetch = <Q::Fetch as Fetch>::init(world, &self.fetch_state);
-        let mut filter = <F::Fetch as Fetch>::init(world, &self.filter_state);
+        let mut fetch =
+            <Q::Fetch as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
+        let mut filter =
+            <F::Fetch as Fetch>::init(world, &self.filter_state, last_change_tick, change_tick);
 
         fetch.set_archetype(&self.fetch_state, archetype, &world.storages().tables);
         filter.set_archetype(&self.filter_state, archetype, &world.storages().tables);
@@ -193,7 +214,7 @@ where
         world: &'w World,
     ) -> QueryIter<'w, 's, Q, F> {
         self.validate_world_and_update_archetypes(world);
-        self.iter_unchecked_manual(world)
+        self.iter_unchecked_manual(world, world.last_change_tick(), world.read_change_tick())
     }
 
     /// # Safety
@@ -205,8 +226,139 @@ where
     pub(crate) unsafe fn iter_unchecked_manual<'w, 's>(
         &'s self,
         world: &'w World,
+        last_change_tick: u32,
+        change_tick: u32,
     ) -> QueryIter<'w, 's, Q, F> {
-        QueryIter::new(world, self)
+        QueryIter::new(world, self, last_change_tick, change_tick)
+    }
+
+    /// Iterates over the query results ordered by the key `f` extracts from them. Results with equal keys are
+    /// ordered by [Entity], so the order is the same every time the query is sorted.
+    #[inline]
+    pub fn iter_sorted_by_key<'w, 's, K: Ord + Send + Sync + 'static>(
+        &'s mut self,
+        world: &'w World,
+        f: impl FnMut(&<Q::Fetch as Fetch<'w>>::Item) -> K,
+    ) -> QuerySortedIter<'w, 's, Q, F, K>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        // SAFE: query is read only
+        unsafe {
+            self.validate_world_and_update_archetypes(world);
+            self.iter_sorted_by_key_unchecked_manual(
+                world,
+                f,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Iterates over the query results ordered by the key `f` extracts from them. Results with equal keys are
+    /// ordered by [Entity], so the order is the same every time the query is sorted.
+    #[inline]
+    pub fn iter_sorted_by_key_mut<'w, 's, K: Ord + Send + Sync + 'static>(
+        &'s mut self,
+        world: &'w mut World,
+        f: impl FnMut(&<Q::Fetch as Fetch<'w>>::Item) -> K,
+    ) -> QuerySortedIter<'w, 's, Q, F, K> {
+        // SAFE: query has unique world access
+        unsafe {
+            self.validate_world_and_update_archetypes(world);
+            self.iter_sorted_by_key_unchecked_manual(
+                world,
+                f,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Iterates over groups of query results that share the key `f` extracts from them. See
+    /// [QueryGroupedIter::next_group].
+    #[inline]
+    pub fn iter_grouped_by<'w, 's, K: Ord + Send + Sync + 'static>(
+        &'s mut self,
+        world: &'w World,
+        f: impl FnMut(&<Q::Fetch as Fetch<'w>>::Item) -> K,
+    ) -> QueryGroupedIter<'w, 's, Q, F, K>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        QueryGroupedIter::new(self.iter_sorted_by_key(world, f))
+    }
+
+    /// Collects the query results' entities and the keys `f` extracts from the results, then sorts them.
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world` with
+    /// a mismatched WorldId is unsafe.
+    pub(crate) unsafe fn iter_sorted_by_key_unchecked_manual<
+        'w,
+        's,
+        K: Ord + Send + Sync + 'static,
+    >(
+        &'s self,
+        world: &'w World,
+        mut f: impl FnMut(&<Q::Fetch as Fetch<'w>>::Item) -> K,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> QuerySortedIter<'w, 's, Q, F, K> {
+        let buffer = mem::replace(&mut *self.sort_buffer.lock(), Box::new(()));
+        let mut buffer = buffer.downcast::<Vec<(K, Entity)>>().unwrap_or_default();
+        buffer.clear();
+
+        let mut fetch =
+            <Q::Fetch as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
+        let mut filter =
+            <F::Fetch as Fetch>::init(world, &self.filter_state, last_change_tick, change_tick);
+        if fetch.is_dense() && filter.is_dense() {
+            let tables = &world.storages().tables;
+            for table_id in self.matched_table_ids.iter() {
+                let table = tables.get_unchecked(*table_id);
+                filter.set_table(&self.filter_state, table);
+                if !filter.table_may_match() {
+                    continue;
+                }
+                fetch.set_table(&self.fetch_state, table);
+                let entities = table.entities();
+
+                for table_index in 0..table.len() {
+                    if !filter.table_filter_fetch(table_index) {
+                        continue;
+                    }
+                    let item = fetch.table_fetch(table_index);
+                    buffer.push((f(&item), *entities.get_unchecked(table_index)));
+                }
+            }
+        } else {
+            let archetypes = &world.archetypes;
+            let tables = &world.storages().tables;
+            for archetype_id in self.matched_archetype_ids.iter() {
+                let archetype = archetypes.get_unchecked(*archetype_id);
+                fetch.set_archetype(&self.fetch_state, archetype, tables);
+                filter.set_archetype(&self.filter_state, archetype, tables);
+                let entities = archetype.entities();
+
+                for archetype_index in 0..archetype.len() {
+                    if !filter.archetype_filter_fetch(archetype_index) {
+                        continue;
+                    }
+                    let item = fetch.archetype_fetch(archetype_index);
+                    buffer.push((f(&item), *entities.get_unchecked(archetype_index)));
+                }
+            }
+        }
+        buffer.sort_unstable();
+
+        QuerySortedIter::new(world, self, buffer, last_change_tick, change_tick)
+    }
+
+    /// Puts back the buffer of a finished sorted iteration, so the next one can reuse it.
+    pub(crate) fn return_sort_buffer(&self, buffer: Box<dyn Any + Send + Sync>) {
+        *self.sort_buffer.lock() = buffer;
     }
 
     #[inline]
@@ -245,7 +397,12 @@ where
         func: impl FnMut(<Q::Fetch as Fetch<'w>>::Item),
     ) {
         self.validate_world_and_update_archetypes(world);
-        self.for_each_unchecked_manual(world, func);
+        self.for_each_unchecked_manual(
+            world,
+            func,
+            world.last_change_tick(),
+            world.read_change_tick(),
+        );
     }
 
     #[inline]
@@ -290,7 +447,14 @@ where
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
     ) {
         self.validate_world_and_update_archetypes(world);
-        self.par_for_each_unchecked_manual(world, task_pool, batch_size, func);
+        self.par_for_each_unchecked_manual(
+            world,
+            task_pool,
+            batch_size,
+            func,
+            world.last_change_tick(),
+            world.read_change_tick(),
+        );
     }
 
     /// # Safety
@@ -302,15 +466,22 @@ where
         &'s self,
         world: &'w World,
         mut func: impl FnMut(<Q::Fetch as Fetch<'w>>::Item),
+        last_change_tick: u32,
+        change_tick: u32,
     ) {
-        let mut fetch = <Q::Fetch as Fetch>::init(world, &self.fetch_state);
-        let mut filter = <F::Fetch as Fetch>::init(world, &self.filter_state);
+        let mut fetch =
+            <Q::Fetch as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
+        let mut filter =
+            <F::Fetch as Fetch>::init(world, &self.filter_state, last_change_tick, change_tick);
         if fetch.is_dense() && filter.is_dense() {
             let tables = &world.storages().tables;
             for table_id in self.matched_table_ids.iter() {
                 let table = tables.get_unchecked(*table_id);
-                fetch.set_table(&self.fetch_state, table);
                 filter.set_table(&self.filter_state, table);
+                if !filter.table_may_match() {
+                    continue;
+                }
+                fetch.set_table(&self.fetch_state, table);
 
                 for table_index in 0..table.len() {
                     if !filter.table_filter_fetch(table_index) {
@@ -349,21 +520,39 @@ where
         task_pool: &TaskPool,
         batch_size: usize,
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
+        last_change_tick: u32,
+        change_tick: u32,
     ) {
         task_pool.scope(|scope| {
-            let fetch = <Q::Fetch as Fetch>::init(world, &self.fetch_state);
-            let filter = <F::Fetch as Fetch>::init(world, &self.filter_state);
+            let fetch =
+                <Q::Fetch as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
+            let mut filter =
+                <F::Fetch as Fetch>::init(world, &self.filter_state, last_change_tick, change_tick);
 
             if fetch.is_dense() && filter.is_dense() {
                 let tables = &world.storages().tables;
                 for table_id in self.matched_table_ids.iter() {
                     let table = tables.get_unchecked(*table_id);
+                    filter.set_table(&self.filter_state, table);
+                    if !filter.table_may_match() {
+                        continue;
+                    }
                     let mut offset = 0;
                     while offset < table.len() {
                         let func = func.clone();
                         scope.spawn(async move {
-                            let mut fetch = <Q::Fetch as Fetch>::init(world, &self.fetch_state);
-                            let mut filter = <F::Fetch as Fetch>::init(world, &self.filter_state);
+                            let mut fetch = <Q::Fetch as Fetch>::init(
+                                world,
+                                &self.fetch_state,
+                                last_change_tick,
+                                change_tick,
+                            );
+                            let mut filter = <F::Fetch as Fetch>::init(
+                                world,
+                                &self.filter_state,
+                                last_change_tick,
+                                change_tick,
+                            );
                             let tables = &world.storages().tables;
                             let table = tables.get_unchecked(*table_id);
                             fetch.set_table(&self.fetch_state, table);
@@ -388,8 +577,18 @@ where
                     while offset < archetype.len() {
                         let func = func.clone();
                         scope.spawn(async move {
-                            let mut fetch = <Q::Fetch as Fetch>::init(world, &self.fetch_state);
-                            let mut filter = <F::Fetch as Fetch>::init(world, &self.filter_state);
+                            let mut fetch = <Q::Fetch as Fetch>::init(
+                                world,
+                                &self.fetch_state,
+                                last_change_tick,
+                                change_tick,
+                            );
+                            let mut filter = <F::Fetch as Fetch>::init(
+                                world,
+                                &self.filter_state,
+                                last_change_tick,
+                                change_tick,
+                            );
                             let tables = &world.storages().tables;
                             let archetype = world.archetypes.get_unchecked(*archetype_id);
                             fetch.set_archetype(&self.fetch_state, archetype, tables);
@@ -1,16 +1,22 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    any::TypeId,
+    ops::{Deref, DerefMut},
+    sync::atomic::AtomicU32,
+};
 
 use crate::{
-    component::{Component, ComponentFlags},
+    component::{mark_changed_summary, Component, ComponentId, ComponentTicks},
     entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
     world::{FromWorld, World},
 };
-use bevy_reflect::{impl_reflect_value, FromType, Reflect, ReflectDeserialize};
+use bevy_reflect::{impl_reflect_value, FromType, Reflect, ReflectDeserialize, TypeRegistry};
+use bevy_utils::HashSet;
 
 #[derive(Clone)]
 pub struct ReflectComponent {
     add_component: fn(&mut World, Entity, &dyn Reflect),
     apply_component: fn(&mut World, Entity, &dyn Reflect),
+    remove_component: fn(&mut World, Entity),
     reflect_component: fn(&World, Entity) -> Option<&dyn Reflect>,
     reflect_component_mut: unsafe fn(&World, Entity) -> Option<ReflectMut>,
     copy_component: fn(&World, &mut World, Entity, Entity),
@@ -25,6 +31,10 @@ impl ReflectComponent {
         (self.apply_component)(world, entity, component);
     }
 
+    pub fn remove_component(&self, world: &mut World, entity: Entity) {
+        (self.remove_component)(world, entity);
+    }
+
     pub fn reflect_component<'a>(
         &self,
         world: &'a World,
@@ -82,6 +92,9 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
                 let mut component = world.get_mut::<C>(entity).unwrap();
                 component.apply(reflected_component);
             },
+            remove_component: |world, entity| {
+                world.entity_mut(entity).remove::<C>();
+            },
             copy_component: |source_world, destination_world, source_entity, destination_entity| {
                 let source_component = source_world.get::<C>(source_entity).unwrap();
                 let mut destination_component = C::from_world(destination_world);
@@ -102,17 +115,65 @@ impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectComponent {
                     .get_unchecked_mut::<C>()
                     .map(|c| ReflectMut {
                         value: c.value as &mut dyn Reflect,
-                        flags: c.flags,
+                        component_ticks: c.component_ticks,
+                        changed_summary: c.changed_summary,
+                        last_change_tick: c.last_change_tick,
+                        change_tick: c.change_tick,
                     })
             },
         }
     }
 }
 
+#[derive(Clone)]
+pub struct ReflectResource {
+    insert_resource: fn(&mut World, &dyn Reflect),
+    remove_resource: fn(&mut World),
+    reflect_resource: fn(&World) -> Option<&dyn Reflect>,
+}
+
+impl ReflectResource {
+    /// Inserts the resource, or applies `resource` to it if it already exists.
+    pub fn insert_resource(&self, world: &mut World, resource: &dyn Reflect) {
+        (self.insert_resource)(world, resource);
+    }
+
+    pub fn remove_resource(&self, world: &mut World) {
+        (self.remove_resource)(world);
+    }
+
+    pub fn reflect_resource<'a>(&self, world: &'a World) -> Option<&'a dyn Reflect> {
+        (self.reflect_resource)(world)
+    }
+}
+
+impl<C: Component + Reflect + FromWorld> FromType<C> for ReflectResource {
+    fn from_type() -> Self {
+        ReflectResource {
+            insert_resource: |world, reflected_resource| {
+                if let Some(mut resource) = world.get_resource_mut::<C>() {
+                    resource.apply(reflected_resource);
+                } else {
+                    let mut resource = C::from_world(world);
+                    resource.apply(reflected_resource);
+                    world.insert_resource(resource);
+                }
+            },
+            remove_resource: |world| {
+                world.remove_resource::<C>();
+            },
+            reflect_resource: |world| world.get_resource::<C>().map(|r| r as &dyn Reflect),
+        }
+    }
+}
+
 /// Unique borrow of a Reflected component
 pub struct ReflectMut<'a> {
     pub(crate) value: &'a mut dyn Reflect,
-    pub(crate) flags: &'a mut ComponentFlags,
+    pub(crate) component_ticks: &'a mut ComponentTicks,
+    pub(crate) changed_summary: Option<&'a AtomicU32>,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
 }
 
 impl<'a> Deref for ReflectMut<'a> {
@@ -127,11 +188,28 @@ impl<'a> Deref for ReflectMut<'a> {
 impl<'a> DerefMut for ReflectMut<'a> {
     #[inline]
     fn deref_mut(&mut self) -> &mut dyn Reflect {
-        self.flags.insert(ComponentFlags::MUTATED);
+        self.component_ticks.set_changed(self.change_tick);
+        if let Some(changed_summary) = self.changed_summary {
+            mark_changed_summary(changed_summary, self.change_tick, self.change_tick);
+        }
         self.value
     }
 }
 
+impl<'a> ReflectMut<'a> {
+    /// Returns true if (and only if) this component been added since the last execution of this system.
+    pub fn is_added(&self) -> bool {
+        self.component_ticks
+            .is_added(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns true if (and only if) this component been changed since the last execution of this system.
+    pub fn is_changed(&self) -> bool {
+        self.component_ticks
+            .is_changed(self.last_change_tick, self.change_tick)
+    }
+}
+
 impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
 
 #[derive(Clone)]
@@ -163,3 +241,502 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
         }
     }
 }
+
+/// The component and resource types a [WorldSnapshot] captures, along with how they are copied.
+///
+/// Types registered with [SnapshotRegistry::register_component] and [SnapshotRegistry::register_resource] are
+/// copied with [Clone], which is the fastest option and doesn't require the types to be reflectable.
+/// [SnapshotRegistry::from_type_registry] adds every type registered with [ReflectComponent] or [ReflectResource],
+/// which are copied with reflection instead. Registering a type again replaces how it is copied.
+#[derive(Default)]
+pub struct SnapshotRegistry {
+    components: Vec<(TypeId, ComponentCapture)>,
+    resources: Vec<(TypeId, ResourceCapture)>,
+}
+
+type ComponentCapture = Box<dyn Fn(&World, &[Entity]) -> Box<dyn ComponentValues> + Send + Sync>;
+type ResourceCapture = Box<dyn Fn(&World) -> Box<dyn ResourceValue> + Send + Sync>;
+
+impl SnapshotRegistry {
+    /// Returns a registry that copies every component and resource that is reflectable in `type_registry`.
+    pub fn from_type_registry(type_registry: &TypeRegistry) -> Self {
+        let mut registry = SnapshotRegistry::default();
+        for registration in type_registry.iter() {
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                let reflect_component = reflect_component.clone();
+                registry.add_component(
+                    registration.type_id(),
+                    Box::new(move |world, entities| {
+                        let values = entities
+                            .iter()
+                            .map(|entity| {
+                                reflect_component
+                                    .reflect_component(world, *entity)
+                                    .unwrap()
+                                    .clone_value()
+                            })
+                            .collect();
+                        Box::new(ReflectedComponents {
+                            reflect_component: reflect_component.clone(),
+                            values,
+                        })
+                    }),
+                );
+            }
+            if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                let reflect_resource = reflect_resource.clone();
+                registry.add_resource(
+                    registration.type_id(),
+                    Box::new(move |world| {
+                        Box::new(ReflectedResource {
+                            reflect_resource: reflect_resource.clone(),
+                            value: reflect_resource
+                                .reflect_resource(world)
+                                .map(|resource| resource.clone_value()),
+                        })
+                    }),
+                );
+            }
+        }
+        registry
+    }
+
+    /// Copies components of type `T` with [Clone].
+    pub fn register_component<T: Component + Clone>(&mut self) -> &mut Self {
+        self.add_component(
+            TypeId::of::<T>(),
+            Box::new(|world, entities| {
+                let values = entities
+                    .iter()
+                    .map(|entity| world.get::<T>(*entity).unwrap().clone())
+                    .collect();
+                Box::new(ClonedComponents(values))
+            }),
+        );
+        self
+    }
+
+    /// Copies the resource of type `T` with [Clone].
+    pub fn register_resource<T: Component + Clone>(&mut self) -> &mut Self {
+        self.add_resource(
+            TypeId::of::<T>(),
+            Box::new(|world| Box::new(ClonedResource(world.get_resource::<T>().cloned()))),
+        );
+        self
+    }
+
+    fn add_component(&mut self, type_id: TypeId, capture: ComponentCapture) {
+        match self.components.iter_mut().find(|(id, _)| *id == type_id) {
+            Some((_, existing)) => *existing = capture,
+            None => self.components.push((type_id, capture)),
+        }
+    }
+
+    fn add_resource(&mut self, type_id: TypeId, capture: ResourceCapture) {
+        match self.resources.iter_mut().find(|(id, _)| *id == type_id) {
+            Some((_, existing)) => *existing = capture,
+            None => self.resources.push((type_id, capture)),
+        }
+    }
+}
+
+/// The captured values of one component type, in the order of the captured entities.
+trait ComponentValues: Send + Sync {
+    fn insert(&self, world: &mut World, entities: &[Entity]);
+    fn remove(&self, world: &mut World, entity: Entity);
+}
+
+struct ClonedComponents<T>(Vec<T>);
+
+impl<T: Component + Clone> ComponentValues for ClonedComponents<T> {
+    fn insert(&self, world: &mut World, entities: &[Entity]) {
+        for (entity, value) in entities.iter().zip(self.0.iter()) {
+            world.entity_mut(*entity).insert(value.clone());
+        }
+    }
+
+    fn remove(&self, world: &mut World, entity: Entity) {
+        world.entity_mut(entity).remove::<T>();
+    }
+}
+
+struct ReflectedComponents {
+    reflect_component: ReflectComponent,
+    values: Vec<Box<dyn Reflect>>,
+}
+
+impl ComponentValues for ReflectedComponents {
+    fn insert(&self, world: &mut World, entities: &[Entity]) {
+        for (entity, value) in entities.iter().zip(self.values.iter()) {
+            self.reflect_component
+                .add_component(world, *entity, &**value);
+        }
+    }
+
+    fn remove(&self, world: &mut World, entity: Entity) {
+        self.reflect_component.remove_component(world, entity);
+    }
+}
+
+/// The captured value of one resource type.
+trait ResourceValue: Send + Sync {
+    fn restore(&self, world: &mut World);
+}
+
+struct ClonedResource<T>(Option<T>);
+
+impl<T: Component + Clone> ResourceValue for ClonedResource<T> {
+    fn restore(&self, world: &mut World) {
+        match &self.0 {
+            Some(value) => world.insert_resource(value.clone()),
+            None => {
+                world.remove_resource::<T>();
+            }
+        }
+    }
+}
+
+struct ReflectedResource {
+    reflect_resource: ReflectResource,
+    value: Option<Box<dyn Reflect>>,
+}
+
+impl ResourceValue for ReflectedResource {
+    fn restore(&self, world: &mut World) {
+        match &self.value {
+            Some(value) => self.reflect_resource.insert_resource(world, &**value),
+            None => self.reflect_resource.remove_resource(world),
+        }
+    }
+}
+
+struct CapturedComponents {
+    type_id: TypeId,
+    entities: Vec<Entity>,
+    values: Box<dyn ComponentValues>,
+}
+
+/// A copy of the entities, components and resources of a [World] that can be restored later, for example to roll
+/// back the simulation in networked games or to replay a recording.
+///
+/// Only the components and resources in the [SnapshotRegistry] the snapshot is taken with are captured. Unlike a
+/// scene, restoring a snapshot keeps the [Entity] ids and generations of the snapshotted world, and entities
+/// spawned afterwards get the same ids they got the first time around.
+///
+/// ```
+/// use bevy_ecs::{
+///     prelude::*,
+///     reflect::{SnapshotRegistry, WorldSnapshot},
+/// };
+///
+/// #[derive(Clone)]
+/// struct Position(f32);
+///
+/// let mut registry = SnapshotRegistry::default();
+/// registry.register_component::<Position>();
+///
+/// let mut world = World::new();
+/// let entity = world.spawn().insert(Position(0.0)).id();
+/// let snapshot = WorldSnapshot::capture(&world, &registry);
+///
+/// world.get_mut::<Position>(entity).unwrap().0 = 10.0;
+/// world.despawn(entity);
+///
+/// snapshot.restore(&mut world);
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 0.0);
+/// ```
+pub struct WorldSnapshot {
+    generations: Vec<u32>,
+    pending: Vec<u32>,
+    components: Vec<CapturedComponents>,
+    resources: Vec<Box<dyn ResourceValue>>,
+}
+
+impl WorldSnapshot {
+    /// Captures the entities of `world`, along with all of their components and all resources that are registered
+    /// as reflectable in `type_registry`. See [SnapshotRegistry::from_type_registry].
+    pub fn from_world(world: &World, type_registry: &TypeRegistry) -> Self {
+        Self::capture(world, &SnapshotRegistry::from_type_registry(type_registry))
+    }
+
+    /// Captures the entities of `world`, along with all of their components and all resources that are in
+    /// `registry`. Entities that were reserved but not spawned yet are not captured.
+    pub fn capture(world: &World, registry: &SnapshotRegistry) -> Self {
+        let (generations, pending) = world.entities.allocator_state();
+
+        let components = registry
+            .components
+            .iter()
+            .map(|(type_id, capture)| {
+                let entities = match world.components().get_id(*type_id) {
+                    Some(component_id) => Self::entities_with(world, component_id),
+                    None => Vec::new(),
+                };
+                CapturedComponents {
+                    type_id: *type_id,
+                    values: capture(world, &entities),
+                    entities,
+                }
+            })
+            .collect();
+
+        let resources = registry
+            .resources
+            .iter()
+            .map(|(_, capture)| capture(world))
+            .collect();
+
+        WorldSnapshot {
+            generations,
+            pending,
+            components,
+            resources,
+        }
+    }
+
+    /// Resets the entities of `world` to the ones in this snapshot, keeping their ids and generations. Entities
+    /// that exist both in `world` and in the snapshot are updated in place, other entities are despawned or
+    /// spawned. Only the components and resources that were captured are reset: captured components are removed
+    /// from entities that didn't have them, and captured resources are removed if they did not exist when the
+    /// snapshot was taken. Other components of the updated entities are kept.
+    ///
+    /// Component hooks do not run while the entities are restored, as the world is only partially restored
+    /// at that point.
+    pub fn restore(&self, world: &mut World) {
+        let hooks = world.components.take_hooks();
+
+        world.flush();
+        let is_free = self.free_ids();
+        for entity in Self::live_entities(world) {
+            let is_captured = self.generations.get(entity.id() as usize)
+                == Some(&entity.generation())
+                && !is_free[entity.id() as usize];
+            if !is_captured {
+                world.despawn(entity);
+            }
+        }
+
+        world.restore_entities(&self.generations, &self.pending);
+
+        for captured in self.components.iter() {
+            if let Some(component_id) = world.components().get_id(captured.type_id) {
+                let captured_entities = captured.entities.iter().collect::<HashSet<_>>();
+                for entity in Self::entities_with(world, component_id) {
+                    if !captured_entities.contains(&entity) {
+                        captured.values.remove(world, entity);
+                    }
+                }
+            }
+            captured.values.insert(world, &captured.entities);
+        }
+
+        // Reflected components are created with `FromWorld`, which may spawn entities. Remove them again and hand
+        // out ids in the snapshotted order.
+        world.flush();
+        for entity in Self::live_entities(world) {
+            if is_free.get(entity.id() as usize).copied().unwrap_or(true) {
+                world.despawn(entity);
+            }
+        }
+        world
+            .entities
+            .restore_free_list(&self.generations, &self.pending);
+
+        world.components.restore_hooks(hooks);
+
+        for resource in self.resources.iter() {
+            resource.restore(world);
+        }
+    }
+
+    fn free_ids(&self) -> Vec<bool> {
+        let mut is_free = vec![false; self.generations.len()];
+        for &id in self.pending.iter() {
+            is_free[id as usize] = true;
+        }
+        is_free
+    }
+
+    fn entities_with(world: &World, component_id: ComponentId) -> Vec<Entity> {
+        world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(component_id))
+            .flat_map(|archetype| archetype.entities().iter().cloned())
+            .collect()
+    }
+
+    fn live_entities(world: &World) -> Vec<Entity> {
+        world
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities().iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SnapshotRegistry, WorldSnapshot};
+    use crate::{
+        prelude::{ReflectComponent, ReflectResource},
+        relation::Relation,
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistry};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position(f32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Frame(u32);
+
+    struct NotReflected;
+
+    #[test]
+    fn snapshot_restore() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Position>();
+        registry.register::<Health>();
+        registry.register::<Frame>();
+
+        let mut world = World::new();
+        let a = world.spawn().insert(Position(1.0)).id();
+        let removed = world.spawn().id();
+        world.despawn(removed);
+        let b = world
+            .spawn()
+            .insert_bundle((Position(2.0), Health(10), NotReflected))
+            .id();
+        let empty = world.spawn().id();
+
+        let snapshot = WorldSnapshot::from_world(&world, &registry);
+        let spawned_after_snapshot = world.spawn().id();
+
+        world.get_mut::<Position>(a).unwrap().0 = 5.0;
+        world.entity_mut(b).remove::<Health>();
+        world.despawn(empty);
+        world.insert_resource(Frame(1));
+
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<Position>(a), Some(&Position(1.0)));
+        assert_eq!(world.get::<Position>(b), Some(&Position(2.0)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(10)));
+        assert!(
+            world.get::<NotReflected>(b).is_some(),
+            "components that are not captured are kept on entities that are restored in place"
+        );
+        assert!(world.get_entity(empty).is_some());
+        assert!(world.get_entity(removed).is_none());
+        assert!(world.get_entity(spawned_after_snapshot).is_none());
+        assert!(
+            world.get_resource::<Frame>().is_none(),
+            "resources that did not exist in the snapshot are removed"
+        );
+        assert_eq!(world.entities().len(), 3);
+
+        assert_eq!(
+            world.spawn().id(),
+            spawned_after_snapshot,
+            "entities spawned after restoring reuse the same ids"
+        );
+
+        world.insert_resource(Frame(2));
+        let snapshot = WorldSnapshot::from_world(&world, &registry);
+        *world.get_resource_mut::<Frame>().unwrap() = Frame(3);
+        snapshot.restore(&mut world);
+        assert_eq!(world.get_resource::<Frame>(), Some(&Frame(2)));
+    }
+
+    #[test]
+    fn snapshot_restore_skips_hooks() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Position>();
+
+        let mut world = World::new();
+        let hooks = world.component_hooks_mut::<Position>();
+        hooks.on_add = Some(|world, _, _| {
+            world.spawn().insert(Health(0));
+        });
+        hooks.on_remove = Some(|world, _, _| {
+            world.spawn().insert(Health(1));
+        });
+
+        let entity = world.spawn().insert(Position(1.0)).id();
+        let snapshot = WorldSnapshot::from_world(&world, &registry);
+        let spawned_after_snapshot = world.spawn().id();
+        world.despawn(spawned_after_snapshot);
+        world.despawn(entity);
+
+        snapshot.restore(&mut world);
+        assert_eq!(
+            world.query::<&Health>().iter(&world).collect::<Vec<_>>(),
+            vec![&Health(0)],
+            "only the entity spawned by the hook before the snapshot exists"
+        );
+        assert_eq!(world.entities().len(), 2);
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1.0)));
+        assert_eq!(world.spawn().id(), spawned_after_snapshot);
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Velocity(f32);
+
+    struct ChildOf;
+
+    #[test]
+    fn snapshot_restore_cloned() {
+        let mut registry = SnapshotRegistry::default();
+        registry
+            .register_component::<Velocity>()
+            .register_component::<Relation<ChildOf>>()
+            .register_resource::<Velocity>();
+
+        let mut world = World::new();
+        let parent = world.spawn().insert(Velocity(1.0)).id();
+        let child = world
+            .spawn()
+            .insert(Velocity(2.0))
+            .insert_relation::<ChildOf>(parent)
+            .id();
+        let unchanged = world.spawn().insert(Velocity(3.0)).id();
+        world.insert_resource(Velocity(4.0));
+
+        let snapshot = WorldSnapshot::capture(&world, &registry);
+        world.clear_trackers();
+
+        world.despawn(parent);
+        world.get_mut::<Velocity>(child).unwrap().0 = 5.0;
+        let spawned_after_snapshot = world.spawn().insert(Velocity(6.0)).id();
+        world.entity_mut(unchanged).insert(NotReflected);
+        world.remove_resource::<Velocity>();
+        world.clear_trackers();
+
+        snapshot.restore(&mut world);
+
+        assert_eq!(world.get::<Velocity>(parent), Some(&Velocity(1.0)));
+        assert_eq!(world.get::<Velocity>(child), Some(&Velocity(2.0)));
+        assert_eq!(
+            world
+                .get::<Relation<ChildOf>>(child)
+                .map(|relation| relation.target()),
+            Some(parent)
+        );
+        assert_eq!(world.related::<ChildOf>(parent), &[child]);
+        assert!(world.get_entity(spawned_after_snapshot).is_none());
+        assert!(world.get::<NotReflected>(unchanged).is_some());
+        assert_eq!(world.get_resource::<Velocity>(), Some(&Velocity(4.0)));
+        assert_eq!(
+            world.removed::<Velocity>().collect::<Vec<_>>(),
+            vec![spawned_after_snapshot],
+            "entities that are restored in place don't lose their components"
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use crate::{
+    bundle::BundleInfo,
+    component::{ComponentId, Components, StorageType},
+    entity::Entity,
+    storage::{SparseSet, SparseSets, Table},
+    world::World,
+};
+use bevy_utils::HashMap;
+use std::{any::TypeId, fmt, marker::PhantomData};
+
+/// A component that relates the entity it is attached to (the "source") to a `target` [Entity].
+/// `K` is a marker type that names the kind of relationship, so an entity can hold relations of
+/// several different kinds at once (but only one relation of each kind).
+///
+/// Relations are added with [EntityMut::insert_relation](crate::world::EntityMut::insert_relation)
+/// or [Commands::insert_relation](crate::system::Commands::insert_relation). The [World] keeps a
+/// reverse index of every relation (see [Relations]), which is updated whenever a relation is
+/// inserted, removed or despawned. When a target is despawned, every relation pointing at it is
+/// removed from its source, so relations never dangle.
+///
+/// Relations can't be mutated in place. To retarget a relation, insert a new one.
+///
+/// ```
+/// use bevy_ecs::{relation::Relation, world::World};
+///
+/// struct OwnedBy;
+///
+/// let mut world = World::new();
+/// let player = world.spawn().id();
+/// let sword = world.spawn().insert_relation::<OwnedBy>(player).id();
+///
+/// assert_eq!(world.get::<Relation<OwnedBy>>(sword).unwrap().target(), player);
+/// assert_eq!(world.related::<OwnedBy>(player), &[sword]);
+///
+/// world.despawn(player);
+/// assert!(world.get::<Relation<OwnedBy>>(sword).is_none());
+/// ```
+#[repr(transparent)]
+pub struct Relation<K> {
+    // NOTE: the relation index reads this field through type-erased component pointers, so it must
+    // remain the only non-zero-sized field of this `repr(transparent)` struct.
+    target: Entity,
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<K> Relation<K> {
+    #[inline]
+    pub(crate) fn new(target: Entity) -> Self {
+        Self {
+            target,
+            marker: PhantomData,
+        }
+    }
+
+    /// The entity this relation points at.
+    #[inline]
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+impl<K> Clone for Relation<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K> Copy for Relation<K> {}
+
+impl<K> fmt::Debug for Relation<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Relation")
+            .field(&std::any::type_name::<K>())
+            .field(&self.target)
+            .finish()
+    }
+}
+
+struct RelationKind {
+    /// Maps each source to the target of its relation.
+    targets: HashMap<Entity, Entity>,
+    /// Maps each target to the sources of the relations pointing at it.
+    sources: HashMap<Entity, Vec<Entity>>,
+    /// Removes the typed [Relation] component of this kind from the given source.
+    remove: fn(&mut World, Entity),
+}
+
+/// The reverse index of every [Relation] in a [World], grouped by relation kind. Relation kinds are
+/// identified by the [ComponentId] of their [Relation] component.
+#[derive(Default)]
+pub struct Relations {
+    kinds: SparseSet<ComponentId, RelationKind>,
+    kind_ids: HashMap<TypeId, ComponentId>,
+}
+
+impl Relations {
+    /// Returns the [ComponentId] of the [Relation] component of kind `K`, if that kind is registered.
+    #[inline]
+    pub fn kind_id<K: 'static>(&self) -> Option<ComponentId> {
+        self.kind_ids.get(&TypeId::of::<K>()).cloned()
+    }
+
+    /// Returns the sources of all relations of kind `K` that point at `target`.
+    #[inline]
+    pub fn related<K: 'static>(&self, target: Entity) -> &[Entity] {
+        self.kind_id::<K>()
+            .map_or(&[], |component_id| self.sources(component_id, target))
+    }
+
+    /// Returns true if `component_id` is the component of a registered relation kind.
+    #[inline]
+    pub fn contains(&self, component_id: ComponentId) -> bool {
+        self.kinds.contains(component_id)
+    }
+
+    /// Returns the sources of all relations of the given kind that point at `target`.
+    pub fn sources(&self, component_id: ComponentId, target: Entity) -> &[Entity] {
+        self.kinds
+            .get(component_id)
+            .and_then(|kind| kind.sources.get(&target))
+            .map_or(&[], |sources| sources.as_slice())
+    }
+
+    /// Returns the target of the relation of the given kind held by `source`, if there is one.
+    pub fn target(&self, component_id: ComponentId, source: Entity) -> Option<Entity> {
+        self.kinds
+            .get(component_id)
+            .and_then(|kind| kind.targets.get(&source))
+            .cloned()
+    }
+
+    pub(crate) fn init_kind<K: 'static>(&mut self, component_id: ComponentId) {
+        fn remove_relation<K: 'static>(world: &mut World, source: Entity) {
+            if let Some(mut entity_mut) = world.get_entity_mut(source) {
+                entity_mut.remove::<Relation<K>>();
+            }
+        }
+
+        self.kind_ids.insert(TypeId::of::<K>(), component_id);
+        self.kinds
+            .get_or_insert_with(component_id, || RelationKind {
+                targets: Default::default(),
+                sources: Default::default(),
+                remove: remove_relation::<K>,
+            });
+    }
+
+    /// Records that `source` now relates to `target`, replacing any previous relation of this kind.
+    pub(crate) fn insert(&mut self, component_id: ComponentId, source: Entity, target: Entity) {
+        let kind = match self.kinds.get_mut(component_id) {
+            Some(kind) => kind,
+            None => return,
+        };
+        if let Some(old_target) = kind.targets.insert(source, target) {
+            if old_target == target {
+                return;
+            }
+            remove_source(&mut kind.sources, old_target, source);
+        }
+        kind.sources
+            .entry(target)
+            .or_insert_with(Vec::new)
+            .push(source);
+    }
+
+    /// Forgets the relation of the given kind held by `source`, if there is one.
+    pub(crate) fn remove(&mut self, component_id: ComponentId, source: Entity) {
+        if let Some(kind) = self.kinds.get_mut(component_id) {
+            if let Some(target) = kind.targets.remove(&source) {
+                remove_source(&mut kind.sources, target, source);
+            }
+        }
+    }
+
+    /// Forgets every relation pointing at `target`, returning the affected sources along with the
+    /// function that removes the relation component from them.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn take_targeting(
+        &mut self,
+        target: Entity,
+    ) -> Vec<(fn(&mut World, Entity), Vec<Entity>)> {
+        let mut result = Vec::new();
+        for kind in self.kinds.values_mut() {
+            if let Some(sources) = kind.sources.remove(&target) {
+                for source in sources.iter() {
+                    kind.targets.remove(source);
+                }
+                result.push((kind.remove, sources));
+            }
+        }
+        result
+    }
+
+    /// Indexes every relation component in `bundle_info`, which was just written for `entity`.
+    /// # Safety
+    /// `table_row` must be `entity`'s row in `table` and all components in `bundle_info` must have
+    /// been written for `entity`.
+    pub(crate) unsafe fn insert_bundle(
+        &mut self,
+        components: &Components,
+        sparse_sets: &SparseSets,
+        table: &Table,
+        table_row: usize,
+        bundle_info: &BundleInfo,
+        entity: Entity,
+    ) {
+        if self.kinds.is_empty() {
+            return;
+        }
+        for component_id in bundle_info.component_ids.iter().cloned() {
+            if !self.contains(component_id) {
+                continue;
+            }
+            let component_info = components.get_info_unchecked(component_id);
+            let ptr = match component_info.storage_type() {
+                StorageType::Table => table
+                    .get_column(component_id)
+                    .unwrap()
+                    .get_unchecked(table_row),
+                StorageType::SparseSet => {
+                    sparse_sets.get(component_id).unwrap().get(entity).unwrap()
+                }
+            };
+            // SAFE: relation kinds are only registered for `Relation<K>`, which is a transparent
+            // wrapper around its target
+            let target = *ptr.cast::<Entity>();
+            self.insert(component_id, entity, target);
+        }
+    }
+}
+
+fn remove_source(sources: &mut HashMap<Entity, Vec<Entity>>, target: Entity, source: Entity) {
+    if let Some(target_sources) = sources.get_mut(&target) {
+        if let Some(index) = target_sources.iter().position(|e| *e == source) {
+            target_sources.swap_remove(index);
+        }
+        if target_sources.is_empty() {
+            sources.remove(&target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::{ComponentDescriptor, StorageType},
+        entity::Entity,
+        relation::{Relation, Relations},
+        schedule::{Stage, SystemStage},
+        system::{CommandQueue, Commands, IntoSystem, Query, Res, ResMut},
+        world::World,
+    };
+
+    struct ChildOf;
+    struct Targets;
+
+    fn sorted(entities: &[Entity]) -> Vec<Entity> {
+        let mut entities = entities.to_vec();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn insert_and_remove_relations() {
+        let mut world = World::new();
+        let parent = world.spawn().id();
+        let a = world.spawn().insert_relation::<ChildOf>(parent).id();
+        let b = world
+            .spawn()
+            .insert(1u32)
+            .insert_relation::<ChildOf>(parent)
+            .id();
+        assert_eq!(sorted(world.related::<ChildOf>(parent)), vec![a, b]);
+        assert!(world.related::<Targets>(parent).is_empty());
+
+        assert_eq!(
+            world.entity_mut(a).remove_relation::<ChildOf>(),
+            Some(parent)
+        );
+        assert_eq!(world.related::<ChildOf>(parent), &[b]);
+        assert_eq!(world.entity_mut(a).remove_relation::<ChildOf>(), None);
+
+        world.entity_mut(b).remove::<u32>();
+        assert_eq!(world.related::<ChildOf>(parent), &[b]);
+        world
+            .entity_mut(b)
+            .remove_bundle::<(u32, Relation<ChildOf>)>();
+        assert_eq!(world.related::<ChildOf>(parent), &[b]);
+        world
+            .entity_mut(b)
+            .remove_bundle_intersection::<(u32, Relation<ChildOf>)>();
+        assert!(world.related::<ChildOf>(parent).is_empty());
+    }
+
+    #[test]
+    fn retarget_relation() {
+        let mut world = World::new();
+        let x = world.spawn().id();
+        let y = world.spawn().id();
+        let a = world.spawn().insert_relation::<Targets>(x).id();
+        world.entity_mut(a).insert_relation::<Targets>(y);
+        assert!(world.related::<Targets>(x).is_empty());
+        assert_eq!(world.related::<Targets>(y), &[a]);
+        assert_eq!(world.get::<Relation<Targets>>(a).unwrap().target(), y);
+    }
+
+    #[test]
+    fn despawn_source_and_target() {
+        let mut world = World::new();
+        let parent = world.spawn().id();
+        let a = world
+            .spawn()
+            .insert_relation::<ChildOf>(parent)
+            .insert_relation::<Targets>(parent)
+            .id();
+        let b = world.spawn().insert_relation::<ChildOf>(parent).id();
+
+        world.despawn(a);
+        assert_eq!(world.related::<ChildOf>(parent), &[b]);
+        assert!(world.related::<Targets>(parent).is_empty());
+
+        world.despawn(parent);
+        assert!(world.get::<Relation<ChildOf>>(b).is_none());
+        assert_eq!(
+            world.removed::<Relation<ChildOf>>().collect::<Vec<_>>(),
+            vec![a, b]
+        );
+        let relations = world.relations();
+        let kind = world
+            .components()
+            .get_id(std::any::TypeId::of::<Relation<ChildOf>>())
+            .unwrap();
+        assert_eq!(relations.target(kind, b), None);
+    }
+
+    #[test]
+    fn self_relation() {
+        let mut world = World::new();
+        let a = world.spawn().id();
+        world.entity_mut(a).insert_relation::<Targets>(a);
+        assert_eq!(world.related::<Targets>(a), &[a]);
+        world.despawn(a);
+        assert!(world.related::<Targets>(a).is_empty());
+    }
+
+    #[test]
+    fn sparse_set_relation() {
+        let mut world = World::new();
+        world
+            .register_component(ComponentDescriptor::new::<Relation<ChildOf>>(
+                StorageType::SparseSet,
+            ))
+            .unwrap();
+        let parent = world.spawn().id();
+        let a = world.spawn().insert_relation::<ChildOf>(parent).id();
+        assert_eq!(world.related::<ChildOf>(parent), &[a]);
+        world.despawn(parent);
+        assert!(world.get::<Relation<ChildOf>>(a).is_none());
+    }
+
+    #[test]
+    fn reinsert_removed_relation() {
+        let mut world = World::new();
+        let parent = world.spawn().id();
+        let a = world.spawn().insert_relation::<ChildOf>(parent).id();
+        let relation = world.entity_mut(a).remove::<Relation<ChildOf>>().unwrap();
+        let entities = world
+            .spawn_batch(vec![(relation,)])
+            .collect::<Vec<Entity>>();
+        assert_eq!(world.related::<ChildOf>(parent), entities.as_slice());
+    }
+
+    #[test]
+    fn relation_commands() {
+        let mut world = World::new();
+        let parent = world.spawn().id();
+        let mut command_queue = CommandQueue::default();
+        Commands::new(&mut command_queue, &world)
+            .spawn((1u32,))
+            .with_relation::<ChildOf>(parent);
+        command_queue.apply(&mut world);
+        let child = world.related::<ChildOf>(parent)[0];
+        assert_eq!(world.get::<u32>(child), Some(&1));
+
+        let dead = world.spawn().id();
+        world.despawn(dead);
+        Commands::new(&mut command_queue, &world)
+            .insert_relation::<Targets>(child, dead)
+            .remove_relation::<ChildOf>(child);
+        command_queue.apply(&mut world);
+        assert!(world.get::<Relation<Targets>>(child).is_none());
+        assert!(world.related::<ChildOf>(parent).is_empty());
+    }
+
+    #[test]
+    fn query_related() {
+        struct Parent(Entity);
+        struct Total(u32);
+
+        fn double(parent: Res<Parent>, mut children: Query<&mut u32>) {
+            children.for_each_related_mut::<ChildOf>(parent.0, |mut value| *value *= 2);
+        }
+
+        fn total(
+            parent: Res<Parent>,
+            relations: &Relations,
+            children: Query<(Entity, &u32)>,
+            mut total: ResMut<Total>,
+        ) {
+            let kind = relations.kind_id::<ChildOf>().unwrap();
+            total.0 = 0;
+            for (child, value) in children.iter_related::<ChildOf>(parent.0) {
+                assert_eq!(relations.target(kind, child), Some(parent.0));
+                total.0 += *value;
+            }
+        }
+
+        let mut world = World::new();
+        let parent = world.spawn().id();
+        world
+            .spawn()
+            .insert(1u32)
+            .insert_relation::<ChildOf>(parent);
+        world
+            .spawn()
+            .insert(2u32)
+            .insert_relation::<ChildOf>(parent);
+        world.spawn().insert_relation::<ChildOf>(parent);
+        world.spawn().insert(4u32);
+        world.insert_resource(Parent(parent));
+        world.insert_resource(Total(0));
+
+        SystemStage::single(double.system()).run(&mut world);
+        SystemStage::single(total.system()).run(&mut world);
+        assert_eq!(world.get_resource::<Total>().unwrap().0, 6);
+    }
+}
@@ -1,4 +1,12 @@
-use crate::{archetype::ArchetypeGeneration, schedule::ParallelSystemContainer, world::World};
+use crate::{
+    archetype::ArchetypeGeneration,
+    schedule::{
+        current_thread_index, ParallelSystemContainer, ScheduleProfiler, SystemContainer,
+        SystemSpan,
+    },
+    world::World,
+};
+use bevy_utils::{Duration, Instant};
 use downcast_rs::{impl_downcast, Downcast};
 
 pub trait ParallelSystemExecutor: Downcast + Send + Sync {
@@ -29,9 +37,25 @@ impl ParallelSystemExecutor for SingleThreadedExecutor {
     fn run_systems(&mut self, systems: &mut [ParallelSystemContainer], world: &mut World) {
         self.update_archetypes(systems, world);
 
+        let profiling = world.contains_resource::<ScheduleProfiler>();
         for system in systems {
             if system.should_run() {
-                system.system_mut().run((), world);
+                if profiling {
+                    let start = Instant::now();
+                    system.system_mut().run((), world);
+                    let end = Instant::now();
+                    if let Some(mut profiler) = world.get_resource_mut::<ScheduleProfiler>() {
+                        profiler.record_system(SystemSpan {
+                            name: system.system_name(),
+                            thread: current_thread_index(),
+                            start,
+                            end,
+                            wait: Duration::from_secs(0),
+                        });
+                    }
+                } else {
+                    system.system_mut().run((), world);
+                }
             }
         }
     }
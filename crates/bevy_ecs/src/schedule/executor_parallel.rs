@@ -1,11 +1,15 @@
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeGeneration},
     query::Access,
-    schedule::{ParallelSystemContainer, ParallelSystemExecutor},
+    schedule::{
+        current_thread_index, ParallelSystemContainer, ParallelSystemExecutor, ScheduleProfiler,
+        SystemContainer, SystemSpan,
+    },
     world::World,
 };
 use async_channel::{Receiver, Sender};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool};
+use bevy_utils::{Duration, Instant};
 use fixedbitset::FixedBitSet;
 
 #[cfg(test)]
@@ -27,17 +31,22 @@ struct SystemSchedulingMetadata {
     archetype_component_access: Access<ArchetypeComponentId>,
     /// Whether or not this system is send-able
     is_send: bool,
+    /// When the system was last queued, only updated while profiling.
+    queued_at: Instant,
 }
 
+/// Start, end and thread index of a system run, only measured while profiling.
+type SystemTiming = Option<(Instant, Instant, u32)>;
+
 pub struct ParallelExecutor {
     /// Last archetypes generation observed by parallel systems.
     archetype_generation: ArchetypeGeneration,
     /// Cached metadata of every system.
     system_metadata: Vec<SystemSchedulingMetadata>,
     /// Used by systems to notify the executor that they have finished.
-    finish_sender: Sender<usize>,
+    finish_sender: Sender<(usize, SystemTiming)>,
     /// Receives finish events from systems.
-    finish_receiver: Receiver<usize>,
+    finish_receiver: Receiver<(usize, SystemTiming)>,
     /// Systems that should be started at next opportunity.
     queued: FixedBitSet,
     /// Systems that are currently running.
@@ -50,6 +59,10 @@ pub struct ParallelExecutor {
     active_archetype_component_access: Access<ArchetypeComponentId>,
     /// Scratch space to avoid reallocating a vector when updating dependency counters.
     dependants_scratch: Vec<usize>,
+    /// Whether a [ScheduleProfiler] is present in the world during the current iteration.
+    profiling: bool,
+    /// Timings of systems that have finished this iteration, only populated while profiling.
+    finished_runs: Vec<(usize, Instant, Instant, u32)>,
     #[cfg(test)]
     events_sender: Option<Sender<SchedulingEvent>>,
 }
@@ -69,6 +82,8 @@ impl Default for ParallelExecutor {
             should_run: Default::default(),
             active_archetype_component_access: Default::default(),
             dependants_scratch: Default::default(),
+            profiling: false,
+            finished_runs: Default::default(),
             #[cfg(test)]
             events_sender: None,
         }
@@ -95,6 +110,7 @@ impl ParallelSystemExecutor for ParallelExecutor {
                 dependencies_now: 0,
                 is_send: system.is_send(),
                 archetype_component_access: Default::default(),
+                queued_at: Instant::now(),
             });
         }
         // Populate the dependants lists in the scheduling metadata.
@@ -114,6 +130,7 @@ impl ParallelSystemExecutor for ParallelExecutor {
         }
 
         self.update_archetypes(systems, world);
+        self.profiling = world.contains_resource::<ScheduleProfiler>();
 
         let compute_pool = world
             .get_resource_or_insert_with(|| ComputeTaskPool(TaskPool::default()))
@@ -127,15 +144,15 @@ impl ParallelSystemExecutor for ParallelExecutor {
                     // Avoid deadlocking if no systems were actually started.
                     if self.running.count_ones(..) != 0 {
                         // Wait until at least one system has finished.
-                        let index = self
+                        let (index, timing) = self
                             .finish_receiver
                             .recv()
                             .await
                             .unwrap_or_else(|error| unreachable!(error));
-                        self.process_finished_system(index);
+                        self.process_finished_system(index, timing);
                         // Gather other systems than may have finished.
-                        while let Ok(index) = self.finish_receiver.try_recv() {
-                            self.process_finished_system(index);
+                        while let Ok((index, timing)) = self.finish_receiver.try_recv() {
+                            self.process_finished_system(index, timing);
                         }
                         // At least one system has finished, so active access is outdated.
                         self.rebuild_active_access();
@@ -144,6 +161,10 @@ impl ParallelSystemExecutor for ParallelExecutor {
                 }
             });
         });
+
+        if self.profiling {
+            self.record_spans(systems, world);
+        }
     }
 }
 
@@ -185,6 +206,12 @@ impl ParallelExecutor {
         world: &'scope World,
     ) {
         self.should_run.clear();
+        let profiling = self.profiling;
+        let now = if profiling {
+            Some(Instant::now())
+        } else {
+            None
+        };
         for (index, system_data) in self.system_metadata.iter_mut().enumerate() {
             // Spawn the system task.
             if systems[index].should_run() {
@@ -197,9 +224,16 @@ impl ParallelExecutor {
                         .recv()
                         .await
                         .unwrap_or_else(|error| unreachable!(error));
-                    unsafe { system.run_unsafe((), world) };
+                    let timing = if profiling {
+                        let start = Instant::now();
+                        unsafe { system.run_unsafe((), world) };
+                        Some((start, Instant::now(), current_thread_index()))
+                    } else {
+                        unsafe { system.run_unsafe((), world) };
+                        None
+                    };
                     finish_sender
-                        .send(index)
+                        .send((index, timing))
                         .await
                         .unwrap_or_else(|error| unreachable!(error));
                 };
@@ -212,6 +246,9 @@ impl ParallelExecutor {
             // Queue the system if it has no dependencies, otherwise reset its dependency counter.
             if system_data.dependencies_total == 0 {
                 self.queued.insert(index);
+                if let Some(now) = now {
+                    system_data.queued_at = now;
+                }
             } else {
                 system_data.dependencies_now = system_data.dependencies_total;
             }
@@ -271,7 +308,10 @@ impl ParallelExecutor {
 
     /// Unmarks the system give index as running, caches indices of its dependants
     /// in the `dependants_scratch`.
-    fn process_finished_system(&mut self, index: usize) {
+    fn process_finished_system(&mut self, index: usize, timing: SystemTiming) {
+        if let Some((start, end, thread)) = timing {
+            self.finished_runs.push((index, start, end, thread));
+        }
         let system_data = &self.system_metadata[index];
         if !system_data.is_send {
             self.non_send_running = false;
@@ -293,12 +333,45 @@ impl ParallelExecutor {
     /// Drains `dependants_scratch`, decrementing dependency counters and enqueueing any
     /// systems that become able to run.
     fn update_counters_and_queue_systems(&mut self) {
+        let now = if self.profiling && !self.dependants_scratch.is_empty() {
+            Some(Instant::now())
+        } else {
+            None
+        };
         for index in self.dependants_scratch.drain(..) {
             let dependant_data = &mut self.system_metadata[index];
             dependant_data.dependencies_now -= 1;
             if dependant_data.dependencies_now == 0 {
                 self.queued.insert(index);
+                if let Some(now) = now {
+                    dependant_data.queued_at = now;
+                }
+            }
+        }
+    }
+
+    /// Moves the timings of systems that ran this iteration into the world's [ScheduleProfiler].
+    fn record_spans(&mut self, systems: &[ParallelSystemContainer], world: &mut World) {
+        let mut profiler = match world.get_resource_mut::<ScheduleProfiler>() {
+            Some(profiler) => profiler,
+            None => {
+                self.finished_runs.clear();
+                return;
             }
+        };
+        for (index, start, end, thread) in self.finished_runs.drain(..) {
+            let queued_at = self.system_metadata[index].queued_at;
+            profiler.record_system(SystemSpan {
+                name: systems[index].system_name(),
+                thread,
+                start,
+                end,
+                wait: if start > queued_at {
+                    start - queued_at
+                } else {
+                    Duration::from_secs(0)
+                },
+            });
         }
     }
 
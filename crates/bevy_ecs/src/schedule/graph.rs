@@ -0,0 +1,440 @@
+use crate::{
+    component::{ComponentId, Components},
+    query::Access,
+};
+use std::{borrow::Cow, fmt::Write};
+
+/// Describes the stages of a [Schedule](super::Schedule), in the order they run. Created with
+/// [Schedule::graph](super::Schedule::graph).
+#[derive(Debug, Clone)]
+pub struct ScheduleGraph {
+    /// The name of the schedule's run criteria system, if it has one.
+    pub run_criteria: Option<Cow<'static, str>>,
+    /// The debug representation of each stage's label, along with a description of the stage.
+    pub stages: Vec<(String, StageGraph)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum StageGraph {
+    SystemStage(SystemStageGraph),
+    Schedule(ScheduleGraph),
+    /// A stage whose contents can't be described, such as a [StateStage](super::StateStage).
+    Other,
+}
+
+/// Describes the systems of a [SystemStage](super::SystemStage). Created with
+/// [SystemStage::graph](super::SystemStage::graph).
+#[derive(Debug, Clone)]
+pub struct SystemStageGraph {
+    /// The system sets of the stage. The first set contains the systems that were added to the stage directly.
+    pub system_sets: Vec<SystemSetNode>,
+    /// The systems of the stage, in the order of their [SystemKind]. Systems of the same kind are topologically
+    /// sorted.
+    pub systems: Vec<SystemNode>,
+    /// Pairs of systems of the same kind that have conflicting data access and no execution order between them.
+    pub ambiguities: Vec<SystemAmbiguity>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemSetNode {
+    /// The name of the set's run criteria system, if it has one.
+    pub run_criteria: Option<Cow<'static, str>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemKind {
+    ExclusiveAtStart,
+    Parallel,
+    /// A flush point created with [apply_buffers](super::apply_buffers).
+    FlushPoint,
+    ExclusiveBeforeCommands,
+    ExclusiveAtEnd,
+}
+
+impl SystemKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SystemKind::ExclusiveAtStart => "exclusive_at_start",
+            SystemKind::Parallel => "parallel",
+            SystemKind::FlushPoint => "flush_point",
+            SystemKind::ExclusiveBeforeCommands => "exclusive_before_commands",
+            SystemKind::ExclusiveAtEnd => "exclusive_at_end",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemNode {
+    pub name: Cow<'static, str>,
+    /// The debug representation of the system's label, if it has one.
+    pub label: Option<String>,
+    pub kind: SystemKind,
+    /// The index of the system's set in [SystemStageGraph::system_sets].
+    pub system_set: usize,
+    /// The indices of the systems that run before this one, in [SystemStageGraph::systems].
+    pub dependencies: Vec<usize>,
+    /// The components and resources accessed by the system. Exclusive systems have no access information, as
+    /// they can access the whole world.
+    pub access: Option<SystemAccess>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SystemAccess {
+    pub reads_all: bool,
+    pub reads: Vec<String>,
+    pub writes: Vec<String>,
+}
+
+impl SystemAccess {
+    pub(super) fn new(access: &Access<ComponentId>, components: &Components) -> Self {
+        SystemAccess {
+            reads_all: access.reads_all(),
+            reads: component_names(access.reads(), components),
+            writes: component_names(access.writes(), components),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SystemAmbiguity {
+    /// The indices of the ambiguous systems in [SystemStageGraph::systems].
+    pub systems: (usize, usize),
+    /// The components and resources both systems access, at least one of them mutably. Empty for exclusive
+    /// systems.
+    pub conflicts: Vec<String>,
+}
+
+pub(super) fn component_names(
+    ids: impl IntoIterator<Item = ComponentId>,
+    components: &Components,
+) -> Vec<String> {
+    ids.into_iter()
+        .map(|id| {
+            components
+                .get_info(id)
+                .map_or_else(|| format!("{:?}", id), |info| info.name().to_string())
+        })
+        .collect()
+}
+
+impl ScheduleGraph {
+    /// Writes the schedule as a [DOT](https://graphviz.org/doc/info/lang.html) graph. Every stage is a cluster,
+    /// execution order constraints are solid edges and ambiguities are dashed red lines.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph schedule {\n    compound=true;\n");
+        self.write_dot(&mut dot, "s", 1);
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String, prefix: &str, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for (index, (label, stage)) in self.stages.iter().enumerate() {
+            let id = format!("{}{}", prefix, index);
+            writeln!(dot, "{}subgraph \"cluster_{}\" {{", indent, id).unwrap();
+            writeln!(dot, "{}    label={};", indent, dot_string(label)).unwrap();
+            match stage {
+                StageGraph::SystemStage(graph) => graph.write_dot(dot, &id, depth + 1),
+                StageGraph::Schedule(graph) => graph.write_dot(dot, &format!("{}_", id), depth + 1),
+                StageGraph::Other => {
+                    writeln!(dot, "{}    \"{}\" [label=\"\", shape=point];", indent, id).unwrap();
+                }
+            }
+            writeln!(dot, "{}}}", indent).unwrap();
+        }
+    }
+
+    /// Writes the schedule as JSON.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"run_criteria\":");
+        write_json_option(json, self.run_criteria.as_deref());
+        json.push_str(",\"stages\":[");
+        for (index, (label, stage)) in self.stages.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"label\":");
+            write_json_string(json, label);
+            match stage {
+                StageGraph::SystemStage(graph) => {
+                    json.push_str(",\"type\":\"system_stage\",\"stage\":");
+                    graph.write_json(json);
+                }
+                StageGraph::Schedule(graph) => {
+                    json.push_str(",\"type\":\"schedule\",\"schedule\":");
+                    graph.write_json(json);
+                }
+                StageGraph::Other => json.push_str(",\"type\":\"other\""),
+            }
+            json.push('}');
+        }
+        json.push_str("]}");
+    }
+}
+
+impl SystemStageGraph {
+    /// Writes the stage as a [DOT](https://graphviz.org/doc/info/lang.html) graph. Execution order constraints
+    /// are solid edges and ambiguities are dashed red lines.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph stage {\n");
+        self.write_dot(&mut dot, "s", 1);
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String, prefix: &str, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for (index, system) in self.systems.iter().enumerate() {
+            let mut label = system.name.to_string();
+            if let Some(system_label) = &system.label {
+                write!(label, "\n{}", system_label).unwrap();
+            }
+            let shape = match system.kind {
+                SystemKind::Parallel => "box",
+                SystemKind::FlushPoint => "diamond",
+                _ => "box, style=bold",
+            };
+            writeln!(
+                dot,
+                "{}\"{}_{}\" [label={}, shape={}];",
+                indent,
+                prefix,
+                index,
+                dot_string(&label),
+                shape
+            )
+            .unwrap();
+        }
+        for (index, system) in self.systems.iter().enumerate() {
+            for dependency in system.dependencies.iter() {
+                writeln!(
+                    dot,
+                    "{}\"{}_{}\" -> \"{}_{}\";",
+                    indent, prefix, dependency, prefix, index
+                )
+                .unwrap();
+            }
+        }
+        for ambiguity in self.ambiguities.iter() {
+            writeln!(
+                dot,
+                "{}\"{}_{}\" -> \"{}_{}\" [dir=none, style=dashed, color=red, label={}];",
+                indent,
+                prefix,
+                ambiguity.systems.0,
+                prefix,
+                ambiguity.systems.1,
+                dot_string(&ambiguity.conflicts.join("\n"))
+            )
+            .unwrap();
+        }
+    }
+
+    /// Writes the stage as JSON.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    fn write_json(&self, json: &mut String) {
+        json.push_str("{\"system_sets\":[");
+        for (index, system_set) in self.system_sets.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"run_criteria\":");
+            write_json_option(json, system_set.run_criteria.as_deref());
+            json.push('}');
+        }
+        json.push_str("],\"systems\":[");
+        for (index, system) in self.systems.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"name\":");
+            write_json_string(json, &system.name);
+            json.push_str(",\"label\":");
+            write_json_option(json, system.label.as_deref());
+            write!(
+                json,
+                ",\"kind\":\"{}\",\"system_set\":{},\"dependencies\":{:?},\"access\":",
+                system.kind.as_str(),
+                system.system_set,
+                system.dependencies
+            )
+            .unwrap();
+            match &system.access {
+                Some(access) => {
+                    write!(json, "{{\"reads_all\":{},\"reads\":", access.reads_all).unwrap();
+                    write_json_strings(json, &access.reads);
+                    json.push_str(",\"writes\":");
+                    write_json_strings(json, &access.writes);
+                    json.push('}');
+                }
+                None => json.push_str("null"),
+            }
+            json.push('}');
+        }
+        json.push_str("],\"ambiguities\":[");
+        for (index, ambiguity) in self.ambiguities.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"systems\":[{},{}],\"conflicts\":",
+                ambiguity.systems.0, ambiguity.systems.1
+            )
+            .unwrap();
+            write_json_strings(json, &ambiguity.conflicts);
+            json.push('}');
+        }
+        json.push_str("]}");
+    }
+}
+
+fn dot_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub(super) fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+fn write_json_option(json: &mut String, value: Option<&str>) {
+    match value {
+        Some(value) => write_json_string(json, value),
+        None => json.push_str("null"),
+    }
+}
+
+fn write_json_strings(json: &mut String, values: &[String]) {
+    json.push('[');
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        write_json_string(json, value);
+    }
+    json.push(']');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        schedule::{
+            ParallelSystemDescriptorCoercion, Schedule, StageGraph, SystemKind, SystemStage,
+        },
+        system::{IntoExclusiveSystem, IntoSystem, Query, Res, ResMut},
+        world::World,
+    };
+
+    struct A(usize);
+
+    fn read(_query: Query<&A>, _res: Res<u32>) {}
+    fn write(_query: Query<&mut A>) {}
+    fn write_res(mut res: ResMut<u32>) {
+        *res += 1;
+    }
+    fn exclusive(_world: &mut World) {}
+
+    #[test]
+    fn schedule_graph() {
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        let mut schedule = Schedule::default().with_stage(
+            "update",
+            SystemStage::parallel()
+                .with_system(read.system().label("read"))
+                .with_system(write_res.system().after("read"))
+                .with_system(write.system())
+                .with_system(exclusive.exclusive_system()),
+        );
+
+        let graph = schedule.graph(&mut world);
+        assert_eq!(graph.stages.len(), 1);
+        assert_eq!(graph.stages[0].0, "\"update\"");
+        let stage = match &graph.stages[0].1 {
+            StageGraph::SystemStage(stage) => stage,
+            _ => panic!("expected a system stage"),
+        };
+
+        assert_eq!(stage.systems.len(), 4);
+        assert_eq!(stage.systems[0].kind, SystemKind::ExclusiveAtStart);
+        assert!(stage.systems[0].access.is_none());
+
+        let find = |name: &str| {
+            stage
+                .systems
+                .iter()
+                .position(|system| system.name.ends_with(name))
+                .unwrap()
+        };
+        let read_index = find("::read");
+        let write_res_index = find("::write_res");
+        let write_index = find("::write");
+        assert_eq!(stage.systems[read_index].label.as_deref(), Some("\"read\""));
+        assert_eq!(
+            stage.systems[write_res_index].dependencies,
+            vec![read_index]
+        );
+        let read_access = stage.systems[read_index].access.as_ref().unwrap();
+        assert!(read_access.writes.is_empty());
+        assert_eq!(read_access.reads.len(), 2);
+
+        assert_eq!(stage.ambiguities.len(), 1);
+        let ambiguity = &stage.ambiguities[0];
+        let mut systems = [ambiguity.systems.0, ambiguity.systems.1];
+        systems.sort_unstable();
+        let mut expected = [read_index, write_index];
+        expected.sort_unstable();
+        assert_eq!(systems, expected);
+        assert_eq!(ambiguity.conflicts.len(), 1);
+        assert!(ambiguity.conflicts[0].ends_with("::A"));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph schedule {"));
+        assert!(dot.contains(&format!(
+            "\"s0_{}\" -> \"s0_{}\";",
+            read_index, write_res_index
+        )));
+        assert!(dot.contains("style=dashed"));
+
+        let json = graph.to_json();
+        assert!(
+            json.starts_with("{\"run_criteria\":null,\"stages\":[{\"label\":\"\\\"update\\\"\"")
+        );
+        assert!(json.contains("\"kind\":\"exclusive_at_start\""));
+        assert!(json.contains(&format!("\"dependencies\":[{}]", read_index)));
+    }
+}
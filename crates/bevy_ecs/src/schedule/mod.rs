@@ -1,6 +1,9 @@
 mod executor;
 mod executor_parallel;
+mod graph;
 mod label;
+mod profiler;
+mod run_criteria;
 mod stage;
 mod state;
 mod system_container;
@@ -9,7 +12,10 @@ mod system_set;
 
 pub use executor::*;
 pub use executor_parallel::*;
+pub use graph::*;
 pub use label::*;
+pub use profiler::*;
+pub use run_criteria::*;
 pub use stage::*;
 pub use state::*;
 pub use system_container::*;
@@ -23,7 +29,7 @@ use crate::{
     system::{BoxedSystem, IntoSystem, System, SystemId},
     world::World,
 };
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, Instant};
 use std::borrow::Cow;
 
 #[derive(Default)]
@@ -190,6 +196,33 @@ impl Schedule {
             .and_then(|stage| stage.downcast_mut::<T>())
     }
 
+    /// Removes the stage with the given label from the schedule, returning it if it existed.
+    pub fn remove_stage(&mut self, label: &dyn StageLabel) -> Option<Box<dyn Stage>> {
+        let stage = self.stages.remove(label)?;
+        self.stage_order.retain(|existing| &**existing != label);
+        Some(stage)
+    }
+
+    /// Describes the stages of this schedule, in the order they run. See [SystemStage::graph].
+    pub fn graph(&mut self, world: &mut World) -> ScheduleGraph {
+        let mut stages = Vec::with_capacity(self.stage_order.len());
+        for label in self.stage_order.iter() {
+            let stage = self.stages.get_mut(label).unwrap();
+            let graph = if let Some(stage) = stage.downcast_mut::<SystemStage>() {
+                StageGraph::SystemStage(stage.graph(world))
+            } else if let Some(schedule) = stage.downcast_mut::<Schedule>() {
+                StageGraph::Schedule(schedule.graph(world))
+            } else {
+                StageGraph::Other
+            };
+            stages.push((format!("{:?}", label), graph));
+        }
+        ScheduleGraph {
+            run_criteria: self.run_criteria.name(),
+            stages,
+        }
+    }
+
     pub fn run_once(&mut self, world: &mut World) {
         for label in self.stage_order.iter() {
             #[cfg(feature = "trace")]
@@ -198,7 +231,16 @@ impl Schedule {
             #[cfg(feature = "trace")]
             let _stage_guard = stage_span.enter();
             let stage = self.stages.get_mut(label).unwrap();
-            stage.run(world);
+            if world.contains_resource::<ScheduleProfiler>() {
+                let start = Instant::now();
+                stage.run(world);
+                let end = Instant::now();
+                if let Some(mut profiler) = world.get_resource_mut::<ScheduleProfiler>() {
+                    profiler.record_stage(label_name(&**label), start, end);
+                }
+            } else {
+                stage.run(world);
+            }
         }
     }
 }
@@ -206,7 +248,7 @@ impl Schedule {
 impl Stage for Schedule {
     fn run(&mut self, world: &mut World) {
         loop {
-            match self.run_criteria.should_run(world) {
+            match self.run_criteria.should_run(world, None) {
                 ShouldRun::No => return,
                 ShouldRun::Yes => {
                     self.run_once(world);
@@ -235,15 +277,24 @@ pub enum ShouldRun {
     NoAndCheckAgain,
 }
 
+enum RunCriteriaInner {
+    None,
+    Single(BoxedSystem<(), ShouldRun>),
+    /// Uses the result of the labeled run criteria of the stage.
+    Labeled(BoxedRunCriteriaLabel),
+    /// Passes the result of the labeled run criteria of the stage to a system.
+    Piped(BoxedRunCriteriaLabel, BoxedSystem<ShouldRun, ShouldRun>),
+}
+
 pub(crate) struct RunCriteria {
-    criteria_system: Option<BoxedSystem<(), ShouldRun>>,
+    inner: RunCriteriaInner,
     initialized: bool,
 }
 
 impl Default for RunCriteria {
     fn default() -> Self {
         Self {
-            criteria_system: None,
+            inner: RunCriteriaInner::None,
             initialized: false,
         }
     }
@@ -251,21 +302,87 @@ impl Default for RunCriteria {
 
 impl RunCriteria {
     pub fn set(&mut self, criteria_system: BoxedSystem<(), ShouldRun>) {
-        self.criteria_system = Some(criteria_system);
+        self.inner = RunCriteriaInner::Single(criteria_system);
+        self.initialized = false;
+    }
+
+    pub fn set_label(&mut self, label: BoxedRunCriteriaLabel) {
+        self.inner = RunCriteriaInner::Labeled(label);
+        self.initialized = false;
+    }
+
+    pub fn set_piped(
+        &mut self,
+        label: BoxedRunCriteriaLabel,
+        criteria_system: BoxedSystem<ShouldRun, ShouldRun>,
+    ) {
+        self.inner = RunCriteriaInner::Piped(label, criteria_system);
         self.initialized = false;
     }
 
-    pub fn should_run(&mut self, world: &mut World) -> ShouldRun {
-        if let Some(ref mut run_criteria) = self.criteria_system {
-            if !self.initialized {
-                run_criteria.initialize(world);
-                self.initialized = true;
+    /// Returns the label of the run criteria whose result this one depends on, if there is one.
+    pub fn parent(&self) -> Option<&BoxedRunCriteriaLabel> {
+        match &self.inner {
+            RunCriteriaInner::Labeled(label) | RunCriteriaInner::Piped(label, _) => Some(label),
+            RunCriteriaInner::None | RunCriteriaInner::Single(_) => None,
+        }
+    }
+
+    /// Evaluates the run criteria. `parent` is the result of the run criteria returned by [RunCriteria::parent],
+    /// and must be provided if there is one.
+    pub fn should_run(&mut self, world: &mut World, parent: Option<ShouldRun>) -> ShouldRun {
+        match &mut self.inner {
+            RunCriteriaInner::None => ShouldRun::Yes,
+            RunCriteriaInner::Single(run_criteria) => {
+                if !self.initialized {
+                    run_criteria.initialize(world);
+                    self.initialized = true;
+                }
+                let should_run = run_criteria.run((), world);
+                run_criteria.apply_buffers(world);
+                should_run
+            }
+            RunCriteriaInner::Labeled(label) => parent.unwrap_or_else(|| {
+                panic!(
+                    "The result of the run criteria {:?} was not provided.",
+                    label
+                )
+            }),
+            RunCriteriaInner::Piped(label, run_criteria) => {
+                let parent = parent.unwrap_or_else(|| {
+                    panic!(
+                        "The result of the run criteria {:?} was not provided.",
+                        label
+                    )
+                });
+                if !self.initialized {
+                    run_criteria.initialize(world);
+                    self.initialized = true;
+                }
+                let should_run = run_criteria.run(parent, world);
+                run_criteria.apply_buffers(world);
+                should_run
+            }
+        }
+    }
+
+    /// Returns the name of the criteria system, or the label of the run criteria it uses, if there is one.
+    pub fn name(&self) -> Option<Cow<'static, str>> {
+        match &self.inner {
+            RunCriteriaInner::None => None,
+            RunCriteriaInner::Single(system) => Some(system.name()),
+            RunCriteriaInner::Labeled(label) => Some(Cow::Owned(format!("{:?}", label))),
+            RunCriteriaInner::Piped(label, system) => {
+                Some(Cow::Owned(format!("Pipe({:?}, {})", label, system.name())))
             }
-            let should_run = run_criteria.run((), world);
-            run_criteria.apply_buffers(world);
-            should_run
-        } else {
-            ShouldRun::Yes
+        }
+    }
+
+    pub fn check_change_tick(&mut self, change_tick: u32) {
+        match &mut self.inner {
+            RunCriteriaInner::Single(run_criteria) => run_criteria.check_change_tick(change_tick),
+            RunCriteriaInner::Piped(_, run_criteria) => run_criteria.check_change_tick(change_tick),
+            RunCriteriaInner::None | RunCriteriaInner::Labeled(_) => (),
         }
     }
 }
@@ -326,4 +443,6 @@ impl System for RunOnce {
     fn apply_buffers(&mut self, _world: &mut World) {}
 
     fn initialize(&mut self, _world: &mut World) {}
+
+    fn check_change_tick(&mut self, _change_tick: u32) {}
 }
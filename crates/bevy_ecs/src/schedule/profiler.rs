@@ -0,0 +1,288 @@
+use crate::schedule::{graph::write_json_string, StageLabel};
+use bevy_utils::{Duration, Instant};
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::Write as _,
+    io::{self, Write},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// When this resource is present in the [World](crate::world::World), schedules record how long each stage takes
+/// to run, and stages record how long each system takes to run. Parallel systems also record how long they
+/// waited to start.
+///
+/// Spans accumulate until they are cleared with [ScheduleProfiler::clear].
+pub struct ScheduleProfiler {
+    epoch: Instant,
+    system_spans: Vec<SystemSpan>,
+    stage_spans: Vec<StageSpan>,
+}
+
+/// A single run of a system.
+#[derive(Debug, Clone)]
+pub struct SystemSpan {
+    pub name: Cow<'static, str>,
+    /// An index identifying the thread the system ran on.
+    pub thread: u32,
+    pub start: Instant,
+    pub end: Instant,
+    /// The time between all of the system's dependencies finishing and the system starting, spent waiting for
+    /// systems with conflicting access to finish or for a free thread. Zero for systems that do not run in parallel.
+    pub wait: Duration,
+}
+
+/// A single run of a stage.
+#[derive(Debug, Clone)]
+pub struct StageSpan {
+    /// The stage's label: the string itself for string labels, otherwise its debug representation.
+    pub label: String,
+    pub start: Instant,
+    pub end: Instant,
+}
+
+impl Default for ScheduleProfiler {
+    fn default() -> Self {
+        Self {
+            epoch: Instant::now(),
+            system_spans: Vec::new(),
+            stage_spans: Vec::new(),
+        }
+    }
+}
+
+impl ScheduleProfiler {
+    /// The instant this profiler was created at. Chrome traces use it as their start time.
+    pub fn epoch(&self) -> Instant {
+        self.epoch
+    }
+
+    pub fn system_spans(&self) -> &[SystemSpan] {
+        &self.system_spans
+    }
+
+    pub fn stage_spans(&self) -> &[StageSpan] {
+        &self.stage_spans
+    }
+
+    pub fn clear(&mut self) {
+        self.system_spans.clear();
+        self.stage_spans.clear();
+    }
+
+    pub(crate) fn record_system(&mut self, span: SystemSpan) {
+        self.system_spans.push(span);
+    }
+
+    pub(crate) fn record_stage(&mut self, label: String, start: Instant, end: Instant) {
+        self.stage_spans.push(StageSpan { label, start, end });
+    }
+}
+
+/// Returns the name of a stage label as shown in profiles, without the quotes of the debug representation of
+/// string labels.
+pub(crate) fn label_name(label: &dyn StageLabel) -> String {
+    let label_any: &dyn Any = label.as_any();
+    if let Some(name) = label_any.downcast_ref::<&'static str>() {
+        name.to_string()
+    } else if let Some(name) = label_any.downcast_ref::<Cow<'static, str>>() {
+        name.to_string()
+    } else {
+        format!("{:?}", label)
+    }
+}
+
+/// Returns a small index identifying the current thread.
+pub(crate) fn current_thread_index() -> u32 {
+    static NEXT_THREAD_INDEX: AtomicU32 = AtomicU32::new(1);
+    thread_local! {
+        static THREAD_INDEX: u32 = NEXT_THREAD_INDEX.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_INDEX.with(|index| *index)
+}
+
+/// Writes the spans of a [ScheduleProfiler] in the
+/// [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+/// which can be opened with `chrome://tracing` or [Perfetto](https://ui.perfetto.dev). Stages are shown on
+/// thread 0, and systems on the thread they ran on.
+///
+/// Spans can be written as they are recorded, so that the trace of a long running app does not have to be kept
+/// in memory. Trace viewers accept traces that were not finished, for example because the app crashed.
+pub struct ChromeTraceWriter<W: Write> {
+    writer: W,
+    has_events: bool,
+}
+
+impl<W: Write> ChromeTraceWriter<W> {
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(b"[")?;
+        Ok(Self {
+            writer,
+            has_events: false,
+        })
+    }
+
+    /// Writes all spans currently recorded by `profiler`.
+    pub fn write_spans(&mut self, profiler: &ScheduleProfiler) -> io::Result<()> {
+        let mut events = String::new();
+        for span in profiler.stage_spans() {
+            self.write_event(
+                &mut events,
+                profiler,
+                &span.label,
+                "stage",
+                0,
+                span.start,
+                span.end,
+                None,
+            );
+        }
+        for span in profiler.system_spans() {
+            self.write_event(
+                &mut events,
+                profiler,
+                &span.name,
+                "system",
+                span.thread,
+                span.start,
+                span.end,
+                Some(span.wait),
+            );
+        }
+        self.writer.write_all(events.as_bytes())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_event(
+        &mut self,
+        events: &mut String,
+        profiler: &ScheduleProfiler,
+        name: &str,
+        category: &str,
+        thread: u32,
+        start: Instant,
+        end: Instant,
+        wait: Option<Duration>,
+    ) {
+        if self.has_events {
+            events.push(',');
+        }
+        self.has_events = true;
+        events.push_str("\n{\"name\":");
+        write_json_string(events, name);
+        write!(
+            events,
+            ",\"cat\":\"{}\",\"ph\":\"X\",\"pid\":0,\"tid\":{},\"ts\":{},\"dur\":{}",
+            category,
+            thread,
+            micros_between(profiler.epoch, start),
+            micros_between(start, end),
+        )
+        .unwrap();
+        if let Some(wait) = wait {
+            write!(
+                events,
+                ",\"args\":{{\"wait_us\":{}}}",
+                wait.as_secs_f64() * 1e6
+            )
+            .unwrap();
+        }
+        events.push('}');
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Closes the trace and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.write_all(b"\n]\n")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn micros_between(start: Instant, end: Instant) -> f64 {
+    if end > start {
+        (end - start).as_secs_f64() * 1e6
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChromeTraceWriter, ScheduleProfiler};
+    use crate::{
+        schedule::{ParallelSystemDescriptorCoercion, Schedule, SystemStage},
+        system::{IntoExclusiveSystem, IntoSystem, ResMut},
+        world::World,
+    };
+
+    fn first(mut value: ResMut<u32>) {
+        *value += 1;
+    }
+
+    fn second(mut value: ResMut<u32>) {
+        *value += 1;
+    }
+
+    fn exclusive(world: &mut World) {
+        *world.get_resource_mut::<u32>().unwrap() += 1;
+    }
+
+    #[test]
+    fn profiler() {
+        let mut world = World::new();
+        world.insert_resource(0u32);
+        world.insert_resource(ScheduleProfiler::default());
+        let mut schedule = Schedule::default()
+            .with_stage(
+                "parallel",
+                SystemStage::parallel()
+                    .with_system(first.system().label("first"))
+                    .with_system(second.system().after("first")),
+            )
+            .with_stage(
+                "single",
+                SystemStage::single_threaded()
+                    .with_system(first.system())
+                    .with_system(exclusive.exclusive_system()),
+            );
+        schedule.run_once(&mut world);
+
+        let profiler = world.get_resource::<ScheduleProfiler>().unwrap();
+        let stages = profiler
+            .stage_spans()
+            .iter()
+            .map(|span| span.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(stages, vec!["parallel", "single"]);
+
+        let systems = profiler.system_spans();
+        assert_eq!(systems.len(), 4);
+        assert!(systems[0].name.ends_with("::first"));
+        assert!(systems[1].name.ends_with("::second"));
+        assert!(systems[0].end <= systems[1].start);
+        assert!(systems[2].name.ends_with("::exclusive"));
+        assert!(systems[3].name.ends_with("::first"));
+        let stage = &profiler.stage_spans()[0];
+        assert!(stage.start <= systems[0].start && systems[1].end <= stage.end);
+
+        let mut writer = ChromeTraceWriter::new(Vec::new()).unwrap();
+        writer.write_spans(profiler).unwrap();
+        let trace = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert!(trace.starts_with("[\n{\"name\":\"parallel\",\"cat\":\"stage\""));
+        assert_eq!(trace.matches("\"ph\":\"X\"").count(), 6);
+        assert!(trace.ends_with("}\n]\n"));
+
+        world
+            .get_resource_mut::<ScheduleProfiler>()
+            .unwrap()
+            .clear();
+        schedule.run_once(&mut world);
+        let profiler = world.get_resource::<ScheduleProfiler>().unwrap();
+        assert_eq!(profiler.stage_spans().len(), 2);
+        assert_eq!(profiler.system_spans().len(), 4);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId},
+    component::ComponentId,
+    query::Access,
+    schedule::ShouldRun,
+    system::{System, SystemId},
+    world::World,
+};
+use std::borrow::Cow;
+
+impl ShouldRun {
+    /// Returns true for [ShouldRun::Yes] and [ShouldRun::YesAndCheckAgain].
+    pub fn should_run(self) -> bool {
+        matches!(self, ShouldRun::Yes | ShouldRun::YesAndCheckAgain)
+    }
+
+    /// Returns true for [ShouldRun::YesAndCheckAgain] and [ShouldRun::NoAndCheckAgain].
+    pub fn check_again(self) -> bool {
+        matches!(
+            self,
+            ShouldRun::YesAndCheckAgain | ShouldRun::NoAndCheckAgain
+        )
+    }
+
+    fn from_parts(should_run: bool, check_again: bool) -> Self {
+        match (should_run, check_again) {
+            (true, false) => ShouldRun::Yes,
+            (false, false) => ShouldRun::No,
+            (true, true) => ShouldRun::YesAndCheckAgain,
+            (false, true) => ShouldRun::NoAndCheckAgain,
+        }
+    }
+
+    /// Runs only if both `self` and `other` run.
+    ///
+    /// If the result runs, it asks to be checked again if either side does. If it doesn't run, it asks to be
+    /// checked again only if every side that didn't run does, so that a criteria that will keep returning
+    /// [ShouldRun::No] stops the check.
+    pub fn and(self, other: ShouldRun) -> ShouldRun {
+        let should_run = self.should_run() && other.should_run();
+        let check_again = if should_run {
+            self.check_again() || other.check_again()
+        } else {
+            [self, other]
+                .iter()
+                .filter(|result| !result.should_run())
+                .all(|result| result.check_again())
+        };
+        Self::from_parts(should_run, check_again)
+    }
+
+    /// Runs if either `self` or `other` runs.
+    ///
+    /// If the result runs, it asks to be checked again only if every side that ran does, so that a criteria that
+    /// will keep returning [ShouldRun::Yes] doesn't run the systems forever. If it doesn't run, it asks to be
+    /// checked again if either side does.
+    pub fn or(self, other: ShouldRun) -> ShouldRun {
+        self.not().and(other.not()).not()
+    }
+
+    /// Runs if `self` doesn't, and asks to be checked again if `self` does.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> ShouldRun {
+        Self::from_parts(!self.should_run(), self.check_again())
+    }
+}
+
+/// Combines the results of two run criteria systems with [ShouldRun::and] or [ShouldRun::or]. Both systems are
+/// always run, so that stateful criteria like fixed timesteps are updated regardless of the other result.
+pub struct CombinedRunCriteria<A, B> {
+    criteria_a: A,
+    criteria_b: B,
+    combine: fn(ShouldRun, ShouldRun) -> ShouldRun,
+    name: Cow<'static, str>,
+    id: SystemId,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<A, B> System for CombinedRunCriteria<A, B>
+where
+    A: System<In = (), Out = ShouldRun>,
+    B: System<In = (), Out = ShouldRun>,
+{
+    type In = ();
+    type Out = ShouldRun;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype) {
+        self.criteria_a.new_archetype(archetype);
+        self.criteria_b.new_archetype(archetype);
+
+        self.archetype_component_access
+            .extend(self.criteria_a.archetype_component_access());
+        self.archetype_component_access
+            .extend(self.criteria_b.archetype_component_access());
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.criteria_a.is_send() && self.criteria_b.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: Self::In, world: &World) -> Self::Out {
+        let a = self.criteria_a.run_unsafe((), world);
+        let b = self.criteria_b.run_unsafe((), world);
+        (self.combine)(a, b)
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.criteria_a.apply_buffers(world);
+        self.criteria_b.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.criteria_a.initialize(world);
+        self.criteria_b.initialize(world);
+        self.component_access
+            .extend(self.criteria_a.component_access());
+        self.component_access
+            .extend(self.criteria_b.component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.criteria_a.check_change_tick(change_tick);
+        self.criteria_b.check_change_tick(change_tick);
+    }
+}
+
+/// Inverts the result of a run criteria system with [ShouldRun::not].
+pub struct NotRunCriteria<A> {
+    criteria: A,
+    name: Cow<'static, str>,
+    id: SystemId,
+}
+
+impl<A: System<In = (), Out = ShouldRun>> System for NotRunCriteria<A> {
+    type In = ();
+    type Out = ShouldRun;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype) {
+        self.criteria.new_archetype(archetype);
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.criteria.archetype_component_access()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.criteria.component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.criteria.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: Self::In, world: &World) -> Self::Out {
+        self.criteria.run_unsafe((), world).not()
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.criteria.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.criteria.initialize(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.criteria.check_change_tick(change_tick);
+    }
+}
+
+/// Combinators for run criteria systems. To pass the result of a run criteria to another one, use
+/// [IntoChainSystem::chain](crate::system::IntoChainSystem::chain) with a system taking
+/// [In<ShouldRun>](crate::system::In).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::ShouldRun;
+/// struct Paused(bool);
+///
+/// fn every_other_frame(mut frame: Local<u32>) -> ShouldRun {
+///     *frame += 1;
+///     if *frame % 2 == 0 { ShouldRun::Yes } else { ShouldRun::No }
+/// }
+///
+/// fn paused(paused: Res<Paused>) -> ShouldRun {
+///     if paused.0 { ShouldRun::Yes } else { ShouldRun::No }
+/// }
+///
+/// fn gameplay() {}
+///
+/// let stage = SystemStage::parallel()
+///     .with_run_criteria(every_other_frame.system().and(paused.system().not()))
+///     .with_system(gameplay.system());
+/// ```
+pub trait RunCriteriaCombinators: System<In = (), Out = ShouldRun> + Sized {
+    /// Runs only if both criteria run. See [ShouldRun::and].
+    fn and<B: System<In = (), Out = ShouldRun>>(self, other: B) -> CombinedRunCriteria<Self, B> {
+        CombinedRunCriteria {
+            name: Cow::Owned(format!("And({}, {})", self.name(), other.name())),
+            criteria_a: self,
+            criteria_b: other,
+            combine: ShouldRun::and,
+            id: SystemId::new(),
+            component_access: Default::default(),
+            archetype_component_access: Default::default(),
+        }
+    }
+
+    /// Runs if either criteria runs. See [ShouldRun::or].
+    fn or<B: System<In = (), Out = ShouldRun>>(self, other: B) -> CombinedRunCriteria<Self, B> {
+        CombinedRunCriteria {
+            name: Cow::Owned(format!("Or({}, {})", self.name(), other.name())),
+            criteria_a: self,
+            criteria_b: other,
+            combine: ShouldRun::or,
+            id: SystemId::new(),
+            component_access: Default::default(),
+            archetype_component_access: Default::default(),
+        }
+    }
+
+    /// Runs if this criteria doesn't. See [ShouldRun::not].
+    fn not(self) -> NotRunCriteria<Self> {
+        NotRunCriteria {
+            name: Cow::Owned(format!("Not({})", self.name())),
+            criteria: self,
+            id: SystemId::new(),
+        }
+    }
+}
+
+impl<S: System<In = (), Out = ShouldRun>> RunCriteriaCombinators for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::RunCriteriaCombinators;
+    use crate::{
+        schedule::{ShouldRun, Stage, SystemSet, SystemStage},
+        system::{In, IntoChainSystem, IntoSystem, Local, Res, ResMut, System},
+        world::World,
+    };
+
+    #[test]
+    fn combine_results() {
+        use ShouldRun::*;
+        assert_eq!(Yes.and(YesAndCheckAgain), YesAndCheckAgain);
+        assert_eq!(YesAndCheckAgain.and(No), No);
+        assert_eq!(NoAndCheckAgain.and(Yes), NoAndCheckAgain);
+        assert_eq!(Yes.or(No), Yes);
+        assert_eq!(YesAndCheckAgain.or(Yes), Yes);
+        assert_eq!(No.or(NoAndCheckAgain), NoAndCheckAgain);
+        assert_eq!(YesAndCheckAgain.not(), NoAndCheckAgain);
+    }
+
+    #[test]
+    fn combinators_have_own_ids() {
+        let criteria = paused.system();
+        let criteria_id = criteria.id();
+        let not = criteria.not();
+        assert_ne!(not.id(), criteria_id);
+        let not_id = not.id();
+        assert_ne!(not.and(two_steps.system()).id(), not_id);
+    }
+
+    struct Paused(bool);
+
+    fn two_steps(mut steps: Local<u32>) -> ShouldRun {
+        *steps += 1;
+        if *steps <= 2 {
+            ShouldRun::YesAndCheckAgain
+        } else {
+            *steps = 0;
+            ShouldRun::No
+        }
+    }
+
+    fn paused(paused: Res<Paused>) -> ShouldRun {
+        if paused.0 {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+
+    fn count(mut counter: ResMut<Vec<usize>>) {
+        counter[0] += 1;
+    }
+
+    fn count_second(mut counter: ResMut<Vec<usize>>) {
+        counter[1] += 1;
+    }
+
+    fn count_evaluations(
+        In(should_run): In<ShouldRun>,
+        mut counter: ResMut<Vec<usize>>,
+    ) -> ShouldRun {
+        counter[2] += 1;
+        should_run
+    }
+
+    fn unless_paused(In(should_run): In<ShouldRun>, paused: Res<Paused>) -> ShouldRun {
+        should_run.and(if paused.0 {
+            ShouldRun::No
+        } else {
+            ShouldRun::Yes
+        })
+    }
+
+    #[test]
+    fn combinators() {
+        let mut world = World::new();
+        world.insert_resource(Paused(false));
+        world.insert_resource(vec![0usize; 3]);
+        let mut stage = SystemStage::parallel()
+            .with_run_criteria(two_steps.system().and(paused.system().not()))
+            .with_system(count.system());
+        stage.run(&mut world);
+        assert_eq!(world.get_resource::<Vec<usize>>().unwrap()[0], 2);
+        world.get_resource_mut::<Paused>().unwrap().0 = true;
+        stage.run(&mut world);
+        assert_eq!(world.get_resource::<Vec<usize>>().unwrap()[0], 2);
+    }
+
+    #[test]
+    fn labeled_run_criteria() {
+        let mut world = World::new();
+        world.insert_resource(Paused(false));
+        world.insert_resource(vec![0usize; 3]);
+        let mut stage = SystemStage::parallel()
+            .with_labeled_run_criteria("two steps", two_steps.system())
+            .with_system_set(
+                SystemSet::new()
+                    .with_run_criteria_label("two steps")
+                    .with_system(count.system()),
+            )
+            .with_system_set(
+                SystemSet::new()
+                    .with_run_criteria_piped(
+                        "two steps",
+                        count_evaluations.system().chain(unless_paused.system()),
+                    )
+                    .with_system(count_second.system()),
+            );
+        stage.run(&mut world);
+        // The shared criteria is evaluated once per check, so both sets run twice.
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![2, 2, 3]);
+        world.get_resource_mut::<Paused>().unwrap().0 = true;
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![4, 2, 4]);
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
+    component::{ComponentId, Components, CHECK_TICK_THRESHOLD},
+    query::Access,
     schedule::{
-        BoxedSystemLabel, ExclusiveSystemContainer, InsertionPoint, ParallelExecutor,
-        ParallelSystemContainer, ParallelSystemExecutor, RunCriteria, ShouldRun,
-        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemLabel, SystemSet,
+        current_thread_index, graph::component_names, BoxedRunCriteriaLabel, BoxedSystemLabel,
+        ExclusiveSystemContainer, InsertionPoint, ParallelExecutor, ParallelSystemContainer,
+        ParallelSystemExecutor, RunCriteria, RunCriteriaLabel, ScheduleProfiler, ShouldRun,
+        SingleThreadedExecutor, SystemAccess, SystemAmbiguity, SystemContainer, SystemDescriptor,
+        SystemKind, SystemLabel, SystemNode, SystemSet, SystemSetNode, SystemSpan,
+        SystemStageGraph,
     },
-    system::System,
+    system::{System, SystemId},
     world::{World, WorldId},
 };
-use bevy_utils::{tracing::info, HashMap, HashSet};
+use bevy_utils::{tracing::info, Duration, HashMap, HashSet, Instant};
 use downcast_rs::{impl_downcast, Downcast};
 use fixedbitset::FixedBitSet;
 use std::borrow::Cow;
@@ -45,6 +50,30 @@ struct VirtualSystemSet {
     should_run: ShouldRun,
 }
 
+/// A run criteria that system sets refer to by label. It is evaluated once per stage run, and again each time the
+/// stage checks run criteria if it asked to be checked again.
+struct LabeledRunCriteria {
+    label: BoxedRunCriteriaLabel,
+    run_criteria: RunCriteria,
+    should_run: ShouldRun,
+}
+
+impl LabeledRunCriteria {
+    /// Finds the result of the labeled run criteria that `run_criteria` depends on, if any.
+    fn parent_result(
+        labeled: &[LabeledRunCriteria],
+        run_criteria: &RunCriteria,
+    ) -> Option<ShouldRun> {
+        run_criteria.parent().map(|label| {
+            labeled
+                .iter()
+                .find(|criteria| *criteria.label == **label)
+                .unwrap_or_else(|| panic!("Run criteria with label {:?} does not exist.", label))
+                .should_run
+        })
+    }
+}
+
 /// Stores and executes systems. Execution order is not defined unless explicitly specified;
 /// see `SystemDescriptor` documentation.
 pub struct SystemStage {
@@ -54,6 +83,8 @@ pub struct SystemStage {
     executor: Box<dyn ParallelSystemExecutor>,
     /// Groups of systems; each set has its own run criterion.
     system_sets: Vec<VirtualSystemSet>,
+    /// Run criteria shared by system sets.
+    labeled_run_criteria: Vec<LabeledRunCriteria>,
     /// Topologically sorted exclusive systems that want to be ran at the start of the stage.
     exclusive_at_start: Vec<ExclusiveSystemContainer>,
     /// Topologically sorted exclusive systems that want to be ran after parallel systems but
@@ -63,6 +94,12 @@ pub struct SystemStage {
     exclusive_at_end: Vec<ExclusiveSystemContainer>,
     /// Topologically sorted parallel systems.
     parallel: Vec<ParallelSystemContainer>,
+    /// For each parallel system, the number of flush points it runs after. Parallel systems run in batches
+    /// separated by flush points, with the command buffers of each batch applied before the next one runs.
+    parallel_batches: Vec<usize>,
+    /// The number of batches of parallel systems; one more than the number of flush points any parallel system
+    /// runs after.
+    parallel_batch_count: usize,
     /// Determines if the stage was modified and needs to rebuild its graphs and orders.
     systems_modified: bool,
     /// Determines if the stage's executor was changed.
@@ -75,6 +112,8 @@ pub struct SystemStage {
     uninitialized_at_end: Vec<usize>,
     /// Newly inserted systems that will be initialized at the next opportunity.
     uninitialized_parallel: Vec<usize>,
+    /// The world change tick at which the change ticks of this stage's systems were last checked.
+    last_tick_check: u32,
 }
 
 impl SystemStage {
@@ -87,16 +126,20 @@ impl SystemStage {
             world_id: None,
             executor,
             system_sets: vec![set],
+            labeled_run_criteria: Vec::new(),
             exclusive_at_start: Default::default(),
             exclusive_before_commands: Default::default(),
             exclusive_at_end: Default::default(),
             parallel: vec![],
+            parallel_batches: vec![],
+            parallel_batch_count: 1,
             systems_modified: true,
             executor_modified: true,
             uninitialized_parallel: vec![],
             uninitialized_at_start: vec![],
             uninitialized_before_commands: vec![],
             uninitialized_at_end: vec![],
+            last_tick_check: Default::default(),
         }
     }
 
@@ -141,6 +184,50 @@ impl SystemStage {
         self
     }
 
+    /// Uses the result of the run criteria with the given label for the systems added directly to the stage.
+    pub fn with_run_criteria_label(mut self, label: impl RunCriteriaLabel) -> Self {
+        self.system_sets[0].run_criteria.set_label(Box::new(label));
+        self
+    }
+
+    pub fn with_labeled_run_criteria<S: System<In = (), Out = ShouldRun>>(
+        mut self,
+        label: impl RunCriteriaLabel,
+        system: S,
+    ) -> Self {
+        self.add_labeled_run_criteria(label, system);
+        self
+    }
+
+    /// Adds a run criteria that system sets can share with
+    /// [SystemSet::with_run_criteria_label] or [SystemSet::with_run_criteria_piped]. It is evaluated once each
+    /// time the stage checks run criteria, no matter how many sets use it.
+    ///
+    /// # Panics
+    /// Panics if a run criteria with the same label was already added to the stage.
+    pub fn add_labeled_run_criteria<S: System<In = (), Out = ShouldRun>>(
+        &mut self,
+        label: impl RunCriteriaLabel,
+        system: S,
+    ) -> &mut Self {
+        let label: BoxedRunCriteriaLabel = Box::new(label);
+        if self
+            .labeled_run_criteria
+            .iter()
+            .any(|criteria| *criteria.label == *label)
+        {
+            panic!("Run criteria with label {:?} already exists.", label);
+        }
+        let mut run_criteria = RunCriteria::default();
+        run_criteria.set(Box::new(system));
+        self.labeled_run_criteria.push(LabeledRunCriteria {
+            label,
+            run_criteria,
+            should_run: ShouldRun::No,
+        });
+        self
+    }
+
     pub fn add_system_set(&mut self, system_set: SystemSet) -> &mut Self {
         self.systems_modified = true;
         let SystemSet {
@@ -196,6 +283,77 @@ impl SystemStage {
         self
     }
 
+    /// Removes the systems with the given ids, along with the system sets left without any systems. Returns the
+    /// number of systems removed.
+    pub fn remove_systems(&mut self, ids: &[SystemId]) -> usize {
+        let mut emptied_sets = HashSet::default();
+        let removed = remove_systems_from(
+            &mut self.exclusive_at_start,
+            &mut self.uninitialized_at_start,
+            ids,
+            &mut emptied_sets,
+        ) + remove_systems_from(
+            &mut self.exclusive_before_commands,
+            &mut self.uninitialized_before_commands,
+            ids,
+            &mut emptied_sets,
+        ) + remove_systems_from(
+            &mut self.exclusive_at_end,
+            &mut self.uninitialized_at_end,
+            ids,
+            &mut emptied_sets,
+        ) + remove_systems_from(
+            &mut self.parallel,
+            &mut self.uninitialized_parallel,
+            ids,
+            &mut emptied_sets,
+        );
+        if removed == 0 {
+            return 0;
+        }
+        self.systems_modified = true;
+
+        // The first set belongs to the stage itself and is always kept.
+        emptied_sets.remove(&0);
+        for set in self
+            .exclusive_at_start
+            .iter()
+            .chain(self.exclusive_before_commands.iter())
+            .chain(self.exclusive_at_end.iter())
+            .map(|container| container.system_set())
+            .chain(self.parallel.iter().map(|container| container.system_set()))
+        {
+            emptied_sets.remove(&set);
+        }
+        if !emptied_sets.is_empty() {
+            let mut new_indices = Vec::with_capacity(self.system_sets.len());
+            let mut kept = 0;
+            for index in 0..self.system_sets.len() {
+                new_indices.push(kept);
+                if !emptied_sets.contains(&index) {
+                    kept += 1;
+                }
+            }
+            let mut index = 0;
+            self.system_sets.retain(|_| {
+                index += 1;
+                !emptied_sets.contains(&(index - 1))
+            });
+            for container in self
+                .exclusive_at_start
+                .iter_mut()
+                .chain(self.exclusive_before_commands.iter_mut())
+                .chain(self.exclusive_at_end.iter_mut())
+            {
+                container.set_system_set(new_indices[container.system_set()]);
+            }
+            for container in self.parallel.iter_mut() {
+                container.set_system_set(new_indices[container.system_set()]);
+            }
+        }
+        removed
+    }
+
     fn initialize_systems(&mut self, world: &mut World) {
         for index in self.uninitialized_at_start.drain(..) {
             self.exclusive_at_start[index]
@@ -239,6 +397,7 @@ impl SystemStage {
                 )
             }
         }
+        self.rebuild_parallel_batches();
         match sort_systems(&mut self.exclusive_at_start) {
             Ok(()) => (),
             Err(LabelNotFound(label)) => {
@@ -304,6 +463,26 @@ impl SystemStage {
         }
     }
 
+    /// Assigns every parallel system to the batch that follows the last flush point it runs after.
+    /// Parallel systems must be sorted.
+    fn rebuild_parallel_batches(&mut self) {
+        self.parallel_batches.clear();
+        self.parallel_batch_count = 1;
+        for container in self.parallel.iter() {
+            let batch = container
+                .dependencies()
+                .iter()
+                .map(|&dependency| {
+                    let is_flush_point = self.parallel[dependency].is_flush_point();
+                    self.parallel_batches[dependency] + is_flush_point as usize
+                })
+                .max()
+                .unwrap_or(0);
+            self.parallel_batch_count = self.parallel_batch_count.max(batch + 1);
+            self.parallel_batches.push(batch);
+        }
+    }
+
     /// Logs execution order ambiguities between systems. System orders must be fresh.
     fn report_ambiguities(&self) {
         debug_assert!(!self.systems_modified);
@@ -544,8 +723,8 @@ fn find_ambiguities(systems: &[impl SystemContainer]) -> Vec<(usize, usize)> {
     ambiguities
 }
 
-impl Stage for SystemStage {
-    fn run(&mut self, world: &mut World) {
+impl SystemStage {
+    fn validate_world(&mut self, world: &World) {
         if let Some(world_id) = self.world_id {
             assert!(
                 world.id() == world_id,
@@ -554,17 +733,10 @@ impl Stage for SystemStage {
         } else {
             self.world_id = Some(world.id());
         }
-        // Evaluate sets' run criteria, initialize sets as needed, detect if any sets were changed.
-        let mut has_work = false;
-        for system_set in self.system_sets.iter_mut() {
-            let result = system_set.run_criteria.should_run(world);
-            match result {
-                ShouldRun::Yes | ShouldRun::YesAndCheckAgain => has_work = true,
-                ShouldRun::No | ShouldRun::NoAndCheckAgain => (),
-            }
-            system_set.should_run = result;
-        }
+    }
 
+    /// Initializes newly added systems and rebuilds the execution order if the stage was modified.
+    fn update_systems(&mut self, world: &mut World) {
         if self.systems_modified {
             self.initialize_systems(world);
             self.rebuild_orders_and_dependencies();
@@ -578,6 +750,201 @@ impl Stage for SystemStage {
             self.executor.rebuild_cached_data(&self.parallel);
             self.executor_modified = false;
         }
+    }
+
+    /// Describes the systems of this stage, their execution order constraints, their data access and their
+    /// ambiguities. Systems that were added since the stage last ran are initialized first, as if the stage was
+    /// about to run.
+    pub fn graph(&mut self, world: &mut World) -> SystemStageGraph {
+        self.validate_world(world);
+        self.update_systems(world);
+
+        let components = world.components();
+        let mut graph = SystemStageGraph {
+            system_sets: self
+                .system_sets
+                .iter()
+                .map(|set| SystemSetNode {
+                    run_criteria: set.run_criteria.name(),
+                })
+                .collect(),
+            systems: Vec::new(),
+            ambiguities: Vec::new(),
+        };
+        describe_systems(
+            &mut graph,
+            &self.exclusive_at_start,
+            |_| (SystemKind::ExclusiveAtStart, None),
+            components,
+        );
+        describe_systems(
+            &mut graph,
+            &self.parallel,
+            |container| {
+                let kind = if container.is_flush_point() {
+                    SystemKind::FlushPoint
+                } else {
+                    SystemKind::Parallel
+                };
+                (kind, Some(container.system().component_access()))
+            },
+            components,
+        );
+        describe_systems(
+            &mut graph,
+            &self.exclusive_before_commands,
+            |_| (SystemKind::ExclusiveBeforeCommands, None),
+            components,
+        );
+        describe_systems(
+            &mut graph,
+            &self.exclusive_at_end,
+            |_| (SystemKind::ExclusiveAtEnd, None),
+            components,
+        );
+        graph
+    }
+
+    /// Clamps the change ticks of the world and of this stage's systems once enough ticks have passed, so that
+    /// change detection keeps working after the world's change tick wraps around.
+    fn check_change_ticks(&mut self, world: &mut World) {
+        let change_tick = world.change_tick();
+        let time_since_last_check = change_tick.wrapping_sub(self.last_tick_check);
+        if time_since_last_check > CHECK_TICK_THRESHOLD {
+            for system_set in self.system_sets.iter_mut() {
+                system_set.run_criteria.check_change_tick(change_tick);
+            }
+            for criteria in self.labeled_run_criteria.iter_mut() {
+                criteria.run_criteria.check_change_tick(change_tick);
+            }
+            for container in self
+                .exclusive_at_start
+                .iter_mut()
+                .chain(self.exclusive_before_commands.iter_mut())
+                .chain(self.exclusive_at_end.iter_mut())
+            {
+                container.system_mut().check_change_tick(change_tick);
+            }
+            for container in self.parallel.iter_mut() {
+                container.system_mut().check_change_tick(change_tick);
+            }
+            world.check_change_ticks();
+            self.last_tick_check = change_tick;
+        }
+    }
+}
+
+/// Removes the systems with the given ids from `systems`, and the indices of removed systems from `uninitialized`.
+/// The sets of removed systems are added to `sets`. Returns the number of systems removed.
+fn remove_systems_from<C: SystemContainer>(
+    systems: &mut Vec<C>,
+    uninitialized: &mut Vec<usize>,
+    ids: &[SystemId],
+    sets: &mut HashSet<usize>,
+) -> usize {
+    let mut new_indices = Vec::with_capacity(systems.len());
+    let mut kept = 0;
+    for container in systems.iter() {
+        if ids.contains(&container.system_id()) {
+            sets.insert(container.system_set());
+            new_indices.push(None);
+        } else {
+            new_indices.push(Some(kept));
+            kept += 1;
+        }
+    }
+    let removed = systems.len() - kept;
+    if removed > 0 {
+        systems.retain(|container| !ids.contains(&container.system_id()));
+        *uninitialized = uninitialized
+            .iter()
+            .filter_map(|&index| new_indices[index])
+            .collect();
+    }
+    removed
+}
+
+/// Appends a group of sorted systems to `graph`, along with the ambiguities between them. Exclusive systems have
+/// no `access`.
+fn describe_systems<C: SystemContainer>(
+    graph: &mut SystemStageGraph,
+    systems: &[C],
+    describe: impl Fn(&C) -> (SystemKind, Option<&Access<ComponentId>>),
+    components: &Components,
+) {
+    let offset = graph.systems.len();
+    for container in systems {
+        let (kind, access) = describe(container);
+        graph.systems.push(SystemNode {
+            name: container.system_name(),
+            label: container
+                .label()
+                .as_ref()
+                .map(|label| format!("{:?}", label)),
+            kind,
+            system_set: container.system_set(),
+            dependencies: container
+                .dependencies()
+                .iter()
+                .map(|dependency| dependency + offset)
+                .collect(),
+            access: access.map(|access| SystemAccess::new(access, components)),
+        });
+    }
+    for (a, b) in find_ambiguities(systems) {
+        let conflicts = match (describe(&systems[a]).1, describe(&systems[b]).1) {
+            (Some(a), Some(b)) => component_names(a.get_conflicts(b), components),
+            _ => Vec::new(),
+        };
+        graph.ambiguities.push(SystemAmbiguity {
+            systems: (a + offset, b + offset),
+            conflicts,
+        });
+    }
+}
+
+/// Runs an exclusive system, recording its span if a [ScheduleProfiler] is present.
+fn run_exclusive_system(container: &mut ExclusiveSystemContainer, world: &mut World) {
+    if !world.contains_resource::<ScheduleProfiler>() {
+        container.system_mut().run(world);
+        return;
+    }
+    let start = Instant::now();
+    container.system_mut().run(world);
+    let end = Instant::now();
+    if let Some(mut profiler) = world.get_resource_mut::<ScheduleProfiler>() {
+        profiler.record_system(SystemSpan {
+            name: container.system_name(),
+            thread: current_thread_index(),
+            start,
+            end,
+            wait: Duration::from_secs(0),
+        });
+    }
+}
+
+impl Stage for SystemStage {
+    fn run(&mut self, world: &mut World) {
+        self.validate_world(world);
+        // Evaluate sets' run criteria, initialize sets as needed, detect if any sets were changed.
+        for criteria in self.labeled_run_criteria.iter_mut() {
+            criteria.should_run = criteria.run_criteria.should_run(world, None);
+        }
+        let mut has_work = false;
+        for system_set in self.system_sets.iter_mut() {
+            let parent = LabeledRunCriteria::parent_result(
+                &self.labeled_run_criteria,
+                &system_set.run_criteria,
+            );
+            let result = system_set.run_criteria.should_run(world, parent);
+            match result {
+                ShouldRun::Yes | ShouldRun::YesAndCheckAgain => has_work = true,
+                ShouldRun::No | ShouldRun::NoAndCheckAgain => (),
+            }
+            system_set.should_run = result;
+        }
+
+        self.update_systems(world);
 
         while has_work {
             // Run systems that want to be at the start of stage.
@@ -585,30 +952,43 @@ impl Stage for SystemStage {
                 if let ShouldRun::Yes | ShouldRun::YesAndCheckAgain =
                     self.system_sets[container.system_set()].should_run
                 {
-                    container.system_mut().run(world);
+                    run_exclusive_system(container, world);
                 }
             }
 
-            // Run parallel systems using the executor.
+            // Run parallel systems using the executor, one batch at a time. The buffers of every batch but the
+            // last are applied at the flush point that ends it.
             // TODO: hard dependencies, nested sets, whatever... should be evaluated here.
-            for container in &mut self.parallel {
-                match self.system_sets[container.system_set()].should_run {
-                    ShouldRun::Yes | ShouldRun::YesAndCheckAgain => container.should_run = true,
-                    ShouldRun::No | ShouldRun::NoAndCheckAgain => container.should_run = false,
+            for batch in 0..self.parallel_batch_count {
+                if batch > 0 {
+                    for container in &mut self.parallel {
+                        if container.should_run {
+                            container.system_mut().apply_buffers(world);
+                        }
+                    }
                 }
+                for (container, &system_batch) in
+                    self.parallel.iter_mut().zip(self.parallel_batches.iter())
+                {
+                    container.should_run = system_batch == batch
+                        && match self.system_sets[container.system_set()].should_run {
+                            ShouldRun::Yes | ShouldRun::YesAndCheckAgain => true,
+                            ShouldRun::No | ShouldRun::NoAndCheckAgain => false,
+                        };
+                }
+                self.executor.run_systems(&mut self.parallel, world);
             }
-            self.executor.run_systems(&mut self.parallel, world);
 
             // Run systems that want to be between parallel systems and their command buffers.
             for container in &mut self.exclusive_before_commands {
                 if let ShouldRun::Yes | ShouldRun::YesAndCheckAgain =
                     self.system_sets[container.system_set()].should_run
                 {
-                    container.system_mut().run(world);
+                    run_exclusive_system(container, world);
                 }
             }
 
-            // Apply parallel systems' buffers.
+            // Apply the buffers of the last batch of parallel systems.
             for container in &mut self.parallel {
                 if container.should_run {
                     container.system_mut().apply_buffers(world);
@@ -620,18 +1000,27 @@ impl Stage for SystemStage {
                 if let ShouldRun::Yes | ShouldRun::YesAndCheckAgain =
                     self.system_sets[container.system_set()].should_run
                 {
-                    container.system_mut().run(world);
+                    run_exclusive_system(container, world);
                 }
             }
 
-            // Reevaluate system sets' run criteria.
+            // Reevaluate labeled run criteria, then system sets' run criteria.
+            for criteria in self.labeled_run_criteria.iter_mut() {
+                if criteria.should_run.check_again() {
+                    criteria.should_run = criteria.run_criteria.should_run(world, None);
+                }
+            }
             has_work = false;
             for system_set in self.system_sets.iter_mut() {
                 match system_set.should_run {
                     ShouldRun::No => (),
                     ShouldRun::Yes => system_set.should_run = ShouldRun::No,
                     ShouldRun::YesAndCheckAgain | ShouldRun::NoAndCheckAgain => {
-                        let new_result = system_set.run_criteria.should_run(world);
+                        let parent = LabeledRunCriteria::parent_result(
+                            &self.labeled_run_criteria,
+                            &system_set.run_criteria,
+                        );
+                        let new_result = system_set.run_criteria.should_run(world, parent);
                         match new_result {
                             ShouldRun::Yes | ShouldRun::YesAndCheckAgain => has_work = true,
                             ShouldRun::No | ShouldRun::NoAndCheckAgain => (),
@@ -641,6 +1030,8 @@ impl Stage for SystemStage {
                 }
             }
         }
+
+        self.check_change_ticks(world);
     }
 }
 
@@ -648,10 +1039,11 @@ impl Stage for SystemStage {
 mod tests {
     use crate::{
         schedule::{
-            BoxedSystemLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            ShouldRun, SingleThreadedExecutor, Stage, SystemSet, SystemStage,
+            apply_buffers, BoxedSystemLabel, ExclusiveSystemDescriptorCoercion,
+            ParallelSystemDescriptorCoercion, ShouldRun, SingleThreadedExecutor, Stage, SystemSet,
+            SystemStage,
         },
-        system::{IntoExclusiveSystem, IntoSystem, Query, ResMut},
+        system::{Commands, IntoExclusiveSystem, IntoSystem, Query, ResMut},
         world::World,
     };
 
@@ -1095,6 +1487,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn flush_points() {
+        fn spawn(mut commands: Commands) {
+            commands.spawn((0usize,));
+        }
+
+        fn count(query: Query<&usize>, mut counts: ResMut<Vec<usize>>) {
+            counts.push(query.iter().count());
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::parallel()
+            .with_system(spawn.system().label("spawn").before("flush"))
+            .with_system(count.system().label("0").after("spawn").before("flush"))
+            .with_system(apply_buffers().label("flush"))
+            .with_system(count.system().label("1").after("flush"))
+            .with_system(spawn.system().after("1").before("second flush"))
+            .with_system(apply_buffers().label("second flush").after("1"))
+            .with_system(count.system().after("second flush"));
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 1, 2]);
+
+        world.get_resource_mut::<Vec<usize>>().unwrap().clear();
+        stage.set_executor(Box::new(SingleThreadedExecutor::default()));
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![2, 3, 4]);
+    }
+
     #[test]
     #[should_panic]
     fn parallel_cycle_1() {
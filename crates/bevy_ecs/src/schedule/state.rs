@@ -1,6 +1,7 @@
 use crate::{
     component::Component,
-    schedule::{Stage, SystemDescriptor, SystemStage},
+    schedule::{ShouldRun, Stage, SystemDescriptor, SystemStage},
+    system::{IntoSystem, Res, System},
     world::World,
 };
 use bevy_utils::HashMap;
@@ -11,6 +12,9 @@ pub(crate) struct StateStages {
     update: Box<dyn Stage>,
     enter: Box<dyn Stage>,
     exit: Box<dyn Stage>,
+    pause: Box<dyn Stage>,
+    resume: Box<dyn Stage>,
+    inactive: Box<dyn Stage>,
 }
 
 impl Default for StateStages {
@@ -19,10 +23,19 @@ impl Default for StateStages {
             enter: Box::new(SystemStage::parallel()),
             update: Box::new(SystemStage::parallel()),
             exit: Box::new(SystemStage::parallel()),
+            pause: Box::new(SystemStage::parallel()),
+            resume: Box::new(SystemStage::parallel()),
+            inactive: Box::new(SystemStage::parallel()),
         }
     }
 }
 
+/// Runs the stages registered for the states of a [State] resource.
+///
+/// * `enter` runs when a state is set or pushed, and `exit` when it is replaced or popped.
+/// * `pause` runs when another state is pushed on top of a state, and `resume` when that state is popped again.
+/// * `update` runs every time the stage runs for the current state, and `inactive` for every state below it in
+///   the stack, starting from the bottom.
 pub struct StateStage<T> {
     stages: HashMap<Discriminant<T>, StateStages>,
 }
@@ -52,6 +65,21 @@ impl<T> StateStage<T> {
         self
     }
 
+    pub fn with_pause_stage<S: Stage>(mut self, state: T, stage: S) -> Self {
+        self.set_pause_stage(state, stage);
+        self
+    }
+
+    pub fn with_resume_stage<S: Stage>(mut self, state: T, stage: S) -> Self {
+        self.set_resume_stage(state, stage);
+        self
+    }
+
+    pub fn with_inactive_stage<S: Stage>(mut self, state: T, stage: S) -> Self {
+        self.set_inactive_stage(state, stage);
+        self
+    }
+
     pub fn set_enter_stage<S: Stage>(&mut self, state: T, stage: S) -> &mut Self {
         let stages = self.state_stages(state);
         stages.enter = Box::new(stage);
@@ -70,6 +98,24 @@ impl<T> StateStage<T> {
         self
     }
 
+    pub fn set_pause_stage<S: Stage>(&mut self, state: T, stage: S) -> &mut Self {
+        let stages = self.state_stages(state);
+        stages.pause = Box::new(stage);
+        self
+    }
+
+    pub fn set_resume_stage<S: Stage>(&mut self, state: T, stage: S) -> &mut Self {
+        let stages = self.state_stages(state);
+        stages.resume = Box::new(stage);
+        self
+    }
+
+    pub fn set_inactive_stage<S: Stage>(&mut self, state: T, stage: S) -> &mut Self {
+        let stages = self.state_stages(state);
+        stages.inactive = Box::new(stage);
+        self
+    }
+
     pub fn on_state_enter(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
         self.enter_stage(state, |system_stage: &mut SystemStage| {
             system_stage.add_system(system)
@@ -88,6 +134,28 @@ impl<T> StateStage<T> {
         })
     }
 
+    pub fn on_state_pause(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.pause_stage(state, |system_stage: &mut SystemStage| {
+            system_stage.add_system(system)
+        })
+    }
+
+    pub fn on_state_resume(&mut self, state: T, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.resume_stage(state, |system_stage: &mut SystemStage| {
+            system_stage.add_system(system)
+        })
+    }
+
+    pub fn on_state_inactive(
+        &mut self,
+        state: T,
+        system: impl Into<SystemDescriptor>,
+    ) -> &mut Self {
+        self.inactive_stage(state, |system_stage: &mut SystemStage| {
+            system_stage.add_system(system)
+        })
+    }
+
     pub fn enter_stage<S: Stage, F: FnOnce(&mut S) -> &mut S>(
         &mut self,
         state: T,
@@ -133,121 +201,306 @@ impl<T> StateStage<T> {
         self
     }
 
+    pub fn pause_stage<S: Stage, F: FnOnce(&mut S) -> &mut S>(
+        &mut self,
+        state: T,
+        func: F,
+    ) -> &mut Self {
+        let stages = self.state_stages(state);
+        func(
+            stages
+                .pause
+                .downcast_mut()
+                .expect("'Pause' stage does not match the given type"),
+        );
+        self
+    }
+
+    pub fn resume_stage<S: Stage, F: FnOnce(&mut S) -> &mut S>(
+        &mut self,
+        state: T,
+        func: F,
+    ) -> &mut Self {
+        let stages = self.state_stages(state);
+        func(
+            stages
+                .resume
+                .downcast_mut()
+                .expect("'Resume' stage does not match the given type"),
+        );
+        self
+    }
+
+    pub fn inactive_stage<S: Stage, F: FnOnce(&mut S) -> &mut S>(
+        &mut self,
+        state: T,
+        func: F,
+    ) -> &mut Self {
+        let stages = self.state_stages(state);
+        func(
+            stages
+                .inactive
+                .downcast_mut()
+                .expect("'Inactive' stage does not match the given type"),
+        );
+        self
+    }
+
     fn state_stages(&mut self, state: T) -> &mut StateStages {
         self.stages
             .entry(std::mem::discriminant(&state))
             .or_default()
     }
+
+    fn run_state_stage(
+        &mut self,
+        world: &mut World,
+        state: Discriminant<T>,
+        stage: fn(&mut StateStages) -> &mut Box<dyn Stage>,
+    ) {
+        if let Some(state_stages) = self.stages.get_mut(&state) {
+            stage(state_stages).run(world);
+        }
+    }
 }
 
 #[allow(clippy::mem_discriminant_non_enum)]
 impl<T: Component + Clone> Stage for StateStage<T> {
     fn run(&mut self, world: &mut World) {
-        let current_stage = loop {
-            let (next_stage, current_stage) = {
-                let mut state = world
-                    .get_resource_mut::<State<T>>()
-                    .expect("Missing state resource");
-                let result = (
-                    state.next.as_ref().map(|next| std::mem::discriminant(next)),
-                    std::mem::discriminant(&state.current),
-                );
-
-                state.apply_next();
-
-                result
-            };
-
-            // if next_stage is Some, we just applied a new state
-            if let Some(next_stage) = next_stage {
-                if next_stage != current_stage {
-                    if let Some(current_state_stages) = self.stages.get_mut(&current_stage) {
-                        current_state_stages.exit.run(world);
+        loop {
+            let transition = world
+                .get_resource_mut::<State<T>>()
+                .expect("Missing state resource")
+                .apply_next();
+
+            match transition {
+                Some(StateTransition::Set { exited, entered }) => {
+                    if exited != entered {
+                        self.run_state_stage(world, exited, |stages| &mut stages.exit);
                     }
+                    self.run_state_stage(world, entered, |stages| &mut stages.enter);
                 }
-
-                if let Some(next_state_stages) = self.stages.get_mut(&next_stage) {
-                    next_state_stages.enter.run(world);
+                Some(StateTransition::Push { paused, entered }) => {
+                    self.run_state_stage(world, paused, |stages| &mut stages.pause);
+                    self.run_state_stage(world, entered, |stages| &mut stages.enter);
                 }
-            } else {
-                break current_stage;
+                Some(StateTransition::Pop { exited, resumed }) => {
+                    self.run_state_stage(world, exited, |stages| &mut stages.exit);
+                    self.run_state_stage(world, resumed, |stages| &mut stages.resume);
+                }
+                None => break,
             }
-        };
+        }
 
-        if let Some(current_state_stages) = self.stages.get_mut(&current_stage) {
-            current_state_stages.update.run(world);
+        let stack = world
+            .get_resource::<State<T>>()
+            .expect("Missing state resource")
+            .stack
+            .iter()
+            .map(std::mem::discriminant)
+            .collect::<Vec<_>>();
+        let (current_stage, inactive_stages) = stack.split_last().unwrap();
+        for inactive_stage in inactive_stages {
+            self.run_state_stage(world, *inactive_stage, |stages| &mut stages.inactive);
         }
+        self.run_state_stage(world, *current_stage, |stages| &mut stages.update);
     }
 }
+
 #[derive(Debug, Error)]
 pub enum StateError {
     #[error("Attempted to change the state to the current state.")]
     AlreadyInState,
     #[error("Attempted to queue a state change, but there was already a state queued.")]
     StateAlreadyQueued,
+    #[error("Attempted to pop the last state off the state stack.")]
+    StackUnderflow,
+}
+
+#[derive(Debug)]
+enum ScheduledOperation<T> {
+    Set(T),
+    Push(T),
+    Pop,
+}
+
+/// A state change applied by [State::apply_next], described by the discriminants of the states involved.
+enum StateTransition<T> {
+    Set {
+        exited: Discriminant<T>,
+        entered: Discriminant<T>,
+    },
+    Push {
+        paused: Discriminant<T>,
+        entered: Discriminant<T>,
+    },
+    Pop {
+        exited: Discriminant<T>,
+        resumed: Discriminant<T>,
+    },
 }
 
+/// A stack of states. The state on top of the stack is the current one, and the states below it are inactive
+/// until the states above them are popped. State changes are queued and applied by a [StateStage].
 #[derive(Debug)]
 pub struct State<T: Clone> {
     previous: Option<T>,
-    current: T,
-    next: Option<T>,
+    stack: Vec<T>,
+    scheduled: Option<ScheduledOperation<T>>,
 }
 
 #[allow(clippy::mem_discriminant_non_enum)]
 impl<T: Clone> State<T> {
     pub fn new(state: T) -> Self {
         Self {
-            current: state.clone(),
+            stack: vec![state.clone()],
             previous: None,
             // add value to queue so that we "enter" the state
-            next: Some(state),
+            scheduled: Some(ScheduledOperation::Set(state)),
         }
     }
 
     pub fn current(&self) -> &T {
-        &self.current
+        self.stack.last().unwrap()
     }
 
     pub fn previous(&self) -> Option<&T> {
         self.previous.as_ref()
     }
 
+    /// Returns the state that will be current once the queued state change is applied, if any.
     pub fn next(&self) -> Option<&T> {
-        self.next.as_ref()
+        match self.scheduled.as_ref()? {
+            ScheduledOperation::Set(state) | ScheduledOperation::Push(state) => Some(state),
+            ScheduledOperation::Pop => self.inactives().last(),
+        }
+    }
+
+    /// Returns the states below the current state in the stack, starting from the bottom.
+    pub fn inactives(&self) -> &[T] {
+        &self.stack[..self.stack.len() - 1]
     }
 
     /// Queue a state change. This will fail if there is already a state in the queue, or if the given `state` matches the current state
     pub fn set_next(&mut self, state: T) -> Result<(), StateError> {
-        if std::mem::discriminant(&self.current) == std::mem::discriminant(&state) {
+        self.check_not_current(&state)?;
+        self.check_not_queued()?;
+        self.scheduled = Some(ScheduledOperation::Set(state));
+        Ok(())
+    }
+
+    /// Same as [Self::set_next], but if there is already a next state, it will be overwritten instead of failing
+    pub fn overwrite_next(&mut self, state: T) -> Result<(), StateError> {
+        self.check_not_current(&state)?;
+        self.scheduled = Some(ScheduledOperation::Set(state));
+        Ok(())
+    }
+
+    /// Queue pushing a state on top of the stack, pausing the current state. This will fail if there is already
+    /// a state change in the queue, or if the given `state` matches the current state
+    pub fn push(&mut self, state: T) -> Result<(), StateError> {
+        self.check_not_current(&state)?;
+        self.check_not_queued()?;
+        self.scheduled = Some(ScheduledOperation::Push(state));
+        Ok(())
+    }
+
+    /// Same as [Self::push], but if there is already a state change queued, it will be overwritten instead of
+    /// failing
+    pub fn overwrite_push(&mut self, state: T) -> Result<(), StateError> {
+        self.check_not_current(&state)?;
+        self.scheduled = Some(ScheduledOperation::Push(state));
+        Ok(())
+    }
+
+    /// Queue popping the current state off the stack, resuming the state below it. This will fail if there is
+    /// already a state change in the queue, or if the current state is the only state in the stack
+    pub fn pop(&mut self) -> Result<(), StateError> {
+        self.check_can_pop()?;
+        self.check_not_queued()?;
+        self.scheduled = Some(ScheduledOperation::Pop);
+        Ok(())
+    }
+
+    /// Same as [Self::pop], but if there is already a state change queued, it will be overwritten instead of
+    /// failing
+    pub fn overwrite_pop(&mut self) -> Result<(), StateError> {
+        self.check_can_pop()?;
+        self.scheduled = Some(ScheduledOperation::Pop);
+        Ok(())
+    }
+
+    fn check_not_current(&self, state: &T) -> Result<(), StateError> {
+        if std::mem::discriminant(self.current()) == std::mem::discriminant(state) {
             return Err(StateError::AlreadyInState);
         }
+        Ok(())
+    }
 
-        if self.next.is_some() {
+    fn check_not_queued(&self) -> Result<(), StateError> {
+        if self.scheduled.is_some() {
             return Err(StateError::StateAlreadyQueued);
         }
-
-        self.next = Some(state);
         Ok(())
     }
 
-    /// Same as [Self::set_next], but if there is already a next state, it will be overwritten instead of failing
-    pub fn overwrite_next(&mut self, state: T) -> Result<(), StateError> {
-        if std::mem::discriminant(&self.current) == std::mem::discriminant(&state) {
-            return Err(StateError::AlreadyInState);
+    fn check_can_pop(&self) -> Result<(), StateError> {
+        if self.stack.len() == 1 {
+            return Err(StateError::StackUnderflow);
         }
-
-        self.next = Some(state);
         Ok(())
     }
 
-    fn apply_next(&mut self) {
-        if let Some(next) = self.next.take() {
-            let previous = std::mem::replace(&mut self.current, next);
-            if std::mem::discriminant(&previous) != std::mem::discriminant(&self.current) {
-                self.previous = Some(previous)
+    fn apply_next(&mut self) -> Option<StateTransition<T>> {
+        let transition = match self.scheduled.take()? {
+            ScheduledOperation::Set(next) => {
+                let previous = std::mem::replace(self.stack.last_mut().unwrap(), next);
+                let transition = StateTransition::Set {
+                    exited: std::mem::discriminant(&previous),
+                    entered: std::mem::discriminant(self.current()),
+                };
+                if std::mem::discriminant(&previous) != std::mem::discriminant(self.current()) {
+                    self.previous = Some(previous)
+                }
+                transition
             }
-        }
+            ScheduledOperation::Push(next) => {
+                let transition = StateTransition::Push {
+                    paused: std::mem::discriminant(self.current()),
+                    entered: std::mem::discriminant(&next),
+                };
+                self.previous = Some(self.current().clone());
+                self.stack.push(next);
+                transition
+            }
+            ScheduledOperation::Pop => {
+                let previous = self.stack.pop().unwrap();
+                let transition = StateTransition::Pop {
+                    exited: std::mem::discriminant(&previous),
+                    resumed: std::mem::discriminant(self.current()),
+                };
+                self.previous = Some(previous);
+                transition
+            }
+        };
+        Some(transition)
+    }
+}
+
+#[allow(clippy::mem_discriminant_non_enum)]
+impl<T: Component + Clone> State<T> {
+    /// Returns a run criteria that runs while the current state has the same variant as `state`. Combine it with
+    /// other run criteria through [RunCriteriaCombinators](crate::schedule::RunCriteriaCombinators).
+    pub fn in_state(state: T) -> impl System<In = (), Out = ShouldRun> {
+        let discriminant = std::mem::discriminant(&state);
+        (move |current: Res<State<T>>| {
+            if std::mem::discriminant(current.current()) == discriminant {
+                ShouldRun::Yes
+            } else {
+                ShouldRun::No
+            }
+        })
+        .system()
     }
 }
 
@@ -255,6 +508,122 @@ impl<T: Clone> Deref for State<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        &self.current
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::ResMut;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum MyState {
+        Game,
+        Menu,
+        Dialog,
+    }
+
+    macro_rules! log {
+        ($name:expr) => {{
+            fn log(mut log: ResMut<Vec<&'static str>>) {
+                log.push($name);
+            }
+            log.system()
+        }};
+    }
+
+    #[test]
+    fn state_stack() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<&'static str>::new());
+        world.insert_resource(State::new(MyState::Game));
+
+        fn stage_with(system: impl Into<SystemDescriptor>) -> SystemStage {
+            SystemStage::single_threaded().with_system(system)
+        }
+        let mut stage = StateStage::<MyState>::default()
+            .with_enter_stage(MyState::Game, stage_with(log!("enter")))
+            .with_enter_stage(MyState::Menu, stage_with(log!("enter")))
+            .with_enter_stage(MyState::Dialog, stage_with(log!("enter")))
+            .with_update_stage(MyState::Game, stage_with(log!("game update")))
+            .with_inactive_stage(MyState::Game, stage_with(log!("game inactive")))
+            .with_pause_stage(MyState::Game, stage_with(log!("game pause")))
+            .with_resume_stage(MyState::Game, stage_with(log!("game resume")))
+            .with_exit_stage(MyState::Menu, stage_with(log!("menu exit")))
+            .with_update_stage(MyState::Menu, stage_with(log!("menu update")))
+            .with_inactive_stage(MyState::Menu, stage_with(log!("menu inactive")))
+            .with_update_stage(MyState::Dialog, stage_with(log!("dialog update")));
+
+        let mut run = |world: &mut World| {
+            stage.run(world);
+            std::mem::take(&mut *world.get_resource_mut::<Vec<&'static str>>().unwrap())
+        };
+
+        assert_eq!(run(&mut world), vec!["enter", "game update"]);
+
+        let mut state = world.get_resource_mut::<State<MyState>>().unwrap();
+        assert!(matches!(state.pop(), Err(StateError::StackUnderflow)));
+        state.push(MyState::Menu).unwrap();
+        assert_eq!(state.next(), Some(&MyState::Menu));
+        assert_eq!(
+            run(&mut world),
+            vec!["game pause", "enter", "game inactive", "menu update"]
+        );
+
+        let mut state = world.get_resource_mut::<State<MyState>>().unwrap();
+        state.push(MyState::Dialog).unwrap();
+        assert!(matches!(state.pop(), Err(StateError::StateAlreadyQueued)));
+        assert_eq!(
+            run(&mut world),
+            vec!["enter", "game inactive", "menu inactive", "dialog update"]
+        );
+
+        let state = world.get_resource::<State<MyState>>().unwrap();
+        assert_eq!(state.current(), &MyState::Dialog);
+        assert_eq!(state.inactives(), &[MyState::Game, MyState::Menu]);
+
+        let mut state = world.get_resource_mut::<State<MyState>>().unwrap();
+        state.pop().unwrap();
+        assert_eq!(state.next(), Some(&MyState::Menu));
+        assert_eq!(run(&mut world), vec!["game inactive", "menu update"]);
+
+        let mut state = world.get_resource_mut::<State<MyState>>().unwrap();
+        state.pop().unwrap();
+        assert_eq!(
+            run(&mut world),
+            vec!["menu exit", "game resume", "game update"]
+        );
+        let state = world.get_resource::<State<MyState>>().unwrap();
+        assert_eq!(state.previous(), Some(&MyState::Menu));
+        assert!(state.inactives().is_empty());
+    }
+
+    #[test]
+    fn in_state_run_criteria() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<&'static str>::new());
+        world.insert_resource(State::new(MyState::Game));
+        let mut stage = SystemStage::single_threaded()
+            .with_run_criteria(State::in_state(MyState::Menu))
+            .with_system(log!("menu"));
+
+        stage.run(&mut world);
+        assert!(world
+            .get_resource::<Vec<&'static str>>()
+            .unwrap()
+            .is_empty());
+        world
+            .get_resource_mut::<State<MyState>>()
+            .unwrap()
+            .overwrite_push(MyState::Menu)
+            .unwrap();
+        // The state only changes once a StateStage applies it.
+        StateStage::<MyState>::default().run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<Vec<&'static str>>().unwrap(),
+            vec!["menu"]
+        );
     }
 }
@@ -3,15 +3,18 @@ use crate::{
         BoxedAmbiguitySetLabel, BoxedSystemLabel, ExclusiveSystemDescriptor,
         ParallelSystemDescriptor,
     },
-    system::{ExclusiveSystem, System},
+    system::{ExclusiveSystem, System, SystemId},
 };
 use std::{borrow::Cow, ptr::NonNull};
 
 pub(super) trait SystemContainer {
     fn display_name(&self) -> Cow<'static, str>;
+    fn system_name(&self) -> Cow<'static, str>;
+    fn system_id(&self) -> SystemId;
     fn dependencies(&self) -> &[usize];
     fn set_dependencies(&mut self, dependencies: impl IntoIterator<Item = usize>);
     fn system_set(&self) -> usize;
+    fn set_system_set(&mut self, set: usize);
     fn label(&self) -> &Option<BoxedSystemLabel>;
     fn before(&self) -> &[BoxedSystemLabel];
     fn after(&self) -> &[BoxedSystemLabel];
@@ -55,6 +58,14 @@ impl SystemContainer for ExclusiveSystemContainer {
             .unwrap_or_else(|| self.system.name())
     }
 
+    fn system_name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn system_id(&self) -> SystemId {
+        self.system.id()
+    }
+
     fn dependencies(&self) -> &[usize] {
         &self.dependencies
     }
@@ -68,6 +79,10 @@ impl SystemContainer for ExclusiveSystemContainer {
         self.set
     }
 
+    fn set_system_set(&mut self, set: usize) {
+        self.set = set;
+    }
+
     fn label(&self) -> &Option<BoxedSystemLabel> {
         &self.label
     }
@@ -98,6 +113,7 @@ pub struct ParallelSystemContainer {
     before: Vec<BoxedSystemLabel>,
     after: Vec<BoxedSystemLabel>,
     ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    flush_point: bool,
 }
 
 impl SystemContainer for ParallelSystemContainer {
@@ -108,6 +124,14 @@ impl SystemContainer for ParallelSystemContainer {
             .unwrap_or_else(|| self.system().name())
     }
 
+    fn system_name(&self) -> Cow<'static, str> {
+        self.system().name()
+    }
+
+    fn system_id(&self) -> SystemId {
+        self.system().id()
+    }
+
     fn dependencies(&self) -> &[usize] {
         &self.dependencies
     }
@@ -121,6 +145,10 @@ impl SystemContainer for ParallelSystemContainer {
         self.set
     }
 
+    fn set_system_set(&mut self, set: usize) {
+        self.set = set;
+    }
+
     fn label(&self) -> &Option<BoxedSystemLabel> {
         &self.label
     }
@@ -147,6 +175,13 @@ impl SystemContainer for ParallelSystemContainer {
 unsafe impl Send for ParallelSystemContainer {}
 unsafe impl Sync for ParallelSystemContainer {}
 
+impl Drop for ParallelSystemContainer {
+    fn drop(&mut self) {
+        // SAFE: the pointer was created from a box in `from_descriptor` and is not used after this.
+        unsafe { drop(Box::from_raw(self.system.as_ptr())) };
+    }
+}
+
 impl ParallelSystemContainer {
     pub(crate) fn from_descriptor(descriptor: ParallelSystemDescriptor, set: usize) -> Self {
         ParallelSystemContainer {
@@ -158,6 +193,7 @@ impl ParallelSystemContainer {
             before: descriptor.before,
             after: descriptor.after,
             ambiguity_sets: descriptor.ambiguity_sets,
+            flush_point: descriptor.flush_point,
         }
     }
 
@@ -189,4 +225,9 @@ impl ParallelSystemContainer {
     pub fn dependencies(&self) -> &[usize] {
         &self.dependencies
     }
+
+    /// Returns true if this container was created with [apply_buffers](super::apply_buffers).
+    pub fn is_flush_point(&self) -> bool {
+        self.flush_point
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
     schedule::{AmbiguitySetLabel, BoxedAmbiguitySetLabel, BoxedSystemLabel, SystemLabel},
-    system::{BoxedSystem, ExclusiveSystem, ExclusiveSystemCoerced, ExclusiveSystemFn, System},
+    system::{
+        BoxedSystem, ExclusiveSystem, ExclusiveSystemCoerced, ExclusiveSystemFn, IntoSystem,
+        System, SystemId,
+    },
 };
 
 /// Encapsulates a system and information on when it run in a `SystemStage`.
@@ -9,7 +12,8 @@ use crate::{
 /// * Parallel, accepts non-exclusive systems.
 /// * At start, accepts exclusive systems; runs before parallel systems.
 /// * Before commands, accepts exclusive systems; runs after parallel systems, but before their
-/// command buffers are applied.
+/// command buffers are applied. Buffers of parallel systems that ran before a flush point created with
+/// [apply_buffers] have already been applied at that point.
 /// * At end, accepts exclusive systems; runs after parallel systems' command buffers have
 /// been applied.
 ///
@@ -35,6 +39,16 @@ pub enum SystemDescriptor {
     Exclusive(ExclusiveSystemDescriptor),
 }
 
+impl SystemDescriptor {
+    /// Returns the id of the described system, which identifies it once it is added to a stage.
+    pub fn system_id(&self) -> SystemId {
+        match self {
+            SystemDescriptor::Parallel(descriptor) => descriptor.system.id(),
+            SystemDescriptor::Exclusive(descriptor) => descriptor.system.id(),
+        }
+    }
+}
+
 pub struct SystemLabelMarker;
 
 impl From<ParallelSystemDescriptor> for SystemDescriptor {
@@ -83,6 +97,7 @@ pub struct ParallelSystemDescriptor {
     pub(crate) before: Vec<BoxedSystemLabel>,
     pub(crate) after: Vec<BoxedSystemLabel>,
     pub(crate) ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    pub(crate) flush_point: bool,
 }
 
 fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescriptor {
@@ -92,9 +107,33 @@ fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescrip
         before: Vec::new(),
         after: Vec::new(),
         ambiguity_sets: Vec::new(),
+        flush_point: false,
     }
 }
 
+/// Creates a flush point for the parallel systems of a `SystemStage`. Parallel systems that run before the
+/// flush point have their command buffers applied before any parallel system ordered after it runs, so that
+/// entities they spawn are visible without adding another stage. Flush points are ordered with labels, like
+/// any other parallel system.
+///
+/// # Example
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # fn spawn_enemies() {}
+/// # fn target_enemies() {}
+/// SystemStage::parallel()
+///     .with_system(spawn_enemies.system().before("flush"))
+///     .with_system(apply_buffers().label("flush"))
+///     .with_system(target_enemies.system().after("flush"));
+/// ```
+pub fn apply_buffers() -> ParallelSystemDescriptor {
+    fn apply_buffers_flush_point() {}
+
+    let mut descriptor = new_parallel_descriptor(Box::new(apply_buffers_flush_point.system()));
+    descriptor.flush_point = true;
+    descriptor
+}
+
 pub trait ParallelSystemDescriptorCoercion {
     /// Assigns a label to the system.
     fn label(self, label: impl SystemLabel) -> ParallelSystemDescriptor;
@@ -1,6 +1,6 @@
 use crate::{
-    schedule::{RunCriteria, ShouldRun, SystemDescriptor},
-    system::System,
+    schedule::{RunCriteria, RunCriteriaLabel, ShouldRun, SystemDescriptor},
+    system::{System, SystemId},
 };
 
 /// Describes a group of systems sharing one run criterion.
@@ -36,6 +36,45 @@ impl SystemSet {
         self
     }
 
+    /// Uses the result of the run criteria with the given label, which must be added to the stage with
+    /// [SystemStage::add_labeled_run_criteria](crate::schedule::SystemStage::add_labeled_run_criteria).
+    pub fn with_run_criteria_label(mut self, label: impl RunCriteriaLabel) -> Self {
+        self.add_run_criteria_label(label);
+        self
+    }
+
+    pub fn add_run_criteria_label(&mut self, label: impl RunCriteriaLabel) -> &mut Self {
+        self.run_criteria.set_label(Box::new(label));
+        self
+    }
+
+    /// Passes the result of the run criteria with the given label to `system`, and uses its result.
+    pub fn with_run_criteria_piped<S: System<In = ShouldRun, Out = ShouldRun>>(
+        mut self,
+        label: impl RunCriteriaLabel,
+        system: S,
+    ) -> Self {
+        self.add_run_criteria_piped(label, system);
+        self
+    }
+
+    pub fn add_run_criteria_piped<S: System<In = ShouldRun, Out = ShouldRun>>(
+        &mut self,
+        label: impl RunCriteriaLabel,
+        system: S,
+    ) -> &mut Self {
+        self.run_criteria
+            .set_piped(Box::new(label), Box::new(system));
+        self
+    }
+
+    /// Returns the ids of the systems in this set.
+    pub fn system_ids(&self) -> impl Iterator<Item = SystemId> + '_ {
+        self.descriptors
+            .iter()
+            .map(|descriptor| descriptor.system_id())
+    }
+
     pub fn with_system(mut self, system: impl Into<SystemDescriptor>) -> Self {
         self.add_system(system);
         self
@@ -1,5 +1,5 @@
 use crate::{
-    component::{ComponentFlags, ComponentId, ComponentInfo},
+    component::{ComponentId, ComponentInfo, ComponentTicks},
     entity::Entity,
     storage::BlobVec,
 };
@@ -87,7 +87,7 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
 #[derive(Debug)]
 pub struct ComponentSparseSet {
     dense: BlobVec,
-    flags: UnsafeCell<Vec<ComponentFlags>>,
+    ticks: UnsafeCell<Vec<ComponentTicks>>,
     entities: Vec<Entity>,
     sparse: SparseArray<Entity, usize>,
 }
@@ -96,7 +96,7 @@ impl ComponentSparseSet {
     pub fn new(component_info: &ComponentInfo, capacity: usize) -> Self {
         Self {
             dense: BlobVec::new(component_info.layout(), component_info.drop(), capacity),
-            flags: UnsafeCell::new(Vec::with_capacity(capacity)),
+            ticks: UnsafeCell::new(Vec::with_capacity(capacity)),
             entities: Vec::with_capacity(capacity),
             sparse: Default::default(),
         }
@@ -112,25 +112,26 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
-    /// Inserts the `entity` key and component `value` pair into this sparse set.
+    /// Inserts the `entity` key and component `value` pair into this sparse set. If `entity` already had a value,
+    /// it is marked as changed at `change_tick`. Otherwise it is marked as added at `change_tick`.
     /// The caller is responsible for ensuring the value is not dropped. This collection will drop the value when needed.
     /// # Safety
     /// The `value` pointer must point to a valid address that matches the `Layout` inside the `ComponentInfo` given
     /// when constructing this sparse set.
-    pub unsafe fn insert(&mut self, entity: Entity, value: *mut u8, flags: ComponentFlags) {
-        let dense = &mut self.dense;
-        let entities = &mut self.entities;
-        let flag_list = self.flags.get_mut();
-        let dense_index = *self.sparse.get_or_insert_with(entity, move || {
-            flag_list.push(ComponentFlags::empty());
-            entities.push(entity);
-            dense.push_uninit()
-        });
-        // SAFE: dense_index exists thanks to the call above
-        self.dense.set_unchecked(dense_index, value);
-        (*self.flags.get())
-            .get_unchecked_mut(dense_index)
-            .insert(flags);
+    pub unsafe fn insert(&mut self, entity: Entity, value: *mut u8, change_tick: u32) {
+        if let Some(dense_index) = self.sparse.get(entity).cloned() {
+            self.dense.set_unchecked(dense_index, value);
+            self.ticks
+                .get_mut()
+                .get_unchecked_mut(dense_index)
+                .set_changed(change_tick);
+        } else {
+            let dense_index = self.dense.push_uninit();
+            self.dense.set_unchecked(dense_index, value);
+            self.sparse.insert(entity, dense_index);
+            self.ticks.get_mut().push(ComponentTicks::new(change_tick));
+            self.entities.push(entity);
+        }
     }
 
     #[inline]
@@ -151,27 +152,28 @@ impl ComponentSparseSet {
     /// # Safety
     /// ensure the same entity is not accessed twice at the same time
     #[inline]
-    pub unsafe fn get_with_flags(&self, entity: Entity) -> Option<(*mut u8, *mut ComponentFlags)> {
-        let flags = &mut *self.flags.get();
+    pub unsafe fn get_with_ticks(&self, entity: Entity) -> Option<(*mut u8, *mut ComponentTicks)> {
+        let ticks = &mut *self.ticks.get();
         self.sparse.get(entity).map(move |dense_index| {
             let dense_index = *dense_index;
             // SAFE: if the sparse index points to something in the dense vec, it exists
             (
                 self.dense.get_unchecked(dense_index),
-                flags.get_unchecked_mut(dense_index) as *mut ComponentFlags,
+                ticks.get_unchecked_mut(dense_index) as *mut ComponentTicks,
             )
         })
     }
 
     /// # Safety
     /// ensure the same entity is not accessed twice at the same time
+    #[allow(clippy::mut_from_ref)]
     #[inline]
-    pub unsafe fn get_flags(&self, entity: Entity) -> Option<&mut ComponentFlags> {
-        let flags = &mut *self.flags.get();
+    pub unsafe fn get_ticks(&self, entity: Entity) -> Option<&mut ComponentTicks> {
+        let ticks = &mut *self.ticks.get();
         self.sparse.get(entity).map(move |dense_index| {
             let dense_index = *dense_index;
             // SAFE: if the sparse index points to something in the dense vec, it exists
-            flags.get_unchecked_mut(dense_index)
+            ticks.get_unchecked_mut(dense_index)
         })
     }
 
@@ -179,10 +181,7 @@ impl ComponentSparseSet {
     /// It is the caller's responsibility to drop the returned ptr (if Some is returned).
     pub fn remove_and_forget(&mut self, entity: Entity) -> Option<*mut u8> {
         self.sparse.remove(entity).map(|dense_index| {
-            // SAFE: unique access to flags
-            unsafe {
-                (*self.flags.get()).swap_remove(dense_index);
-            }
+            self.ticks.get_mut().swap_remove(dense_index);
             self.entities.swap_remove(dense_index);
             let is_last = dense_index == self.dense.len() - 1;
             // SAFE: dense_index was just removed from `sparse`, which ensures that it is valid
@@ -197,7 +196,7 @@ impl ComponentSparseSet {
 
     pub fn remove(&mut self, entity: Entity) -> bool {
         if let Some(dense_index) = self.sparse.remove(entity) {
-            self.flags.get_mut().swap_remove(dense_index);
+            self.ticks.get_mut().swap_remove(dense_index);
             self.entities.swap_remove(dense_index);
             let is_last = dense_index == self.dense.len() - 1;
             // SAFE: if the sparse index points to something in the dense vec, it exists
@@ -212,10 +211,9 @@ impl ComponentSparseSet {
         }
     }
 
-    pub(crate) fn clear_flags(&mut self) {
-        let flags = self.flags.get_mut().iter_mut();
-        for component_flags in flags {
-            *component_flags = ComponentFlags::empty();
+    pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
+        for component_ticks in self.ticks.get_mut() {
+            component_ticks.check_ticks(change_tick);
         }
     }
 }
@@ -442,9 +440,9 @@ impl SparseSets {
         self.sets.get_mut(component_id)
     }
 
-    pub(crate) fn clear_flags(&mut self) {
+    pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
         for set in self.sets.values_mut() {
-            set.clear_flags();
+            set.check_change_ticks(change_tick);
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::{
     archetype::ArchetypeId,
-    component::{ComponentFlags, ComponentId, ComponentInfo, Components},
+    component::{
+        check_tick, is_tick_newer, mark_changed_summary, ComponentId, ComponentInfo,
+        ComponentTicks, Components,
+    },
     entity::Entity,
     storage::{BlobVec, SparseSet},
 };
@@ -9,6 +12,7 @@ use std::{
     cell::UnsafeCell,
     hash::{Hash, Hasher},
     ptr::NonNull,
+    sync::atomic::{AtomicU32, Ordering},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,7 +38,9 @@ impl TableId {
 pub struct Column {
     pub(crate) component_id: ComponentId,
     pub(crate) data: BlobVec,
-    pub(crate) flags: UnsafeCell<Vec<ComponentFlags>>,
+    pub(crate) ticks: UnsafeCell<Vec<ComponentTicks>>,
+    /// The newest `changed` tick of any component in this column. Kept up to date by every write to the column.
+    pub(crate) changed_tick: AtomicU32,
 }
 
 impl Column {
@@ -43,7 +49,8 @@ impl Column {
         Column {
             component_id: component_info.id(),
             data: BlobVec::new(component_info.layout(), component_info.drop(), capacity),
-            flags: UnsafeCell::new(Vec::with_capacity(capacity)),
+            ticks: UnsafeCell::new(Vec::with_capacity(capacity)),
+            changed_tick: AtomicU32::new(0),
         }
     }
 
@@ -55,6 +62,32 @@ impl Column {
         self.data.set_unchecked(row, data);
     }
 
+    /// # Safety
+    /// Assumes data has already been allocated for the given row/column.
+    /// Allows aliased mutable accesses to the row's ComponentTicks. Caller must ensure that this does not happen.
+    #[inline]
+    pub unsafe fn set_ticks_unchecked(&self, row: usize, ticks: ComponentTicks, change_tick: u32) {
+        *self.get_ticks_unchecked_mut(row) = ticks;
+        self.mark_changed(ticks.changed, change_tick);
+    }
+
+    /// Records that a component in this column was added or changed at `tick`.
+    #[inline]
+    pub fn mark_changed(&self, tick: u32, change_tick: u32) {
+        mark_changed_summary(&self.changed_tick, tick, change_tick);
+    }
+
+    /// Returns false if no component in this column was added or changed after `last_change_tick`. A return value
+    /// of true means that some component _may_ have changed, as removed components are not accounted for.
+    #[inline]
+    pub fn has_changes(&self, last_change_tick: u32, change_tick: u32) -> bool {
+        is_tick_newer(
+            self.changed_tick.load(Ordering::Relaxed),
+            last_change_tick,
+            change_tick,
+        )
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.data.len()
@@ -67,35 +100,35 @@ impl Column {
 
     /// # Safety
     /// Assumes data has already been allocated for the given row/column.
-    /// Allows aliased mutable accesses to the row's ComponentFlags. Caller must ensure that this does not happen.
+    /// Allows aliased mutable accesses to the row's ComponentTicks. Caller must ensure that this does not happen.
     #[inline]
     #[allow(clippy::mut_from_ref)]
-    pub unsafe fn get_flags_unchecked_mut(&self, row: usize) -> &mut ComponentFlags {
+    pub unsafe fn get_ticks_unchecked_mut(&self, row: usize) -> &mut ComponentTicks {
         debug_assert!(row < self.len());
-        (*self.flags.get()).get_unchecked_mut(row)
+        (&mut *self.ticks.get()).get_unchecked_mut(row)
     }
 
     #[inline]
     pub(crate) unsafe fn swap_remove_unchecked(&mut self, row: usize) {
         self.data.swap_remove_and_drop_unchecked(row);
-        (*self.flags.get()).swap_remove(row);
+        (*self.ticks.get()).swap_remove(row);
     }
 
     #[inline]
     pub(crate) unsafe fn swap_remove_and_forget_unchecked(
         &mut self,
         row: usize,
-    ) -> (*mut u8, ComponentFlags) {
+    ) -> (*mut u8, ComponentTicks) {
         let data = self.data.swap_remove_and_forget_unchecked(row);
-        let flags = (*self.flags.get()).swap_remove(row);
-        (data, flags)
+        let ticks = (*self.ticks.get()).swap_remove(row);
+        (data, ticks)
     }
 
     /// # Safety
     /// allocated value must be immediately set at the returned row
     pub(crate) unsafe fn push_uninit(&mut self) -> usize {
         let row = self.data.push_uninit();
-        (*self.flags.get()).push(ComponentFlags::empty());
+        (*self.ticks.get()).push(ComponentTicks::new(0));
         row
     }
 
@@ -104,8 +137,8 @@ impl Column {
         self.data.reserve(additional);
         // SAFE: unique access to self
         unsafe {
-            let flags = &mut (*self.flags.get());
-            flags.reserve(additional);
+            let ticks = &mut (*self.ticks.get());
+            ticks.reserve(additional);
         }
     }
 
@@ -119,8 +152,8 @@ impl Column {
     /// # Safety
     /// must ensure rust mutability rules are not violated
     #[inline]
-    pub unsafe fn get_flags_mut_ptr(&self) -> *mut ComponentFlags {
-        (*self.flags.get()).as_mut_ptr()
+    pub unsafe fn get_ticks_mut_ptr(&self) -> *mut ComponentTicks {
+        (*self.ticks.get()).as_mut_ptr()
     }
 
     /// # Safety
@@ -134,17 +167,17 @@ impl Column {
     /// # Safety
     /// must ensure rust mutability rules are not violated
     #[inline]
-    pub unsafe fn get_flags_unchecked(&self, row: usize) -> *mut ComponentFlags {
-        debug_assert!(row < (*self.flags.get()).len());
-        self.get_flags_mut_ptr().add(row)
+    pub unsafe fn get_ticks_unchecked(&self, row: usize) -> *mut ComponentTicks {
+        debug_assert!(row < (*self.ticks.get()).len());
+        self.get_ticks_mut_ptr().add(row)
     }
 
     #[inline]
-    pub(crate) fn clear_flags(&mut self) {
-        let flags = unsafe { (*self.flags.get()).iter_mut() };
-        for component_flags in flags {
-            *component_flags = ComponentFlags::empty();
+    pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
+        for component_ticks in self.ticks.get_mut() {
+            component_ticks.check_ticks(change_tick);
         }
+        check_tick(self.changed_tick.get_mut(), change_tick);
     }
 }
 
@@ -211,6 +244,7 @@ impl Table {
 
     /// Moves the `row` column values to `new_table`, for the columns shared between both tables. Returns the index of the
     /// new row in `new_table` and the entity in this table swapped in to replace it (if an entity was swapped in).
+    /// `change_tick` is the world's current change tick, used to keep the change summaries of `new_table` up to date.
     /// missing columns will be "forgotten". It is the caller's responsibility to drop them
     /// # Safety
     /// Row must be in-bounds
@@ -218,15 +252,16 @@ impl Table {
         &mut self,
         row: usize,
         new_table: &mut Table,
+        change_tick: u32,
     ) -> TableMoveResult {
         debug_assert!(row < self.len());
         let is_last = row == self.entities.len() - 1;
         let new_row = new_table.allocate(self.entities.swap_remove(row));
         for column in self.columns.values_mut() {
-            let (data, flags) = column.swap_remove_and_forget_unchecked(row);
+            let (data, ticks) = column.swap_remove_and_forget_unchecked(row);
             if let Some(new_column) = new_table.get_column_mut(column.component_id) {
                 new_column.set_unchecked(new_row, data);
-                *new_column.get_flags_unchecked_mut(new_row) = flags;
+                new_column.set_ticks_unchecked(new_row, ticks, change_tick);
             }
         }
         TableMoveResult {
@@ -241,21 +276,23 @@ impl Table {
 
     /// Moves the `row` column values to `new_table`, for the columns shared between both tables. Returns the index of the
     /// new row in `new_table` and the entity in this table swapped in to replace it (if an entity was swapped in).
+    /// `change_tick` is the world's current change tick, used to keep the change summaries of `new_table` up to date.
     /// # Safety
     /// row must be in-bounds
     pub unsafe fn move_to_and_drop_missing_unchecked(
         &mut self,
         row: usize,
         new_table: &mut Table,
+        change_tick: u32,
     ) -> TableMoveResult {
         debug_assert!(row < self.len());
         let is_last = row == self.entities.len() - 1;
         let new_row = new_table.allocate(self.entities.swap_remove(row));
         for column in self.columns.values_mut() {
             if let Some(new_column) = new_table.get_column_mut(column.component_id) {
-                let (data, flags) = column.swap_remove_and_forget_unchecked(row);
+                let (data, ticks) = column.swap_remove_and_forget_unchecked(row);
                 new_column.set_unchecked(new_row, data);
-                *new_column.get_flags_unchecked_mut(new_row) = flags;
+                new_column.set_ticks_unchecked(new_row, ticks, change_tick);
             } else {
                 column.swap_remove_unchecked(row);
             }
@@ -272,21 +309,23 @@ impl Table {
 
     /// Moves the `row` column values to `new_table`, for the columns shared between both tables. Returns the index of the
     /// new row in `new_table` and the entity in this table swapped in to replace it (if an entity was swapped in).
+    /// `change_tick` is the world's current change tick, used to keep the change summaries of `new_table` up to date.
     /// # Safety
     /// `row` must be in-bounds. `new_table` must contain every component this table has
     pub unsafe fn move_to_superset_unchecked(
         &mut self,
         row: usize,
         new_table: &mut Table,
+        change_tick: u32,
     ) -> TableMoveResult {
         debug_assert!(row < self.len());
         let is_last = row == self.entities.len() - 1;
         let new_row = new_table.allocate(self.entities.swap_remove(row));
         for column in self.columns.values_mut() {
             let new_column = new_table.get_column_mut(column.component_id).unwrap();
-            let (data, flags) = column.swap_remove_and_forget_unchecked(row);
+            let (data, ticks) = column.swap_remove_and_forget_unchecked(row);
             new_column.set_unchecked(new_row, data);
-            *new_column.get_flags_unchecked_mut(new_row) = flags;
+            new_column.set_ticks_unchecked(new_row, ticks, change_tick);
         }
         TableMoveResult {
             new_row,
@@ -334,7 +373,7 @@ impl Table {
         self.entities.push(entity);
         for column in self.columns.values_mut() {
             column.data.set_len(self.entities.len());
-            (*column.flags.get()).push(ComponentFlags::empty());
+            (*column.ticks.get()).push(ComponentTicks::new(0));
         }
         index
     }
@@ -354,9 +393,9 @@ impl Table {
         self.entities.is_empty()
     }
 
-    pub(crate) fn clear_flags(&mut self) {
+    pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
         for column in self.columns.values_mut() {
-            column.clear_flags();
+            column.check_change_ticks(change_tick);
         }
     }
 
@@ -458,9 +497,9 @@ impl Tables {
         self.tables.iter()
     }
 
-    pub(crate) fn clear_flags(&mut self) {
+    pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
         for table in self.tables.iter_mut() {
-            table.clear_flags();
+            table.check_change_ticks(change_tick);
         }
     }
 }
@@ -468,10 +507,14 @@ impl Tables {
 #[cfg(test)]
 mod tests {
     use crate::{
-        component::{Components, TypeInfo},
+        component::{mark_changed_summary, Components, TypeInfo},
         entity::Entity,
         storage::Table,
     };
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
 
     #[test]
     fn table() {
@@ -497,4 +540,27 @@ mod tests {
         assert_eq!(table.capacity(), 256);
         assert_eq!(table.len(), 200);
     }
+
+    #[test]
+    fn changed_summary_keeps_newest_tick() {
+        let summary = Arc::new(AtomicU32::new(0));
+        let change_tick = 1000;
+        let writers = (0..4u32)
+            .map(|writer| {
+                let summary = summary.clone();
+                std::thread::spawn(move || {
+                    for tick in (1..=change_tick).filter(|tick| tick % 4 == writer) {
+                        mark_changed_summary(&summary, tick, change_tick);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(summary.load(Ordering::Relaxed), change_tick);
+        mark_changed_summary(&summary, 10, change_tick);
+        assert_eq!(summary.load(Ordering::Relaxed), change_tick);
+    }
 }
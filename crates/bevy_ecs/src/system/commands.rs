@@ -1,10 +1,11 @@
 use crate::{
     bundle::Bundle,
     component::Component,
-    entity::{Entities, Entity},
+    entity::{Entities, Entity, ReserveEntitiesIterator},
     world::World,
 };
 use bevy_utils::tracing::debug;
+use parking_lot::Mutex;
 use std::marker::PhantomData;
 
 /// A [World] mutation
@@ -82,10 +83,64 @@ impl<'a> Commands<'a> {
     /// # example_system.system();
     /// ```
     pub fn spawn(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.spawn_id(bundle);
+        self
+    }
+
+    /// Like [`Self::spawn`], but returns the new entity's id instead of `self`. The id is reserved immediately, so it
+    /// can be used right away, for example to insert components or relations that refer to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// struct Target(Entity);
+    ///
+    /// fn example_system(mut commands: Commands) {
+    ///     let first = commands.spawn_id((1u32,));
+    ///     commands.spawn((Target(first),));
+    /// }
+    /// # example_system.system();
+    /// ```
+    pub fn spawn_id(&mut self, bundle: impl Bundle) -> Entity {
         let entity = self.entities.reserve_entity();
         self.set_current_entity(entity);
         self.insert_bundle(entity, bundle);
-        self
+        entity
+    }
+
+    /// Reserves a new [Entity] id that can be used immediately, for example to relate several new entities
+    /// to each other in the same system. The entity is created without components when the commands are
+    /// applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// struct Ship;
+    /// struct Turret;
+    /// struct MountedOn;
+    ///
+    /// fn spawn_ship(mut commands: Commands) {
+    ///     let ship = commands.reserve_entity();
+    ///     commands.insert_bundle(ship, (Ship,));
+    ///     for turret in commands.reserve_entities(2) {
+    ///         commands
+    ///             .insert_bundle(turret, (Turret,))
+    ///             .insert_relation::<MountedOn>(turret, ship);
+    ///     }
+    /// }
+    /// # spawn_ship.system();
+    /// ```
+    pub fn reserve_entity(&self) -> Entity {
+        self.entities.reserve_entity()
+    }
+
+    /// Reserves `count` new [Entity] ids at once. See [`Self::reserve_entity`].
+    pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator<'a> {
+        self.entities.reserve_entities(count)
     }
 
     /// Equivalent to iterating `bundles_iter` and calling [`Self::spawn`] on each bundle, but slightly more performant.
@@ -127,6 +182,26 @@ impl<'a> Commands<'a> {
         })
     }
 
+    /// Relates `entity` to `target` with a [Relation](crate::relation::Relation) of kind `K`.
+    /// The relation is not inserted if `target` no longer exists when the command is applied.
+    ///
+    /// See [crate::world::EntityMut::insert_relation].
+    pub fn insert_relation<K: 'static>(&mut self, entity: Entity, target: Entity) -> &mut Self {
+        self.add_command(InsertRelation::<K> {
+            entity,
+            target,
+            phantom: PhantomData,
+        })
+    }
+
+    /// See [crate::world::EntityMut::remove_relation].
+    pub fn remove_relation<K: 'static>(&mut self, entity: Entity) -> &mut Self {
+        self.add_command(RemoveRelation::<K> {
+            entity,
+            phantom: PhantomData,
+        })
+    }
+
     /// See [World::insert_resource].
     pub fn insert_resource<T: Component>(&mut self, resource: T) -> &mut Self {
         self.add_command(InsertResource { resource })
@@ -207,6 +282,14 @@ impl<'a> Commands<'a> {
         self
     }
 
+    /// Relates the current entity to `target` with a [Relation](crate::relation::Relation) of kind `K`.
+    ///
+    /// See [`Self::insert_relation`], [`Self::current_entity`].
+    pub fn with_relation<K: 'static>(&mut self, target: Entity) -> &mut Self {
+        let current_entity = self.current_entity.expect("Cannot add relation because the 'current entity' is not set. You should spawn an entity first.");
+        self.insert_relation::<K>(current_entity, target)
+    }
+
     /// Adds a command directly to the command list. Prefer this to [`Self::add_command_boxed`] if the type of `command` is statically known.
     pub fn add_command<C: Command>(&mut self, command: C) -> &mut Self {
         self.queue.push(Box::new(command));
@@ -241,6 +324,100 @@ impl<'a> Commands<'a> {
     }
 }
 
+/// A pool of [CommandQueue]s that can be written to from many threads at once.
+///
+/// Each [`ParallelCommands::command_scope`] borrows one queue from the pool for its duration, so
+/// concurrent scopes never contend on the same queue. When applied, the queues are applied one after
+/// another in the order of the sort keys of their scopes, so the result doesn't depend on how the
+/// scopes were scheduled across threads.
+#[derive(Default)]
+pub struct ParallelCommandQueue {
+    free: Mutex<Vec<CommandQueue>>,
+    queues: Mutex<Vec<(u64, CommandQueue)>>,
+}
+
+impl ParallelCommandQueue {
+    pub fn apply(&mut self, world: &mut World) {
+        let queues = self.queues.get_mut();
+        queues.sort_by_key(|(sort_key, _)| *sort_key);
+        for (_, queue) in queues.iter_mut() {
+            queue.apply(world);
+        }
+        self.free
+            .get_mut()
+            .extend(queues.drain(..).map(|(_, queue)| queue));
+    }
+
+    fn take_queue(&self) -> CommandQueue {
+        self.free.lock().pop().unwrap_or_default()
+    }
+
+    fn return_queue(&self, sort_key: u64, queue: CommandQueue) {
+        if queue.commands.is_empty() {
+            self.free.lock().push(queue);
+        } else {
+            self.queues.lock().push((sort_key, queue));
+        }
+    }
+}
+
+/// A thread-safe handle for queueing [Command]s, usable from inside
+/// [`Query::par_for_each_mut`](crate::system::Query::par_for_each_mut) closures.
+///
+/// Commands queued in a single [`Self::command_scope`] are applied together and in order. Different
+/// scopes are applied in the order of their sort keys, which makes the result deterministic as long
+/// as the keys are unique, for example by using [`Entity::to_bits`] of the entity being processed.
+///
+/// # Example
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_tasks::ComputeTaskPool;
+///
+/// struct Lifetime(u32);
+/// struct Explosion;
+///
+/// fn expire(
+///     pool: Res<ComputeTaskPool>,
+///     commands: ParallelCommands,
+///     mut query: Query<(Entity, &mut Lifetime)>,
+/// ) {
+///     query.par_for_each_mut(&pool, 32, |(entity, mut lifetime)| {
+///         lifetime.0 = lifetime.0.saturating_sub(1);
+///         if lifetime.0 == 0 {
+///             commands.command_scope(entity.to_bits(), |mut commands| {
+///                 commands.despawn(entity);
+///                 commands.spawn((Explosion,));
+///             });
+///         }
+///     });
+/// }
+/// # expire.system();
+/// ```
+#[derive(Clone, Copy)]
+pub struct ParallelCommands<'a> {
+    queue: &'a ParallelCommandQueue,
+    world: &'a World,
+}
+
+impl<'a> ParallelCommands<'a> {
+    pub fn new(queue: &'a ParallelCommandQueue, world: &'a World) -> Self {
+        Self { queue, world }
+    }
+
+    /// Runs `f` with a [Commands] that writes to a queue owned by the calling scope. Entities spawned
+    /// through it are reserved immediately, so their ids can be used right away.
+    ///
+    /// The commands are applied after those of scopes with a lower `sort_key`. Scopes with the same
+    /// key are applied in an unspecified order.
+    pub fn command_scope<R>(&self, sort_key: u64, f: impl FnOnce(Commands) -> R) -> R {
+        let mut queue = self.queue.take_queue();
+        let result = f(Commands::new(&mut queue, self.world));
+        self.queue.return_queue(sort_key, queue);
+        result
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct Spawn<T> {
     bundle: T,
@@ -350,6 +527,42 @@ where
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct InsertRelation<K> {
+    entity: Entity,
+    target: Entity,
+    phantom: PhantomData<fn() -> K>,
+}
+
+impl<K: 'static> Command for InsertRelation<K> {
+    fn write(self: Box<Self>, world: &mut World) {
+        if world.entities().get(self.target).is_none() {
+            debug!(
+                "Failed to relate {:?} to non-existent entity {:?}",
+                self.entity, self.target
+            );
+            return;
+        }
+        world
+            .entity_mut(self.entity)
+            .insert_relation::<K>(self.target);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RemoveRelation<K> {
+    entity: Entity,
+    phantom: PhantomData<fn() -> K>,
+}
+
+impl<K: 'static> Command for RemoveRelation<K> {
+    fn write(self: Box<Self>, world: &mut World) {
+        if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
+            entity_mut.remove_relation::<K>();
+        }
+    }
+}
+
 pub struct InsertResource<T: Component> {
     resource: T,
 }
@@ -374,9 +587,11 @@ impl<T: Component> Command for RemoveResource<T> {
 #[allow(clippy::float_cmp, clippy::approx_constant)]
 mod tests {
     use crate::{
-        system::{CommandQueue, Commands},
+        entity::Entity,
+        system::{Command, CommandQueue, Commands, ParallelCommandQueue, ParallelCommands},
         world::World,
     };
+    use bevy_tasks::TaskPool;
 
     #[test]
     fn commands() {
@@ -465,4 +680,112 @@ mod tests {
         assert!(!world.contains_resource::<i32>());
         assert!(world.contains_resource::<f64>());
     }
+
+    #[test]
+    fn reserved_entities() {
+        struct ChildOf;
+
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let (parent, children) = {
+            let mut commands = Commands::new(&mut queue, &world);
+            let parent = commands.reserve_entity();
+            let children = commands.reserve_entities(2).collect::<Vec<_>>();
+            commands.insert(parent, 1u32);
+            for child in children.iter() {
+                commands
+                    .insert(*child, 2u32)
+                    .insert_relation::<ChildOf>(*child, parent);
+            }
+            (parent, children)
+        };
+        queue.apply(&mut world);
+
+        assert_eq!(world.entities().len(), 3);
+        assert_eq!(world.get::<u32>(parent), Some(&1));
+        let mut related = world.relations().related::<ChildOf>(parent).to_vec();
+        related.sort();
+        assert_eq!(related, children);
+    }
+
+    #[test]
+    fn spawn_id() {
+        struct Target(Entity);
+
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let (first, second) = {
+            let mut commands = Commands::new(&mut queue, &world);
+            let first = commands.spawn_id((1u32,));
+            let second = commands.spawn_id((2u32, Target(first)));
+            assert_eq!(commands.current_entity(), Some(second));
+            (first, second)
+        };
+        queue.apply(&mut world);
+
+        assert_eq!(world.entities().len(), 2);
+        assert_eq!(world.get::<u32>(first), Some(&1));
+        assert_eq!(world.get::<u32>(second), Some(&2));
+        assert_eq!(world.get::<Target>(second).unwrap().0, first);
+    }
+
+    struct PushValue(u32);
+
+    impl Command for PushValue {
+        fn write(self: Box<Self>, world: &mut World) {
+            world
+                .get_resource_or_insert_with(Vec::<u32>::new)
+                .push(self.0);
+        }
+    }
+
+    #[test]
+    fn parallel_commands() {
+        let mut world = World::default();
+        let task_pool = TaskPool::default();
+        let mut queue = ParallelCommandQueue::default();
+        let entities = (0..100u32)
+            .map(|i| world.spawn().insert(i).id())
+            .collect::<Vec<_>>();
+        let mut query = world.query::<(Entity, &u32)>();
+        {
+            let commands = ParallelCommands::new(&queue, &world);
+            query.par_for_each(&world, &task_pool, 8, |(entity, &i)| {
+                commands.command_scope(i as u64, |mut commands| {
+                    if i % 2 == 0 {
+                        commands.despawn(entity);
+                    } else {
+                        commands.insert(entity, i as u64).spawn((i as i64,));
+                    }
+                    commands.add_command(PushValue(i));
+                });
+            });
+        }
+        queue.apply(&mut world);
+
+        assert_eq!(world.entities().len(), 100);
+        for (i, entity) in entities.into_iter().enumerate() {
+            if i % 2 == 0 {
+                assert!(world.get_entity(entity).is_none());
+            } else {
+                assert_eq!(world.get::<u64>(entity), Some(&(i as u64)));
+            }
+        }
+        let mut spawned = world
+            .query::<&i64>()
+            .iter(&world)
+            .copied()
+            .collect::<Vec<_>>();
+        spawned.sort_unstable();
+        assert_eq!(spawned, (0..100).filter(|i| i % 2 == 1).collect::<Vec<_>>());
+        // scopes are applied in the order of their sort keys
+        assert_eq!(
+            world.get_resource::<Vec<u32>>().unwrap(),
+            &(0..100).collect::<Vec<_>>()
+        );
+
+        // applying again is a no-op
+        queue.apply(&mut world);
+        assert_eq!(world.entities().len(), 100);
+    }
 }
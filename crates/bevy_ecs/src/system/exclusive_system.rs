@@ -1,4 +1,5 @@
 use crate::{
+    component::{check_tick, MAX_CHANGE_AGE},
     system::{BoxedSystem, IntoSystem, System, SystemId},
     world::World,
 };
@@ -12,12 +13,15 @@ pub trait ExclusiveSystem: Send + Sync + 'static {
     fn run(&mut self, world: &mut World);
 
     fn initialize(&mut self, world: &mut World);
+
+    fn check_change_tick(&mut self, change_tick: u32);
 }
 
 pub struct ExclusiveSystemFn {
     func: Box<dyn FnMut(&mut World) + Send + Sync + 'static>,
     name: Cow<'static, str>,
     id: SystemId,
+    last_change_tick: u32,
 }
 
 impl ExclusiveSystem for ExclusiveSystemFn {
@@ -30,10 +34,26 @@ impl ExclusiveSystem for ExclusiveSystemFn {
     }
 
     fn run(&mut self, world: &mut World) {
+        // queries and component accessors used directly on the world detect changes since the last run of this system
+        let saved_last_tick = world.last_change_tick;
+        world.last_change_tick = self.last_change_tick;
+
         (self.func)(world);
+
+        let change_tick = world.change_tick.get_mut();
+        self.last_change_tick = *change_tick;
+        *change_tick = change_tick.wrapping_add(1);
+
+        world.last_change_tick = saved_last_tick;
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.last_change_tick = world.change_tick().wrapping_sub(MAX_CHANGE_AGE);
     }
 
-    fn initialize(&mut self, _: &mut World) {}
+    fn check_change_tick(&mut self, change_tick: u32) {
+        check_tick(&mut self.last_change_tick, change_tick);
+    }
 }
 
 pub trait IntoExclusiveSystem<Params, SystemType> {
@@ -49,6 +69,7 @@ where
             func: Box::new(self),
             name: core::any::type_name::<F>().into(),
             id: SystemId::new(),
+            last_change_tick: 0,
         }
     }
 }
@@ -74,6 +95,10 @@ impl ExclusiveSystem for ExclusiveSystemCoerced {
     fn initialize(&mut self, world: &mut World) {
         self.system.initialize(world);
     }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system.check_change_tick(change_tick);
+    }
 }
 
 impl<S, Params, SystemType> IntoExclusiveSystem<(Params, SystemType), ExclusiveSystemCoerced> for S
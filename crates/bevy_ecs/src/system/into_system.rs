@@ -1,6 +1,6 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    component::ComponentId,
+    component::{check_tick, ComponentId, MAX_CHANGE_AGE},
     query::{Access, FilteredAccessSet},
     system::{System, SystemId, SystemParam, SystemParamFetch, SystemParamState},
     world::World,
@@ -13,18 +13,20 @@ pub struct SystemState {
     pub(crate) name: Cow<'static, str>,
     pub(crate) component_access_set: FilteredAccessSet<ComponentId>,
     pub(crate) archetype_component_access: Access<ArchetypeComponentId>,
+    pub(crate) last_change_tick: u32,
     // NOTE: this must be kept private. making a SystemState non-send is irreversible to prevent SystemParams from overriding each other
     is_send: bool,
 }
 
 impl SystemState {
-    fn new<T>() -> Self {
+    pub(crate) fn new<T>() -> Self {
         Self {
             name: std::any::type_name::<T>().into(),
             archetype_component_access: Access::default(),
             component_access_set: FilteredAccessSet::default(),
             is_send: true,
             id: SystemId::new(),
+            last_change_tick: 0,
         }
     }
 
@@ -138,12 +140,16 @@ where
 
     #[inline]
     unsafe fn run_unsafe(&mut self, input: Self::In, world: &World) -> Self::Out {
-        self.func.run(
+        let change_tick = world.increment_change_tick();
+        let out = self.func.run(
             input,
             self.param_state.as_mut().unwrap(),
             &self.system_state,
             world,
-        )
+            change_tick,
+        );
+        self.system_state.last_change_tick = change_tick;
+        out
     }
 
     #[inline]
@@ -152,8 +158,14 @@ where
         param_state.apply(world);
     }
 
+    #[inline]
+    fn check_change_tick(&mut self, change_tick: u32) {
+        check_tick(&mut self.system_state.last_change_tick, change_tick);
+    }
+
     #[inline]
     fn initialize(&mut self, world: &mut World) {
+        self.system_state.last_change_tick = world.change_tick().wrapping_sub(MAX_CHANGE_AGE);
         self.param_state = Some(<Param::Fetch as SystemParamState>::init(
             world,
             &mut self.system_state,
@@ -169,6 +181,7 @@ pub trait SystemParamFunction<In, Out, Param: SystemParam, Marker>: Send + Sync
         state: &mut Param::Fetch,
         system_state: &SystemState,
         world: &World,
+        change_tick: u32,
     ) -> Out;
 }
 
@@ -182,9 +195,9 @@ macro_rules! impl_system_function {
                 FnMut($(<<$param as SystemParam>::Fetch as SystemParamFetch>::Item),*) -> Out + Send + Sync + 'static, Out: 'static
         {
             #[inline]
-            fn run(&mut self, _input: (), state: &mut <($($param,)*) as SystemParam>::Fetch, system_state: &SystemState, world: &World) -> Out {
+            fn run(&mut self, _input: (), state: &mut <($($param,)*) as SystemParam>::Fetch, system_state: &SystemState, world: &World, change_tick: u32) -> Out {
                 unsafe {
-                    let ($($param,)*) = <<($($param,)*) as SystemParam>::Fetch as SystemParamFetch>::get_param(state, system_state, world);
+                    let ($($param,)*) = <<($($param,)*) as SystemParam>::Fetch as SystemParamFetch>::get_param(state, system_state, world, change_tick);
                     self($($param),*)
                 }
             }
@@ -198,9 +211,9 @@ macro_rules! impl_system_function {
                 FnMut(In<Input>, $(<<$param as SystemParam>::Fetch as SystemParamFetch>::Item),*) -> Out + Send + Sync + 'static, Out: 'static
         {
             #[inline]
-            fn run(&mut self, input: Input, state: &mut <($($param,)*) as SystemParam>::Fetch, system_state: &SystemState, world: &World) -> Out {
+            fn run(&mut self, input: Input, state: &mut <($($param,)*) as SystemParam>::Fetch, system_state: &SystemState, world: &World, change_tick: u32) -> Out {
                 unsafe {
-                    let ($($param,)*) = <<($($param,)*) as SystemParam>::Fetch as SystemParamFetch>::get_param(state, system_state, world);
+                    let ($($param,)*) = <<($($param,)*) as SystemParam>::Fetch as SystemParamFetch>::get_param(state, system_state, world, change_tick);
                     self(In(input), $($param),*)
                 }
             }
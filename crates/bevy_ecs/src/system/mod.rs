@@ -6,6 +6,7 @@ mod query;
 mod system;
 mod system_chaining;
 mod system_param;
+mod system_param_cache;
 
 pub use commands::*;
 pub use exclusive_system::*;
@@ -14,6 +15,7 @@ pub use query::*;
 pub use system::*;
 pub use system_chaining::*;
 pub use system_param::*;
+pub use system_param_cache::*;
 
 #[cfg(test)]
 mod tests {
@@ -153,6 +155,51 @@ mod tests {
         assert!(*world.get_resource::<bool>().unwrap(), "system ran");
     }
 
+    #[test]
+    fn changes_are_seen_by_systems_that_ran_earlier() {
+        fn detect_changes(query: Query<Entity, Changed<A>>, mut detected: ResMut<usize>) {
+            *detected += query.iter().count();
+        }
+
+        fn mutate_once(mut query: Query<&mut A>, mut mutated: Local<bool>) {
+            if !*mutated {
+                for mut a in query.iter_mut() {
+                    *a = A;
+                }
+                *mutated = true;
+            }
+        }
+
+        let mut world = World::default();
+        world.insert_resource(0usize);
+        world.spawn().insert(A);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("detect", SystemStage::single(detect_changes.system()));
+        schedule.add_stage("mutate", SystemStage::single(mutate_once.system()));
+
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<usize>().unwrap(),
+            1,
+            "added component is detected"
+        );
+
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<usize>().unwrap(),
+            2,
+            "mutation made after the detecting system ran is detected on its next run"
+        );
+
+        schedule.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<usize>().unwrap(),
+            2,
+            "changes are only detected once"
+        );
+    }
+
     #[test]
     fn changed_resource_system() {
         struct Added(usize);
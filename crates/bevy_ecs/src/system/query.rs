@@ -2,9 +2,10 @@ use crate::{
     component::Component,
     entity::Entity,
     query::{
-        Fetch, FilterFetch, QueryEntityError, QueryIter, QueryState, ReadOnlyFetch, WorldQuery,
+        Fetch, FilterFetch, QueryEntityError, QueryGroupedIter, QueryIter, QuerySortedIter,
+        QueryState, ReadOnlyFetch, WorldQuery,
     },
-    world::{Mut, World},
+    world::{get_component_and_ticks_with_type, Mut, World},
 };
 use bevy_tasks::TaskPool;
 use std::{any::TypeId, fmt::Debug};
@@ -17,6 +18,8 @@ where
 {
     pub(crate) world: &'w World,
     pub(crate) state: &'w QueryState<Q, F>,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
 }
 
 impl<'w, Q: WorldQuery, F: WorldQuery> Query<'w, Q, F>
@@ -27,8 +30,18 @@ where
     /// This will create a Query that could violate memory safety rules. Make sure that this is only called in
     /// ways that ensure the Queries have unique mutable access.
     #[inline]
-    pub(crate) unsafe fn new(world: &'w World, state: &'w QueryState<Q, F>) -> Self {
-        Self { world, state }
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        state: &'w QueryState<Q, F>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        Self {
+            world,
+            state,
+            last_change_tick,
+            change_tick,
+        }
     }
 
     /// Iterates over the query results. This can only be called for read-only queries
@@ -38,14 +51,20 @@ where
         Q::Fetch: ReadOnlyFetch,
     {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        unsafe { self.state.iter_unchecked_manual(self.world) }
+        unsafe {
+            self.state
+                .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
+        }
     }
 
     /// Iterates over the query results
     #[inline]
     pub fn iter_mut(&mut self) -> QueryIter<'_, '_, Q, F> {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        unsafe { self.state.iter_unchecked_manual(self.world) }
+        unsafe {
+            self.state
+                .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
+        }
     }
 
     /// Iterates over the query results
@@ -54,7 +73,106 @@ where
     #[inline]
     pub unsafe fn iter_unsafe(&self) -> QueryIter<'_, '_, Q, F> {
         // SEMI-SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        self.state.iter_unchecked_manual(self.world)
+        self.state
+            .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
+    }
+
+    /// Iterates over the query results ordered by the key `f` extracts from them. Results with equal keys are
+    /// ordered by [Entity], so the order is the same every frame. The buffer used for sorting is kept in the query's
+    /// state and reused, so sorting does not allocate once the buffer is large enough.
+    /// This can only be called for read-only queries
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// struct Initiative(u32);
+    /// struct Name(&'static str);
+    ///
+    /// fn take_turns(query: Query<(&Initiative, &Name)>) {
+    ///     for (_, name) in query.iter_sorted_by_key(|(initiative, _)| std::cmp::Reverse(initiative.0)) {
+    ///         println!("{}'s turn", name.0);
+    ///     }
+    /// }
+    /// # take_turns.system();
+    /// ```
+    #[inline]
+    pub fn iter_sorted_by_key<'s, K: Ord + Send + Sync + 'static>(
+        &'s self,
+        f: impl FnMut(&<Q::Fetch as Fetch<'s>>::Item) -> K,
+    ) -> QuerySortedIter<'s, 's, Q, F, K>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.iter_sorted_by_key_unchecked_manual(
+                self.world,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Iterates over the query results ordered by the key `f` extracts from them. Results with equal keys are
+    /// ordered by [Entity]. See [Self::iter_sorted_by_key].
+    #[inline]
+    pub fn iter_sorted_by_key_mut<'s, K: Ord + Send + Sync + 'static>(
+        &'s mut self,
+        f: impl FnMut(&<Q::Fetch as Fetch<'s>>::Item) -> K,
+    ) -> QuerySortedIter<'s, 's, Q, F, K> {
+        // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.iter_sorted_by_key_unchecked_manual(
+                self.world,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Iterates over the query results ordered by [Entity]. Unlike [Self::iter], the order does not depend on
+    /// the archetypes and tables the entities are stored in.
+    /// This can only be called for read-only queries
+    #[inline]
+    #[allow(clippy::unit_return_expecting_ord)]
+    pub fn iter_sorted(&self) -> QuerySortedIter<'_, '_, Q, F, ()>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        self.iter_sorted_by_key(|_| ())
+    }
+
+    /// Iterates over the query results ordered by [Entity]. See [Self::iter_sorted].
+    #[inline]
+    #[allow(clippy::unit_return_expecting_ord)]
+    pub fn iter_sorted_mut(&mut self) -> QuerySortedIter<'_, '_, Q, F, ()> {
+        self.iter_sorted_by_key_mut(|_| ())
+    }
+
+    /// Iterates over groups of query results that share the key `f` extracts from them, in the order of their
+    /// keys. See [QueryGroupedIter::next_group].
+    /// This can only be called for read-only queries
+    #[inline]
+    pub fn iter_grouped_by<'s, K: Ord + Send + Sync + 'static>(
+        &'s self,
+        f: impl FnMut(&<Q::Fetch as Fetch<'s>>::Item) -> K,
+    ) -> QueryGroupedIter<'s, 's, Q, F, K>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        QueryGroupedIter::new(self.iter_sorted_by_key(f))
+    }
+
+    /// Iterates over groups of query results that share the key `f` extracts from them, in the order of their
+    /// keys. See [QueryGroupedIter::next_group].
+    #[inline]
+    pub fn iter_grouped_by_mut<'s, K: Ord + Send + Sync + 'static>(
+        &'s mut self,
+        f: impl FnMut(&<Q::Fetch as Fetch<'s>>::Item) -> K,
+    ) -> QueryGroupedIter<'s, 's, Q, F, K> {
+        QueryGroupedIter::new(self.iter_sorted_by_key_mut(f))
     }
 
     /// Runs `f` on each query result. This is faster than the equivalent iter() method, but cannot be chained like a normal iterator.
@@ -65,14 +183,28 @@ where
         Q::Fetch: ReadOnlyFetch,
     {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        unsafe { self.state.for_each_unchecked_manual(self.world, f) };
+        unsafe {
+            self.state.for_each_unchecked_manual(
+                self.world,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        };
     }
 
     /// Runs `f` on each query result. This is faster than the equivalent iter() method, but cannot be chained like a normal iterator.
     #[inline]
     pub fn for_each_mut(&self, f: impl FnMut(<Q::Fetch as Fetch<'w>>::Item)) {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        unsafe { self.state.for_each_unchecked_manual(self.world, f) };
+        unsafe {
+            self.state.for_each_unchecked_manual(
+                self.world,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        };
     }
 
     /// Runs `f` on each query result in parallel using the given task pool.
@@ -87,8 +219,14 @@ where
     {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
         unsafe {
-            self.state
-                .par_for_each_unchecked_manual(self.world, task_pool, batch_size, f)
+            self.state.par_for_each_unchecked_manual(
+                self.world,
+                task_pool,
+                batch_size,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            )
         };
     }
 
@@ -102,8 +240,14 @@ where
     ) {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
         unsafe {
-            self.state
-                .par_for_each_unchecked_manual(self.world, task_pool, batch_size, f)
+            self.state.par_for_each_unchecked_manual(
+                self.world,
+                task_pool,
+                batch_size,
+                f,
+                self.last_change_tick,
+                self.change_tick,
+            )
         };
     }
 
@@ -114,7 +258,14 @@ where
         Q::Fetch: ReadOnlyFetch,
     {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        unsafe { self.state.get_unchecked_manual(self.world, entity) }
+        unsafe {
+            self.state.get_unchecked_manual(
+                self.world,
+                entity,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
     }
 
     /// Gets the query result for the given `entity`
@@ -124,7 +275,14 @@ where
         entity: Entity,
     ) -> Result<<Q::Fetch as Fetch>::Item, QueryEntityError> {
         // // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        unsafe { self.state.get_unchecked_manual(self.world, entity) }
+        unsafe {
+            self.state.get_unchecked_manual(
+                self.world,
+                entity,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
     }
 
     /// Gets the query result for the given `entity`
@@ -136,7 +294,53 @@ where
         entity: Entity,
     ) -> Result<<Q::Fetch as Fetch>::Item, QueryEntityError> {
         // SEMI-SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
-        self.state.get_unchecked_manual(self.world, entity)
+        self.state
+            .get_unchecked_manual(self.world, entity, self.last_change_tick, self.change_tick)
+    }
+
+    /// Iterates over the query results of the entities that hold a [Relation](crate::relation::Relation) of kind `K`
+    /// pointing at `target`. Related entities that don't match the query are skipped.
+    /// This can only be called for read-only queries
+    #[inline]
+    pub fn iter_related<K: 'static>(
+        &self,
+        target: Entity,
+    ) -> impl Iterator<Item = <Q::Fetch as Fetch<'_>>::Item> + '_
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        let world: &World = self.world;
+        let state: &QueryState<Q, F> = self.state;
+        let (last_change_tick, change_tick) = (self.last_change_tick, self.change_tick);
+        world.related::<K>(target).iter().filter_map(move |entity| {
+            // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict
+            unsafe { state.get_unchecked_manual(world, *entity, last_change_tick, change_tick) }
+                .ok()
+        })
+    }
+
+    /// Runs `f` on the query results of the entities that hold a [Relation](crate::relation::Relation) of kind `K`
+    /// pointing at `target`. Related entities that don't match the query are skipped.
+    #[inline]
+    pub fn for_each_related_mut<K: 'static>(
+        &mut self,
+        target: Entity,
+        mut f: impl FnMut(<Q::Fetch as Fetch<'w>>::Item),
+    ) {
+        for entity in self.world.related::<K>(target) {
+            // SAFE: system runs without conflicts with other systems. same-system queries have runtime borrow checks when they conflict.
+            // each related entity is only visited once, so results never alias
+            if let Ok(item) = unsafe {
+                self.state.get_unchecked_manual(
+                    self.world,
+                    *entity,
+                    self.last_change_tick,
+                    self.change_tick,
+                )
+            } {
+                f(item);
+            }
+        }
     }
 
     /// Gets a reference to the entity's component of the given type. This will fail if the entity does not have
@@ -205,9 +409,20 @@ where
             .archetype_component_access
             .has_write(archetype_component)
         {
-            entity_ref
-                .get_unchecked_mut::<T>()
-                .ok_or(QueryComponentError::MissingComponent)
+            get_component_and_ticks_with_type(
+                world,
+                TypeId::of::<T>(),
+                entity,
+                entity_ref.location(),
+            )
+            .map(|(value, ticks, changed_summary)| Mut {
+                value: &mut *value.cast::<T>(),
+                component_ticks: &mut *ticks,
+                changed_summary: changed_summary.as_ref(),
+                last_change_tick: self.last_change_tick,
+                change_tick: self.change_tick,
+            })
+            .ok_or(QueryComponentError::MissingComponent)
         } else {
             Err(QueryComponentError::MissingWriteAccess)
         }
@@ -37,6 +37,9 @@ pub trait System: Send + Sync + 'static {
     }
     fn apply_buffers(&mut self, world: &mut World);
     fn initialize(&mut self, _world: &mut World);
+    /// Clamps the change tick this system last ran at, so that it keeps its meaning after the world's change tick
+    /// wraps around. Called periodically by schedules with the world's current `change_tick`.
+    fn check_change_tick(&mut self, change_tick: u32);
 }
 
 pub type BoxedSystem<In = (), Out = ()> = Box<dyn System<In = In, Out = Out>>;
@@ -1,10 +1,11 @@
 use crate::{
     archetype::{Archetype, Archetypes},
     bundle::Bundles,
-    component::{Component, ComponentFlags, ComponentId, Components},
+    component::{Component, ComponentId, ComponentTicks, Components},
     entity::{Entities, Entity},
     query::{FilterFetch, FilteredAccess, FilteredAccessSet, QueryState, WorldQuery},
-    system::{CommandQueue, Commands, Query, SystemState},
+    relation::Relations,
+    system::{CommandQueue, Commands, ParallelCommandQueue, ParallelCommands, Query, SystemState},
     world::{FromWorld, World},
 };
 pub use bevy_ecs_macros::SystemParam;
@@ -58,6 +59,7 @@ pub trait SystemParamFetch<'a>: SystemParamState {
         state: &'a mut Self,
         system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item;
 }
 
@@ -114,10 +116,11 @@ where
     #[inline]
     unsafe fn get_param(
         state: &'a mut Self,
-        _system_state: &'a SystemState,
+        system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item {
-        Query::new(world, state)
+        Query::new(world, state, system_state.last_change_tick, change_tick)
     }
 }
 
@@ -154,24 +157,28 @@ impl_query_set!();
 /// Use `Option<Res<T>>` if the resource might not always exist.
 pub struct Res<'w, T> {
     value: &'w T,
-    flags: ComponentFlags,
+    ticks: &'w ComponentTicks,
+    last_change_tick: u32,
+    change_tick: u32,
 }
 
 impl<'w, T: Component> Res<'w, T> {
-    /// Returns true if (and only if) this resource been added since the start of the frame.
+    /// Returns true if (and only if) this resource been added since the last execution of this system.
     pub fn added(&self) -> bool {
-        self.flags.contains(ComponentFlags::ADDED)
+        self.ticks.is_added(self.last_change_tick, self.change_tick)
     }
 
-    /// Returns true if (and only if) this resource been mutated since the start of the frame.
+    /// Returns true if (and only if) this resource been mutated (but not added) since the last execution of
+    /// this system.
     pub fn mutated(&self) -> bool {
-        self.flags.contains(ComponentFlags::MUTATED)
+        self.changed() && !self.added()
     }
 
-    /// Returns true if (and only if) this resource been either mutated or added since the start of the frame.
+    /// Returns true if (and only if) this resource been either mutated or added since the last execution of
+    /// this system.
     pub fn changed(&self) -> bool {
-        self.flags
-            .intersects(ComponentFlags::ADDED | ComponentFlags::MUTATED)
+        self.ticks
+            .is_changed(self.last_change_tick, self.change_tick)
     }
 }
 
@@ -227,15 +234,18 @@ impl<'a, T: Component> SystemParamFetch<'a> for ResState<T> {
     #[inline]
     unsafe fn get_param(
         state: &'a mut Self,
-        _system_state: &'a SystemState,
+        system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item {
         let column = world
             .get_populated_resource_column(state.component_id)
             .expect("Requested resource does not exist");
         Res {
             value: &*column.get_ptr().as_ptr().cast::<T>(),
-            flags: *column.get_flags_mut_ptr(),
+            ticks: &*column.get_ticks_mut_ptr(),
+            last_change_tick: system_state.last_change_tick,
+            change_tick,
         }
     }
 }
@@ -259,14 +269,17 @@ impl<'a, T: Component> SystemParamFetch<'a> for OptionResState<T> {
     #[inline]
     unsafe fn get_param(
         state: &'a mut Self,
-        _system_state: &'a SystemState,
+        system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item {
         world
             .get_populated_resource_column(state.0.component_id)
             .map(|column| Res {
                 value: &*column.get_ptr().as_ptr().cast::<T>(),
-                flags: *column.get_flags_mut_ptr(),
+                ticks: &*column.get_ticks_mut_ptr(),
+                last_change_tick: system_state.last_change_tick,
+                change_tick,
             })
     }
 }
@@ -278,24 +291,28 @@ impl<'a, T: Component> SystemParamFetch<'a> for OptionResState<T> {
 /// Use `Option<ResMut<T>>` if the resource might not always exist.
 pub struct ResMut<'w, T> {
     value: &'w mut T,
-    flags: &'w mut ComponentFlags,
+    ticks: &'w mut ComponentTicks,
+    last_change_tick: u32,
+    change_tick: u32,
 }
 
 impl<'w, T: Component> ResMut<'w, T> {
-    /// Returns true if (and only if) this resource been added since the start of the frame.
+    /// Returns true if (and only if) this resource been added since the last execution of this system.
     pub fn added(&self) -> bool {
-        self.flags.contains(ComponentFlags::ADDED)
+        self.ticks.is_added(self.last_change_tick, self.change_tick)
     }
 
-    /// Returns true if (and only if) this resource been mutated since the start of the frame.
+    /// Returns true if (and only if) this resource been mutated (but not added) since the last execution of
+    /// this system.
     pub fn mutated(&self) -> bool {
-        self.flags.contains(ComponentFlags::MUTATED)
+        self.changed() && !self.added()
     }
 
-    /// Returns true if (and only if) this resource been either mutated or added since the start of the frame.
+    /// Returns true if (and only if) this resource been either mutated or added since the last execution of
+    /// this system.
     pub fn changed(&self) -> bool {
-        self.flags
-            .intersects(ComponentFlags::ADDED | ComponentFlags::MUTATED)
+        self.ticks
+            .is_changed(self.last_change_tick, self.change_tick)
     }
 }
 
@@ -309,7 +326,7 @@ impl<'w, T: Component> Deref for ResMut<'w, T> {
 
 impl<'w, T: Component> DerefMut for ResMut<'w, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        self.flags.insert(ComponentFlags::MUTATED);
+        self.ticks.set_changed(self.change_tick);
         self.value
     }
 }
@@ -362,15 +379,18 @@ impl<'a, T: Component> SystemParamFetch<'a> for ResMutState<T> {
     #[inline]
     unsafe fn get_param(
         state: &'a mut Self,
-        _system_state: &'a SystemState,
+        system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item {
         let value = world
             .get_resource_unchecked_mut_with_id(state.component_id)
             .expect("Requested resource does not exist");
         ResMut {
             value: value.value,
-            flags: value.flags,
+            ticks: value.component_ticks,
+            last_change_tick: system_state.last_change_tick,
+            change_tick,
         }
     }
 }
@@ -394,14 +414,17 @@ impl<'a, T: Component> SystemParamFetch<'a> for OptionResMutState<T> {
     #[inline]
     unsafe fn get_param(
         state: &'a mut Self,
-        _system_state: &'a SystemState,
+        system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item {
         world
             .get_resource_unchecked_mut_with_id(state.0.component_id)
             .map(|value| ResMut {
                 value: value.value,
-                flags: value.flags,
+                ticks: value.component_ticks,
+                last_change_tick: system_state.last_change_tick,
+                change_tick,
             })
     }
 }
@@ -431,11 +454,43 @@ impl<'a> SystemParamFetch<'a> for CommandQueue {
         state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         Commands::new(state, world)
     }
 }
 
+impl<'a> SystemParam for ParallelCommands<'a> {
+    type Fetch = ParallelCommandQueue;
+}
+
+// SAFE: only local state is accessed
+unsafe impl SystemParamState for ParallelCommandQueue {
+    type Config = ();
+
+    fn init(_world: &mut World, _system_state: &mut SystemState, _config: Self::Config) -> Self {
+        Default::default()
+    }
+
+    fn apply(&mut self, world: &mut World) {
+        self.apply(world);
+    }
+}
+
+impl<'a> SystemParamFetch<'a> for ParallelCommandQueue {
+    type Item = ParallelCommands<'a>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        _system_state: &'a SystemState,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        ParallelCommands::new(state, world)
+    }
+}
+
 pub struct Local<'a, T: Component>(&'a mut T);
 
 impl<'a, T: Component> Deref for Local<'a, T> {
@@ -477,6 +532,7 @@ impl<'a, T: Component + FromWorld> SystemParamFetch<'a> for LocalState<T> {
         state: &'a mut Self,
         _system_state: &'a SystemState,
         _world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         Local(&mut state.0)
     }
@@ -524,6 +580,7 @@ impl<'a, T: Component> SystemParamFetch<'a> for RemovedComponentsState<T> {
         state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         RemovedComponents {
             world,
@@ -594,6 +651,7 @@ impl<'a, T: 'static> SystemParamFetch<'a> for NonSendState<T> {
         state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         NonSend {
             value: world
@@ -606,7 +664,29 @@ impl<'a, T: 'static> SystemParamFetch<'a> for NonSendState<T> {
 /// Unique borrow of a NonSend resource
 pub struct NonSendMut<'a, T: 'static> {
     pub(crate) value: &'a mut T,
-    pub(crate) flags: &'a mut ComponentFlags,
+    pub(crate) ticks: &'a mut ComponentTicks,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
+}
+
+impl<'a, T: 'static> NonSendMut<'a, T> {
+    /// Returns true if (and only if) this resource been added since the last execution of this system.
+    pub fn added(&self) -> bool {
+        self.ticks.is_added(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns true if (and only if) this resource been mutated (but not added) since the last execution of
+    /// this system.
+    pub fn mutated(&self) -> bool {
+        self.changed() && !self.added()
+    }
+
+    /// Returns true if (and only if) this resource been either mutated or added since the last execution of
+    /// this system.
+    pub fn changed(&self) -> bool {
+        self.ticks
+            .is_changed(self.last_change_tick, self.change_tick)
+    }
 }
 
 impl<'a, T: 'static> Deref for NonSendMut<'a, T> {
@@ -621,7 +701,7 @@ impl<'a, T: 'static> Deref for NonSendMut<'a, T> {
 impl<'a, T: 'static> DerefMut for NonSendMut<'a, T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut T {
-        self.flags.insert(ComponentFlags::MUTATED);
+        self.ticks.set_changed(self.change_tick);
         self.value
     }
 }
@@ -682,15 +762,18 @@ impl<'a, T: 'static> SystemParamFetch<'a> for NonSendMutState<T> {
     #[inline]
     unsafe fn get_param(
         state: &'a mut Self,
-        _system_state: &'a SystemState,
+        system_state: &'a SystemState,
         world: &'a World,
+        change_tick: u32,
     ) -> Self::Item {
         let value = world
             .get_non_send_unchecked_mut_with_id(state.component_id)
             .expect("Requested non-send resource does not exist");
         NonSendMut {
             value: value.value,
-            flags: value.flags,
+            ticks: value.component_ticks,
+            last_change_tick: system_state.last_change_tick,
+            change_tick,
         }
     }
 }
@@ -720,6 +803,7 @@ impl<'a> SystemParamFetch<'a> for ArchetypesState {
         _state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         world.archetypes()
     }
@@ -748,6 +832,7 @@ impl<'a> SystemParamFetch<'a> for ComponentsState {
         _state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         world.components()
     }
@@ -776,6 +861,7 @@ impl<'a> SystemParamFetch<'a> for EntitiesState {
         _state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         world.entities()
     }
@@ -804,11 +890,41 @@ impl<'a> SystemParamFetch<'a> for BundlesState {
         _state: &'a mut Self,
         _system_state: &'a SystemState,
         world: &'a World,
+        _change_tick: u32,
     ) -> Self::Item {
         world.bundles()
     }
 }
 
+impl<'a> SystemParam for &'a Relations {
+    type Fetch = RelationsState;
+}
+
+pub struct RelationsState;
+
+// SAFE: no component value access
+unsafe impl SystemParamState for RelationsState {
+    type Config = ();
+
+    fn init(_world: &mut World, _system_state: &mut SystemState, _config: Self::Config) -> Self {
+        Self
+    }
+}
+
+impl<'a> SystemParamFetch<'a> for RelationsState {
+    type Item = &'a Relations;
+
+    #[inline]
+    unsafe fn get_param(
+        _state: &'a mut Self,
+        _system_state: &'a SystemState,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        world.relations()
+    }
+}
+
 macro_rules! impl_system_param_tuple {
     ($($param: ident),*) => {
         impl<$($param: SystemParam),*> SystemParam for ($($param,)*) {
@@ -824,10 +940,11 @@ macro_rules! impl_system_param_tuple {
                 state: &'a mut Self,
                 system_state: &'a SystemState,
                 world: &'a World,
+                change_tick: u32,
             ) -> Self::Item {
 
                 let ($($param,)*) = state;
-                ($($param::get_param($param, system_state, world),)*)
+                ($($param::get_param($param, system_state, world, change_tick),)*)
             }
         }
 
@@ -0,0 +1,184 @@
+use crate::{
+    archetype::ArchetypeGeneration,
+    component::{check_tick, MAX_CHANGE_AGE},
+    system::{SystemParam, SystemParamFetch, SystemParamState, SystemState},
+    world::{World, WorldId},
+};
+
+/// Caches the state of a set of [SystemParam]s, so that they can be fetched from a [World] outside of a system,
+/// such as in an exclusive system.
+///
+/// Like the params of a system, the cached state persists between fetches: queries keep their matched
+/// archetypes, [Local](crate::system::Local)s keep their values, and change detection filters detect changes
+/// made since the previous fetch. Call [SystemParamCache::apply] to apply buffers such as
+/// [Commands](crate::system::Commands).
+///
+/// The cache is stored outside of any borrow of the [World], so `Param` must be written with `'static`
+/// lifetimes, such as `Query<'static, &'static T>` or `ResMut<'static, T>`. [SystemParamCache::get_mut]
+/// hands the params back with the lifetime of the fetch.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{ExclusiveSystem, SystemParamCache};
+/// struct Health(u32);
+/// struct Log(Vec<u32>);
+///
+/// fn log_changed_health(
+///     world: &mut World,
+///     cache: &mut SystemParamCache<(
+///         Query<'static, &'static Health, Changed<Health>>,
+///         ResMut<'static, Log>,
+///     )>,
+/// ) {
+///     let (health, mut log) = cache.get_mut(world);
+///     log.0.extend(health.iter().map(|health| health.0));
+/// }
+///
+/// let mut cache = None;
+/// let system = move |world: &mut World| {
+///     let cache = cache.get_or_insert_with(|| SystemParamCache::new(world));
+///     log_changed_health(world, cache);
+/// };
+/// # let mut world = World::new();
+/// # world.insert_resource(Log(Vec::new()));
+/// # world.spawn().insert(Health(10));
+/// # let mut system = system.exclusive_system();
+/// # system.initialize(&mut world);
+/// # system.run(&mut world);
+/// # system.run(&mut world);
+/// # assert_eq!(world.get_resource::<Log>().unwrap().0, vec![10]);
+/// ```
+pub struct SystemParamCache<Param: SystemParam> {
+    param_state: Param::Fetch,
+    system_state: SystemState,
+    world_id: WorldId,
+    archetype_generation: ArchetypeGeneration,
+    /// The change tick of the last fetch.
+    change_tick: u32,
+}
+
+impl<Param: SystemParam> SystemParamCache<Param> {
+    pub fn new(world: &mut World) -> Self {
+        Self::with_config(world, Default::default())
+    }
+
+    /// Creates a cache whose params are initialized with `config`, as with
+    /// [FunctionSystem::config](crate::system::FunctionSystem::config).
+    pub fn with_config(
+        world: &mut World,
+        config: <Param::Fetch as SystemParamState>::Config,
+    ) -> Self {
+        let mut system_state = SystemState::new::<Param>();
+        let change_tick = world.change_tick().wrapping_sub(MAX_CHANGE_AGE);
+        system_state.last_change_tick = change_tick;
+        let param_state =
+            <Param::Fetch as SystemParamState>::init(world, &mut system_state, config);
+        Self {
+            param_state,
+            system_state,
+            world_id: world.id(),
+            archetype_generation: ArchetypeGeneration::new(usize::MAX),
+            change_tick,
+        }
+    }
+
+    /// Fetches the params from `world`. Change detection filters detect changes made since the previous fetch.
+    ///
+    /// # Panics
+    /// Panics if `world` is not the World the cache was created with.
+    pub fn get_mut<'a>(
+        &'a mut self,
+        world: &'a mut World,
+    ) -> <Param::Fetch as SystemParamFetch<'a>>::Item {
+        self.validate_world_and_update_archetypes(world);
+        let change_tick = world.increment_change_tick();
+        check_tick(&mut self.change_tick, change_tick);
+        self.system_state.last_change_tick = self.change_tick;
+        self.change_tick = change_tick;
+        // SAFE: the World is borrowed mutably, so the params have exclusive access to it, and params that conflict
+        // with each other were rejected when the cache was created.
+        unsafe {
+            <Param::Fetch as SystemParamFetch>::get_param(
+                &mut self.param_state,
+                &self.system_state,
+                world,
+                change_tick,
+            )
+        }
+    }
+
+    /// Applies the buffers of the params, such as the commands queued with
+    /// [Commands](crate::system::Commands).
+    pub fn apply(&mut self, world: &mut World) {
+        self.param_state.apply(world);
+    }
+
+    fn validate_world_and_update_archetypes(&mut self, world: &World) {
+        if world.id() != self.world_id {
+            panic!("Attempted to use {} with a mismatched World. SystemParamCaches can only be used with the World they were created from.",
+                std::any::type_name::<Self>());
+        }
+        let archetypes = world.archetypes();
+        let old_generation = self.archetype_generation;
+        let new_generation = archetypes.generation();
+        if old_generation == new_generation {
+            return;
+        }
+
+        let archetype_index_range = if old_generation.value() == usize::MAX {
+            0..archetypes.len()
+        } else {
+            old_generation.value()..archetypes.len()
+        };
+        for archetype in archetypes.archetypes[archetype_index_range].iter() {
+            self.param_state
+                .new_archetype(archetype, &mut self.system_state);
+        }
+        self.archetype_generation = new_generation;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        query::Added,
+        system::{Commands, Local, Query, Res, SystemParamCache},
+        world::World,
+    };
+
+    struct A(usize);
+    struct B;
+
+    #[test]
+    fn cached_params() {
+        let mut world = World::new();
+        world.insert_resource(10usize);
+        world.spawn().insert(A(1));
+        let mut cache =
+            SystemParamCache::<(Query<&A, Added<A>>, Res<usize>, Local<usize>, Commands)>::new(
+                &mut world,
+            );
+
+        let (query, resource, mut local, mut commands) = cache.get_mut(&mut world);
+        assert_eq!(query.iter().map(|a| a.0).collect::<Vec<_>>(), vec![1]);
+        *local += *resource;
+        commands.spawn((A(2), B));
+        cache.apply(&mut world);
+
+        // The entity spawned by the commands is in a new archetype, and is the only one added since the last fetch.
+        let (query, _, local, _) = cache.get_mut(&mut world);
+        assert_eq!(query.iter().map(|a| a.0).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(*local, 10);
+
+        let (query, _, _, _) = cache.get_mut(&mut world);
+        assert_eq!(query.iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_world() {
+        let mut world = World::new();
+        let mut cache = SystemParamCache::<Query<&A>>::new(&mut world);
+        cache.get_mut(&mut World::new());
+    }
+}
@@ -1,12 +1,16 @@
 use crate::{
-    archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo},
-    component::{Component, ComponentFlags, ComponentId, Components, StorageType},
+    archetype::{Archetype, ArchetypeId, Archetypes, ComponentStatus},
+    bundle::{Bundle, BundleId, BundleInfo},
+    component::{
+        mark_changed_summary, Component, ComponentHook, ComponentId, ComponentTicks, Components,
+        StorageType,
+    },
     entity::{Entity, EntityLocation},
-    storage::{SparseSet, Storages},
+    relation::{Relation, Relations},
+    storage::{SparseSet, SparseSets, Storages, Table},
     world::{Mut, World},
 };
-use std::any::TypeId;
+use std::{any::TypeId, ptr, sync::atomic::AtomicU32};
 
 pub struct EntityRef<'w> {
     world: &'w World,
@@ -79,12 +83,28 @@ impl<'w> EntityRef<'w> {
     /// This allows aliased mutability. You must make sure this call does not result in multiple mutable references to the same component
     #[inline]
     pub unsafe fn get_unchecked_mut<T: Component>(&self) -> Option<Mut<'w, T>> {
-        get_component_and_flags_with_type(self.world, TypeId::of::<T>(), self.entity, self.location)
-            .map(|(value, flags)| Mut {
+        get_component_and_ticks_with_type(self.world, TypeId::of::<T>(), self.entity, self.location)
+            .map(|(value, ticks, changed_summary)| Mut {
                 value: &mut *value.cast::<T>(),
-                flags: &mut *flags,
+                component_ticks: &mut *ticks,
+                changed_summary: changed_summary.as_ref(),
+                last_change_tick: self.world.last_change_tick(),
+                change_tick: self.world.read_change_tick(),
             })
     }
+
+    /// Gets a pointer to the component with the given `component_id`, if this entity has it. This can be used for
+    /// components that have no Rust type, such as those registered with
+    /// [ComponentDescriptor::new_dynamic](crate::component::ComponentDescriptor::new_dynamic).
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<*const u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: entity location is valid and the component exists
+        unsafe { get_component(self.world, component_id, self.entity, self.location) }
+            .map(|value| value as *const u8)
+    }
 }
 
 pub struct EntityMut<'w> {
@@ -159,15 +179,18 @@ impl<'w> EntityMut<'w> {
     pub fn get_mut<T: Component>(&mut self) -> Option<Mut<'w, T>> {
         // SAFE: world access is unique, entity location is valid, and returned component is of type T
         unsafe {
-            get_component_and_flags_with_type(
+            get_component_and_ticks_with_type(
                 self.world,
                 TypeId::of::<T>(),
                 self.entity,
                 self.location,
             )
-            .map(|(value, flags)| Mut {
+            .map(|(value, ticks, changed_summary)| Mut {
                 value: &mut *value.cast::<T>(),
-                flags: &mut *flags,
+                component_ticks: &mut *ticks,
+                changed_summary: changed_summary.as_ref(),
+                last_change_tick: self.world.last_change_tick(),
+                change_tick: self.world.read_change_tick(),
             })
         }
     }
@@ -176,27 +199,135 @@ impl<'w> EntityMut<'w> {
     /// This allows aliased mutability. You must make sure this call does not result in multiple mutable references to the same component
     #[inline]
     pub unsafe fn get_unchecked_mut<T: Component>(&self) -> Option<Mut<'w, T>> {
-        get_component_and_flags_with_type(self.world, TypeId::of::<T>(), self.entity, self.location)
-            .map(|(value, flags)| Mut {
+        get_component_and_ticks_with_type(self.world, TypeId::of::<T>(), self.entity, self.location)
+            .map(|(value, ticks, changed_summary)| Mut {
                 value: &mut *value.cast::<T>(),
-                flags: &mut *flags,
+                component_ticks: &mut *ticks,
+                changed_summary: changed_summary.as_ref(),
+                last_change_tick: self.world.last_change_tick(),
+                change_tick: self.world.read_change_tick(),
             })
     }
 
+    /// Gets a pointer to the component with the given `component_id`, if this entity has it. This can be used for
+    /// components that have no Rust type, such as those registered with
+    /// [ComponentDescriptor::new_dynamic](crate::component::ComponentDescriptor::new_dynamic).
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<*const u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: entity location is valid and the component exists
+        unsafe { get_component(self.world, component_id, self.entity, self.location) }
+            .map(|value| value as *const u8)
+    }
+
+    /// Gets a mutable pointer to the component with the given `component_id`, if this entity has it. The component
+    /// is marked as changed.
+    #[inline]
+    pub fn get_mut_by_id(&mut self, component_id: ComponentId) -> Option<*mut u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        let change_tick = self.world.change_tick();
+        // SAFE: world access is unique, entity location is valid and the component exists
+        unsafe {
+            get_component_and_ticks(self.world, component_id, self.entity, self.location).map(
+                |(value, ticks, changed_summary)| {
+                    (*ticks).set_changed(change_tick);
+                    if let Some(changed_summary) = changed_summary.as_ref() {
+                        mark_changed_summary(changed_summary, change_tick, change_tick);
+                    }
+                    value
+                },
+            )
+        }
+    }
+
     // TODO: factor out non-generic part to cut down on monomorphization (just check perf)
     // TODO: move relevant methods to World (add/remove bundle)
     pub fn insert_bundle<T: Bundle>(&mut self, bundle: T) -> &mut Self {
         let entity = self.entity;
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components)
+            .id;
+        let change_tick = self.world.change_tick();
+        // SAFE: `bundle` is the bundle described by `bundle_id`, so every component of the bundle is written
+        unsafe {
+            self.insert_bundle_with(
+                bundle_id,
+                |bundle_info, sparse_sets, table, table_row, bundle_status| {
+                    bundle_info.write_components(
+                        sparse_sets,
+                        entity,
+                        table,
+                        table_row,
+                        bundle_status,
+                        bundle,
+                        change_tick,
+                    )
+                },
+            );
+        }
+        self
+    }
+
+    /// Inserts the component with the given `component_id`, moving it out of `value`. This can be used for
+    /// components that were registered with [ComponentDescriptor::new_dynamic](crate::component::ComponentDescriptor::new_dynamic).
+    /// # Safety
+    /// `value` must point to a valid value of the component. Ownership of the value moves to the world, so the caller
+    /// must not drop or use it afterwards.
+    /// # Panics
+    /// Panics if `component_id` is not registered in this entity's [World]
+    pub unsafe fn insert_by_id(&mut self, component_id: ComponentId, value: *mut u8) -> &mut Self {
+        let entity = self.entity;
+        let bundle_id = self
+            .world
+            .bundles
+            .init_dynamic_info(&self.world.components, &[component_id])
+            .id;
+        let change_tick = self.world.change_tick();
+        self.insert_bundle_with(
+            bundle_id,
+            |bundle_info, sparse_sets, table, table_row, bundle_status| {
+                bundle_info.write_component(
+                    sparse_sets,
+                    entity,
+                    table,
+                    table_row,
+                    bundle_status,
+                    0,
+                    value,
+                    change_tick,
+                )
+            },
+        );
+        self
+    }
+
+    /// Moves this entity to the archetype that contains the components of the bundle with the given `bundle_id`,
+    /// then stores the bundle's values with `write` and runs the insert hooks.
+    /// # Safety
+    /// `write` must write every component of the bundle
+    unsafe fn insert_bundle_with(
+        &mut self,
+        bundle_id: BundleId,
+        write: impl FnOnce(&BundleInfo, &mut SparseSets, &Table, usize, &[ComponentStatus]),
+    ) {
+        let entity = self.entity;
+        let change_tick = self.world.read_change_tick();
         let entities = &mut self.world.entities;
         let archetypes = &mut self.world.archetypes;
         let components = &mut self.world.components;
         let storages = &mut self.world.storages;
 
-        let bundle_info = self.world.bundles.init_info::<T>(components);
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
         let current_location = self.location;
 
-        let new_location = unsafe {
-            // SAFE: component ids in `bundle_info` and self.location are valid
+        // SAFE: component ids in `bundle_info` and self.location are valid
+        let new_location = {
             let new_archetype_id = add_bundle_to_archetype(
                 archetypes,
                 storages,
@@ -229,7 +360,7 @@ impl<'w> EntityMut<'w> {
                         .get_2_mut(old_table_id, new_archetype.table_id());
                     // PERF: store "non bundle" components in edge, then just move those to avoid redundant copies
                     let move_result =
-                        old_table.move_to_superset_unchecked(old_table_row, new_table);
+                        old_table.move_to_superset_unchecked(old_table_row, new_table, change_tick);
 
                     let new_location = new_archetype.allocate(entity, move_result.new_row);
                     // if an entity was moved into this entity's table spot, update its table row
@@ -250,31 +381,85 @@ impl<'w> EntityMut<'w> {
         entities.meta[self.entity.id as usize].location = new_location;
 
         // SAFE: archetype was created if it didn't already exist
-        let archetype = unsafe { archetypes.get_unchecked_mut(new_location.archetype_id) };
+        let archetype = archetypes.get_unchecked_mut(new_location.archetype_id);
         // SAFE: archetype tables always exists
-        let table = unsafe { storages.tables.get_unchecked_mut(archetype.table_id()) };
+        let table = storages.tables.get_unchecked_mut(archetype.table_id());
         let table_row = archetype.entity_table_row(new_location.index);
         let from_bundle = archetype.edges().get_from_bundle(bundle_info.id).unwrap();
         // SAFE: table row is valid
-        unsafe {
-            bundle_info.write_components(
-                &mut storages.sparse_sets,
-                entity,
-                table,
-                table_row,
-                &from_bundle.bundle_flags,
-                bundle,
-            )
-        };
-        self
+        write(
+            bundle_info,
+            &mut storages.sparse_sets,
+            table,
+            table_row,
+            &from_bundle.bundle_status,
+        );
+        // SAFE: bundle components were written to `table_row` above
+        self.world.relations.insert_bundle(
+            components,
+            &storages.sparse_sets,
+            table,
+            table_row,
+            bundle_info,
+            entity,
+        );
+
+        let hooks = insert_hooks(components, bundle_info, &from_bundle.bundle_status);
+        if !hooks.is_empty() {
+            for (hook, component_id) in hooks {
+                hook(self.world, entity, component_id);
+            }
+            // hooks have world access, so they may have moved this entity
+            self.update_location();
+        }
+    }
+
+    /// Runs the `on_remove` hooks of the components in the bundle that are about to be removed from this entity.
+    /// If `intersection` is false, hooks only run if the entity has every component in the bundle.
+    fn run_remove_hooks(&mut self, bundle_id: BundleId, intersection: bool) {
+        let world = &mut *self.world;
+        let bundle_info = world.bundles.get(bundle_id).unwrap();
+        // SAFE: entity location is valid
+        let archetype = unsafe { world.archetypes.get_unchecked(self.location.archetype_id) };
+        if !intersection
+            && !bundle_info
+                .component_ids
+                .iter()
+                .all(|component_id| archetype.contains(*component_id))
+        {
+            return;
+        }
+        let hooks = remove_hooks(
+            &world.components,
+            bundle_info
+                .component_ids
+                .iter()
+                .cloned()
+                .filter(|component_id| archetype.contains(*component_id)),
+        );
+        if !hooks.is_empty() {
+            for (hook, component_id) in hooks {
+                hook(world, self.entity, component_id);
+            }
+            // hooks have world access, so they may have moved this entity
+            self.update_location();
+        }
     }
 
     pub fn remove_bundle<T: Bundle>(&mut self) -> Option<T> {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components)
+            .id;
+        self.run_remove_hooks(bundle_id, false);
+        let change_tick = self.world.read_change_tick();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
         let entities = &mut self.world.entities;
         let removed_components = &mut self.world.removed_components;
+        let relations = &mut self.world.relations;
 
         let bundle_info = self.world.bundles.init_info::<T>(components);
         let old_location = self.location;
@@ -307,6 +492,7 @@ impl<'w> EntityMut<'w> {
                     storages,
                     old_archetype,
                     removed_components,
+                    relations,
                     component_id,
                     entity,
                     old_location,
@@ -331,8 +517,13 @@ impl<'w> EntityMut<'w> {
                 .get_2_mut(old_table_id, new_archetype.table_id());
 
             // SAFE: table_row exists. All "missing" components have been extracted into the bundle above and the caller takes ownership
-            let move_result =
-                unsafe { old_table.move_to_and_forget_missing_unchecked(old_table_row, new_table) };
+            let move_result = unsafe {
+                old_table.move_to_and_forget_missing_unchecked(
+                    old_table_row,
+                    new_table,
+                    change_tick,
+                )
+            };
 
             // SAFE: new_table_row is a valid position in new_archetype's table
             let new_location = unsafe { new_archetype.allocate(entity, move_result.new_row) };
@@ -357,13 +548,41 @@ impl<'w> EntityMut<'w> {
 
     /// Remove any components in the bundle that the entity has.
     pub fn remove_bundle_intersection<T: Bundle>(&mut self) {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components)
+            .id;
+        self.remove_intersection_with(bundle_id);
+    }
+
+    /// Removes and drops the component with the given `component_id`, returning true if this entity had it.
+    /// This can be used for components that were registered with
+    /// [ComponentDescriptor::new_dynamic](crate::component::ComponentDescriptor::new_dynamic).
+    pub fn remove_by_id(&mut self, component_id: ComponentId) -> bool {
+        if !self.contains_id(component_id) {
+            return false;
+        }
+        let bundle_id = self
+            .world
+            .bundles
+            .init_dynamic_info(&self.world.components, &[component_id])
+            .id;
+        self.remove_intersection_with(bundle_id);
+        true
+    }
+
+    fn remove_intersection_with(&mut self, bundle_id: BundleId) {
+        self.run_remove_hooks(bundle_id, true);
+        let change_tick = self.world.read_change_tick();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
         let entities = &mut self.world.entities;
         let removed_components = &mut self.world.removed_components;
+        let relations = &mut self.world.relations;
 
-        let bundle_info = self.world.bundles.init_info::<T>(components);
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
         let old_location = self.location;
         let new_archetype_id = unsafe {
             remove_bundle_from_archetype(
@@ -393,6 +612,7 @@ impl<'w> EntityMut<'w> {
                         storages,
                         old_archetype,
                         removed_components,
+                        relations,
                         component_id,
                         entity,
                         old_location,
@@ -418,8 +638,9 @@ impl<'w> EntityMut<'w> {
                 .get_2_mut(old_table_id, new_archetype.table_id());
 
             // SAFE: table_row exists
-            let move_result =
-                unsafe { old_table.move_to_and_drop_missing_unchecked(old_table_row, new_table) };
+            let move_result = unsafe {
+                old_table.move_to_and_drop_missing_unchecked(old_table_row, new_table, change_tick)
+            };
 
             // SAFE: new_table_row is a valid position in new_archetype's table
             let new_location = unsafe { new_archetype.allocate(entity, move_result.new_row) };
@@ -448,8 +669,38 @@ impl<'w> EntityMut<'w> {
         self.remove_bundle::<(T,)>().map(|v| v.0)
     }
 
+    /// Relates this entity to `target` with a [Relation] of kind `K`, replacing any existing relation of that kind.
+    /// This will panic if `target` does not exist.
+    pub fn insert_relation<K: 'static>(&mut self, target: Entity) -> &mut Self {
+        assert!(
+            self.world.entities.get(target).is_some(),
+            "Cannot relate to entity {:?} because it does not exist",
+            target
+        );
+        self.world.initialize_relation::<K>();
+        self.insert(Relation::<K>::new(target))
+    }
+
+    /// Removes this entity's [Relation] of kind `K`, returning its target if there was one.
+    pub fn remove_relation<K: 'static>(&mut self) -> Option<Entity> {
+        self.remove::<Relation<K>>()
+            .map(|relation| relation.target())
+    }
+
     pub fn despawn(self) {
         let world = self.world;
+        // SAFE: entity location is valid
+        let archetype = unsafe { world.archetypes.get_unchecked(self.location.archetype_id) };
+        let hooks = remove_hooks(&world.components, archetype.components());
+        if !hooks.is_empty() {
+            for (hook, component_id) in hooks {
+                hook(world, self.entity, component_id);
+            }
+            // hooks have world access, so they may have despawned this entity already
+            if world.entities.get(self.entity).is_none() {
+                return;
+            }
+        }
         world.flush();
         let location = world
             .entities
@@ -465,6 +716,7 @@ impl<'w> EntityMut<'w> {
                     .removed_components
                     .get_or_insert_with(component_id, Vec::new);
                 removed_components.push(self.entity);
+                world.relations.remove(component_id, self.entity);
             }
             let remove_result = archetype.swap_remove(location.index);
             if let Some(swapped_entity) = remove_result.swapped_entity {
@@ -496,6 +748,13 @@ impl<'w> EntityMut<'w> {
             };
             archetype.set_entity_table_row(moved_location.index, table_row);
         }
+
+        // relations pointing at the despawned entity would dangle, so they are removed from their sources
+        for (remove_relation, sources) in world.relations.take_targeting(self.entity) {
+            for source in sources {
+                remove_relation(world, source);
+            }
+        }
     }
 
     #[inline]
@@ -551,12 +810,12 @@ unsafe fn get_component(
 /// # Safety
 /// Caller must ensure that `component_id` is valid
 #[inline]
-unsafe fn get_component_and_flags(
+unsafe fn get_component_and_ticks(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
     location: EntityLocation,
-) -> Option<(*mut u8, *mut ComponentFlags)> {
+) -> Option<(*mut u8, *mut ComponentTicks, *const AtomicU32)> {
     let archetype = world.archetypes.get_unchecked(location.archetype_id);
     let component_info = world.components.get_info_unchecked(component_id);
     match component_info.storage_type() {
@@ -568,14 +827,16 @@ unsafe fn get_component_and_flags(
             // SAFE: archetypes only store valid table_rows and the stored component type is T
             Some((
                 components.get_unchecked(table_row),
-                components.get_flags_unchecked(table_row),
+                components.get_ticks_unchecked(table_row),
+                &components.changed_tick as *const AtomicU32,
             ))
         }
         StorageType::SparseSet => world
             .storages
             .sparse_sets
             .get(component_id)
-            .and_then(|sparse_set| sparse_set.get_with_flags(entity)),
+            .and_then(|sparse_set| sparse_set.get_with_ticks(entity))
+            .map(|(value, ticks)| (value, ticks, ptr::null())),
     }
 }
 
@@ -584,11 +845,13 @@ unsafe fn get_component_and_flags(
 /// The relevant table row must be removed separately
 /// `component_id` must be valid
 #[inline]
+#[allow(clippy::too_many_arguments)]
 unsafe fn remove_component(
     components: &Components,
     storages: &mut Storages,
     archetype: &Archetype,
     removed_components: &mut SparseSet<ComponentId, Vec<Entity>>,
+    relations: &mut Relations,
     component_id: ComponentId,
     entity: Entity,
     location: EntityLocation,
@@ -596,6 +859,7 @@ unsafe fn remove_component(
     let component_info = components.get_info_unchecked(component_id);
     let removed_components = removed_components.get_or_insert_with(component_id, Vec::new);
     removed_components.push(entity);
+    relations.remove(component_id, entity);
     match component_info.storage_type() {
         StorageType::Table => {
             // SAFE: tables stored in archetype always exist
@@ -629,14 +893,14 @@ unsafe fn get_component_with_type(
 
 /// # Safety
 /// `entity_location` must be within bounds of an archetype that exists.
-pub(crate) unsafe fn get_component_and_flags_with_type(
+pub(crate) unsafe fn get_component_and_ticks_with_type(
     world: &World,
     type_id: TypeId,
     entity: Entity,
     location: EntityLocation,
-) -> Option<(*mut u8, *mut ComponentFlags)> {
+) -> Option<(*mut u8, *mut ComponentTicks, *const AtomicU32)> {
     let component_id = world.components.get_id(type_id)?;
-    get_component_and_flags(world, component_id, entity, location)
+    get_component_and_ticks(world, component_id, entity, location)
 }
 
 /// # Safety
@@ -666,6 +930,47 @@ unsafe fn contains_component_with_id(
         .contains(component_id)
 }
 
+/// Collects the `on_add` hooks of the newly added components in `bundle_info`, followed by the `on_insert`
+/// hooks of all of its components. `bundle_status` must be the status of the bundle's "from bundle" edge.
+pub(crate) fn insert_hooks(
+    components: &Components,
+    bundle_info: &BundleInfo,
+    bundle_status: &[ComponentStatus],
+) -> Vec<(ComponentHook, ComponentId)> {
+    let mut hooks = Vec::new();
+    for (component_id, status) in bundle_info.component_ids.iter().zip(bundle_status.iter()) {
+        // SAFE: bundle components are always registered
+        let hooks_info = unsafe { components.get_info_unchecked(*component_id) }.hooks();
+        if let Some(on_add) = hooks_info.on_add {
+            if *status == ComponentStatus::Added {
+                hooks.push((on_add, *component_id));
+            }
+        }
+    }
+    for component_id in bundle_info.component_ids.iter() {
+        // SAFE: bundle components are always registered
+        let hooks_info = unsafe { components.get_info_unchecked(*component_id) }.hooks();
+        if let Some(on_insert) = hooks_info.on_insert {
+            hooks.push((on_insert, *component_id));
+        }
+    }
+    hooks
+}
+
+/// Collects the `on_remove` hooks of the given components.
+fn remove_hooks(
+    components: &Components,
+    component_ids: impl Iterator<Item = ComponentId>,
+) -> Vec<(ComponentHook, ComponentId)> {
+    component_ids
+        .filter_map(|component_id| {
+            // SAFE: archetype and bundle components are always registered
+            let hooks = unsafe { components.get_info_unchecked(component_id) }.hooks();
+            hooks.on_remove.map(|on_remove| (on_remove, component_id))
+        })
+        .collect()
+}
+
 /// Adds a bundle to the given archetype and returns the resulting archetype. This could be the same [ArchetypeId],
 /// in the event that adding the given bundle does not result in an Archetype change. Results are cached in the
 /// Archetype Graph to avoid redundant work.
@@ -687,14 +992,14 @@ pub(crate) unsafe fn add_bundle_to_archetype(
     }
     let mut new_table_components = Vec::new();
     let mut new_sparse_set_components = Vec::new();
-    let mut tracking_flags = Vec::with_capacity(bundle_info.component_ids.len());
+    let mut bundle_status = Vec::with_capacity(bundle_info.component_ids.len());
 
     let current_archetype = archetypes.get_unchecked_mut(archetype_id);
     for component_id in bundle_info.component_ids.iter().cloned() {
         if current_archetype.contains(component_id) {
-            tracking_flags.push(ComponentFlags::MUTATED);
+            bundle_status.push(ComponentStatus::Mutated);
         } else {
-            tracking_flags.push(ComponentFlags::ADDED);
+            bundle_status.push(ComponentStatus::Added);
             let component_info = components.get_info_unchecked(component_id);
             match component_info.storage_type() {
                 StorageType::Table => new_table_components.push(component_id),
@@ -710,7 +1015,7 @@ pub(crate) unsafe fn add_bundle_to_archetype(
         let edges = current_archetype.edges_mut();
         // the archetype does not change when we add this bundle
         edges.set_add_bundle(bundle_info.id, archetype_id);
-        edges.set_from_bundle(bundle_info.id, archetype_id, tracking_flags);
+        edges.set_from_bundle(bundle_info.id, archetype_id, bundle_status);
         archetype_id
     } else {
         let table_id;
@@ -755,7 +1060,7 @@ pub(crate) unsafe fn add_bundle_to_archetype(
         archetypes
             .get_unchecked_mut(new_archetype_id)
             .edges_mut()
-            .set_from_bundle(bundle_info.id, new_archetype_id, tracking_flags);
+            .set_from_bundle(bundle_info.id, new_archetype_id, bundle_status);
         new_archetype_id
     }
 }
@@ -12,14 +12,19 @@ use crate::{
     archetype::{ArchetypeComponentId, ArchetypeComponentInfo, ArchetypeId, Archetypes},
     bundle::{Bundle, Bundles},
     component::{
-        Component, ComponentDescriptor, ComponentFlags, ComponentId, Components, ComponentsError,
-        StorageType,
+        check_tick, Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentTicks,
+        Components, ComponentsError, StorageType,
     },
-    entity::{Entities, Entity},
-    query::{FilterFetch, QueryState, WorldQuery},
+    entity::{Entities, Entity, ReserveEntitiesIterator},
+    query::{DynamicAccess, DynamicComponents, DynamicState, FilterFetch, QueryState, WorldQuery},
+    relation::{Relation, Relations},
     storage::{Column, SparseSet, Storages},
 };
-use std::{any::TypeId, fmt};
+use std::{
+    any::TypeId,
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct WorldId(u64);
@@ -33,7 +38,6 @@ impl Default for WorldId {
 /// [World] stores and exposes operations on [entities](Entity), [components](Component), and their associated metadata.
 /// Each [Entity] has a set of components. Each component can have up to one instance of each component type.
 /// Entity components can be created, updated, removed, and queried using a given [World].
-#[derive(Default)]
 pub struct World {
     id: WorldId,
     pub(crate) entities: Entities,
@@ -42,9 +46,33 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: SparseSet<ComponentId, Vec<Entity>>,
+    pub(crate) relations: Relations,
     /// Access cache used by [WorldCell].
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     main_thread_validator: MainThreadValidator,
+    pub(crate) change_tick: AtomicU32,
+    pub(crate) last_change_tick: u32,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            entities: Default::default(),
+            components: Default::default(),
+            archetypes: Default::default(),
+            storages: Default::default(),
+            bundles: Default::default(),
+            removed_components: Default::default(),
+            relations: Default::default(),
+            archetype_component_access: Default::default(),
+            main_thread_validator: Default::default(),
+            // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
+            // are detected on first system runs and for direct world queries.
+            change_tick: AtomicU32::new(1),
+            last_change_tick: 0,
+        }
+    }
 }
 
 impl World {
@@ -96,6 +124,12 @@ impl World {
         &self.bundles
     }
 
+    /// Retrieves this world's [Relations] index
+    #[inline]
+    pub fn relations(&self) -> &Relations {
+        &self.relations
+    }
+
     /// Retrieves a [WorldCell], which safely enables multiple mutable World accesses at the same time,
     /// provided those accesses do not conflict with each other.
     #[inline]
@@ -136,6 +170,16 @@ impl World {
         Ok(component_id)
     }
 
+    /// Retrieves the [ComponentHooks] of component `T`, registering `T` with its default configuration if it
+    /// has not been used yet. Use [ComponentDescriptor::with_on_add] and friends to set hooks at registration time.
+    pub fn component_hooks_mut<T: Component>(&mut self) -> &mut ComponentHooks {
+        let component_id = self.components.get_or_insert_id::<T>();
+        self.components
+            .get_info_mut(component_id)
+            .unwrap()
+            .hooks_mut()
+    }
+
     /// Retrieves an [EntityRef] that exposes read-only operations for the given `entity`.
     /// This will panic if the `entity` does not exist. Use [World::get_entity] if you want
     /// to check for entity existence instead of implicitly panic-ing.
@@ -236,6 +280,10 @@ impl World {
     /// ```
     #[inline]
     pub fn get_entity_mut(&mut self, entity: Entity) -> Option<EntityMut> {
+        // a reserved entity needs a location before it can be mutated
+        if self.entities.is_reserved(entity) {
+            self.flush();
+        }
         let location = self.entities.get(entity)?;
         // SAFE: `entity` exists and `location` is that entity's location
         Some(unsafe { EntityMut::new(self, entity, location) })
@@ -280,6 +328,32 @@ impl World {
         }
     }
 
+    /// Reserves a new [Entity] id without exclusive access to the [World]. This is safe to call from
+    /// multiple threads at once.
+    ///
+    /// The entity is materialized without any components the next time the world needs exclusive access
+    /// to its entities, for example in [World::spawn] or [World::entity_mut]. Until then it is not
+    /// returned by [World::get_entity] or queries.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.reserve_entity();
+    /// world.entity_mut(entity).insert(1u32);
+    /// assert_eq!(world.get::<u32>(entity), Some(&1));
+    /// ```
+    #[inline]
+    pub fn reserve_entity(&self) -> Entity {
+        self.entities.reserve_entity()
+    }
+
+    /// Reserves `count` new [Entity] ids at once. See [World::reserve_entity].
+    #[inline]
+    pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator {
+        self.entities.reserve_entities(count)
+    }
+
     /// Spawns a batch of entities with the same component [Bundle] type. Takes a given [Bundle]
     /// iterator and returns a corresponding [Entity] iterator.
     /// This is more efficient than spawning entities and adding components to them individually,
@@ -374,18 +448,15 @@ impl World {
             .unwrap_or(false)
     }
 
-    /// Clears all component tracker state, such as "added", "mutated", and "removed".
+    /// Clears the "removed" component trackers and starts a new change detection window for queries and
+    /// component accessors used directly on the [World]. Systems track their own change detection window, so
+    /// this does not affect what they consider "added" or "mutated".
     pub fn clear_trackers(&mut self) {
-        self.storages.tables.clear_flags();
-        self.storages.sparse_sets.clear_flags();
         for entities in self.removed_components.values_mut() {
             entities.clear();
         }
 
-        let resource_archetype = self.archetypes.resource_mut();
-        for column in resource_archetype.unique_components.values_mut() {
-            column.clear_flags();
-        }
+        self.last_change_tick = self.increment_change_tick();
     }
 
     /// Returns [QueryState] for the given [WorldQuery], which is used to efficiently
@@ -450,6 +521,36 @@ impl World {
         QueryState::new(self)
     }
 
+    /// Returns a [QueryState] that fetches pointers to the components described by `access`, in that order.
+    /// This is mostly useful for components registered with [ComponentDescriptor::new_dynamic].
+    /// ```
+    /// use bevy_ecs::{component::{ComponentDescriptor, StorageType}, query::DynamicAccess, world::World};
+    /// use std::alloc::Layout;
+    ///
+    /// let mut world = World::new();
+    /// // SAFE: u64 is Send + Sync and has no drop logic
+    /// let descriptor = unsafe {
+    ///     ComponentDescriptor::new_dynamic("Score", StorageType::Table, Layout::new::<u64>(), |_| {})
+    /// };
+    /// let score = world.register_component(descriptor).unwrap();
+    ///
+    /// let mut value = 10u64;
+    /// // SAFE: `value` is a u64 and is not used after being moved into the world
+    /// unsafe {
+    ///     world.spawn().insert_by_id(score, (&mut value as *mut u64).cast::<u8>());
+    /// }
+    ///
+    /// let mut query = world.query_dynamic(&[DynamicAccess::Write(score)]);
+    /// for pointers in query.iter_mut(&mut world) {
+    ///     // SAFE: the component is a u64 with write access
+    ///     unsafe { *pointers[0].cast::<u64>() += 1 };
+    /// }
+    /// ```
+    pub fn query_dynamic(&mut self, access: &[DynamicAccess]) -> QueryState<DynamicComponents, ()> {
+        let fetch_state = DynamicState::new(self, access);
+        QueryState::with_fetch_state(self, fetch_state)
+    }
+
     /// Returns an iterator of entities that had components of type `T` removed since the last call to [World::clear_trackers].
     pub fn removed<T: Component>(&self) -> std::iter::Cloned<std::slice::Iter<'_, Entity>> {
         if let Some(component_id) = self.components.get_id(TypeId::of::<T>()) {
@@ -471,6 +572,29 @@ impl World {
         }
     }
 
+    /// Returns the entities that hold a [Relation] of kind `K` pointing at `target`.
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// struct Holds;
+    ///
+    /// let mut world = World::new();
+    /// let chest = world.spawn().id();
+    /// let gold = world.spawn().insert_relation::<Holds>(chest).id();
+    ///
+    /// assert_eq!(world.related::<Holds>(chest), &[gold]);
+    /// ```
+    pub fn related<K: 'static>(&self, target: Entity) -> &[Entity] {
+        self.relations.related::<K>(target)
+    }
+
+    /// Registers the relation kind `K` in this world's [Relations] index, if it isn't already.
+    pub(crate) fn initialize_relation<K: 'static>(&mut self) -> ComponentId {
+        let component_id = self.components.get_or_insert_id::<Relation<K>>();
+        self.relations.init_kind::<K>(component_id);
+        component_id
+    }
+
     /// Inserts a new resource with the given `value`.
     /// Resources are "unique" data of a given type.
     #[inline]
@@ -508,6 +632,21 @@ impl World {
         Some(unsafe { ptr.cast::<T>().read() })
     }
 
+    /// Removes and drops the resource with the given [ComponentId], if it exists. Returns `true` if a resource
+    /// was removed.
+    pub fn remove_resource_by_id(&mut self, component_id: ComponentId) -> bool {
+        let resource_archetype = self.archetypes.resource_mut();
+        let unique_components = resource_archetype.unique_components_mut();
+        match unique_components.get_mut(component_id) {
+            Some(column) if !column.is_empty() => {
+                // SAFE: if a resource column is not empty, row 0 exists
+                unsafe { column.swap_remove_unchecked(0) };
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Returns `true` if a resource of type `T` exists. Otherwise returns `false`.
     #[inline]
     pub fn contains_resource<T: Component>(&self) -> bool {
@@ -612,7 +751,7 @@ impl World {
             .components
             .get_resource_id(TypeId::of::<T>())
             .expect("resource does not exist");
-        let (ptr, mut flags) = {
+        let (ptr, mut ticks) = {
             let resource_archetype = self.archetypes.resource_mut();
             let unique_components = resource_archetype.unique_components_mut();
             let column = unique_components
@@ -628,7 +767,10 @@ impl World {
         // SAFE: pointer is of type T
         let value = Mut {
             value: unsafe { &mut *ptr.cast::<T>() },
-            flags: &mut flags,
+            component_ticks: &mut ticks,
+            changed_summary: None,
+            last_change_tick: self.last_change_tick(),
+            change_tick: self.change_tick(),
         };
         let result = f(value, self);
         let resource_archetype = self.archetypes.resource_mut();
@@ -641,7 +783,7 @@ impl World {
         // SAFE: row was just allocated above
         unsafe { column.set_unchecked(row, ptr) };
         // SAFE: row was just allocated above
-        unsafe { *column.get_flags_unchecked_mut(row) = flags };
+        unsafe { *column.get_ticks_unchecked_mut(row) = ticks };
         result
     }
 
@@ -667,7 +809,10 @@ impl World {
         let column = self.get_populated_resource_column(component_id)?;
         Some(Mut {
             value: &mut *column.get_ptr().as_ptr().cast::<T>(),
-            flags: &mut *column.get_flags_mut_ptr(),
+            component_ticks: &mut *column.get_ticks_mut_ptr(),
+            changed_summary: None,
+            last_change_tick: self.last_change_tick(),
+            change_tick: self.read_change_tick(),
         })
     }
 
@@ -698,6 +843,7 @@ impl World {
     /// `component_id` must be valid and correspond to a resource component of type T
     #[inline]
     unsafe fn insert_resource_with_id<T>(&mut self, component_id: ComponentId, mut value: T) {
+        let change_tick = self.change_tick();
         let column = self.initialize_resource_internal(component_id);
         if column.is_empty() {
             // SAFE: column is of type T and has been allocated above
@@ -707,15 +853,11 @@ impl World {
             // SAFE: index was just allocated above
             column.set_unchecked(row, data);
             std::mem::forget(value);
-            column
-                .get_flags_unchecked_mut(row)
-                .set(ComponentFlags::ADDED, true);
+            *column.get_ticks_unchecked_mut(row) = ComponentTicks::new(change_tick);
         } else {
             // SAFE: column is of type T and has already been allocated
             *column.get_unchecked(0).cast::<T>() = value;
-            column
-                .get_flags_unchecked_mut(0)
-                .set(ComponentFlags::MUTATED, true);
+            column.get_ticks_unchecked_mut(0).set_changed(change_tick);
         }
     }
 
@@ -788,6 +930,66 @@ impl World {
         }
     }
 
+    /// Increments the world's change tick, returning the tick before the increment. Systems call this when they
+    /// start running, so that their changes are recorded at a tick later than any previous system run.
+    #[inline]
+    pub fn increment_change_tick(&self) -> u32 {
+        self.change_tick.fetch_add(1, Ordering::AcqRel)
+    }
+
+    /// Reads the current change tick. Prefer [World::change_tick] when you have unique access.
+    #[inline]
+    pub fn read_change_tick(&self) -> u32 {
+        self.change_tick.load(Ordering::Acquire)
+    }
+
+    /// Returns the current change tick. Changes made directly on the [World] are recorded at this tick.
+    #[inline]
+    pub fn change_tick(&mut self) -> u32 {
+        *self.change_tick.get_mut()
+    }
+
+    /// Returns the change tick of the last [World::clear_trackers] call. Queries and component accessors used
+    /// directly on the [World] consider changes made after this tick as "added" or "mutated".
+    #[inline]
+    pub fn last_change_tick(&self) -> u32 {
+        self.last_change_tick
+    }
+
+    /// Clamps the change ticks stored in this [World] so that they keep their meaning after the change tick
+    /// wraps around. Schedules call this periodically, so it rarely needs to be called manually.
+    pub fn check_change_ticks(&mut self) {
+        let change_tick = self.change_tick();
+        self.storages.tables.check_change_ticks(change_tick);
+        self.storages.sparse_sets.check_change_ticks(change_tick);
+        let resource_archetype = self.archetypes.resource_mut();
+        for column in resource_archetype.unique_components.values_mut() {
+            column.check_change_ticks(change_tick);
+        }
+        check_tick(&mut self.last_change_tick, change_tick);
+    }
+
+    /// Resets the entity allocator to a state captured with `Entities::allocator_state`. Entities that are alive
+    /// both now and in that state keep their components, every other entity that is alive in that state is added to
+    /// the empty [Archetype]. Entities that are not alive in that state must have been despawned beforehand.
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) fn restore_entities(&mut self, generations: &[u32], pending: &[u32]) {
+        self.flush();
+        let empty_archetype = self.archetypes.empty_mut();
+        unsafe {
+            // SAFE: archetype tables always exist
+            let table = self
+                .storages
+                .tables
+                .get_unchecked_mut(empty_archetype.table_id());
+            self.entities
+                .restore_allocator_state(generations, pending, |entity, location| {
+                    // SAFE: no components are allocated by archetype.allocate() because the archetype is empty
+                    *location = empty_archetype.allocate(entity, table.allocate(entity));
+                });
+        }
+    }
+
     /// Empties queued entities and adds them to the empty [Archetype].
     /// This should be called before doing operations that might operate on queued entities,
     /// such as inserting a [Component].
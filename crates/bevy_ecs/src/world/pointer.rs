@@ -1,10 +1,17 @@
-use crate::component::ComponentFlags;
-use std::ops::{Deref, DerefMut};
+use crate::component::{mark_changed_summary, ComponentTicks};
+use std::{
+    ops::{Deref, DerefMut},
+    sync::atomic::AtomicU32,
+};
 
 /// Unique borrow of an entity's component
 pub struct Mut<'a, T> {
     pub(crate) value: &'a mut T,
-    pub(crate) flags: &'a mut ComponentFlags,
+    pub(crate) component_ticks: &'a mut ComponentTicks,
+    /// The change summary of the table column the component is stored in, if it is stored in a table.
+    pub(crate) changed_summary: Option<&'a AtomicU32>,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
 }
 
 impl<'a, T> Deref for Mut<'a, T> {
@@ -19,7 +26,10 @@ impl<'a, T> Deref for Mut<'a, T> {
 impl<'a, T> DerefMut for Mut<'a, T> {
     #[inline]
     fn deref_mut(&mut self) -> &mut T {
-        self.flags.insert(ComponentFlags::MUTATED);
+        self.component_ticks.set_changed(self.change_tick);
+        if let Some(changed_summary) = self.changed_summary {
+            mark_changed_summary(changed_summary, self.change_tick, self.change_tick);
+        }
         self.value
     }
 }
@@ -31,19 +41,22 @@ impl<'a, T: core::fmt::Debug> core::fmt::Debug for Mut<'a, T> {
 }
 
 impl<'w, T> Mut<'w, T> {
-    /// Returns true if (and only if) this component been added since the start of the frame.
+    /// Returns true if (and only if) this component been added since the last execution of this system.
     pub fn added(&self) -> bool {
-        self.flags.contains(ComponentFlags::ADDED)
+        self.component_ticks
+            .is_added(self.last_change_tick, self.change_tick)
     }
 
-    /// Returns true if (and only if) this component been mutated since the start of the frame.
+    /// Returns true if (and only if) this component been mutated (but not added) since the last execution of
+    /// this system.
     pub fn mutated(&self) -> bool {
-        self.flags.contains(ComponentFlags::MUTATED)
+        self.changed() && !self.added()
     }
 
-    /// Returns true if (and only if) this component been either mutated or added since the start of the frame.
+    /// Returns true if (and only if) this component been either mutated or added since the last execution of
+    /// this system.
     pub fn changed(&self) -> bool {
-        self.flags
-            .intersects(ComponentFlags::ADDED | ComponentFlags::MUTATED)
+        self.component_ticks
+            .is_changed(self.last_change_tick, self.change_tick)
     }
 }
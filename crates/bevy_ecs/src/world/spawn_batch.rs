@@ -1,9 +1,9 @@
 use crate::{
-    archetype::{Archetype, ArchetypeId},
-    bundle::{Bundle, BundleInfo},
-    entity::{Entities, Entity},
-    storage::{SparseSets, Table},
-    world::{add_bundle_to_archetype, World},
+    archetype::ArchetypeId,
+    bundle::{Bundle, BundleId},
+    component::{ComponentHook, ComponentId},
+    entity::Entity,
+    world::{add_bundle_to_archetype, insert_hooks, World},
 };
 
 pub struct SpawnBatchIter<'w, I>
@@ -12,11 +12,11 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    entities: &'w mut Entities,
-    archetype: &'w mut Archetype,
-    table: &'w mut Table,
-    sparse_sets: &'w mut SparseSets,
-    bundle_info: &'w BundleInfo,
+    world: &'w mut World,
+    archetype_id: ArchetypeId,
+    bundle_id: BundleId,
+    /// Insert hooks of the bundle's components. Every spawned entity runs all of them.
+    hooks: Vec<(ComponentHook, ComponentId)>,
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -47,6 +47,9 @@ where
         };
         // SAFE: archetype exists
         let archetype = unsafe { world.archetypes.get_unchecked_mut(archetype_id) };
+        let from_bundle = archetype.edges().get_from_bundle(bundle_info.id).unwrap();
+        let hooks = insert_hooks(&world.components, bundle_info, &from_bundle.bundle_status);
+        let bundle_id = bundle_info.id;
         // SAFE: table exists
         let table = unsafe {
             world
@@ -59,11 +62,10 @@ where
         world.entities.reserve(length as u32);
         Self {
             inner: iter,
-            entities: &mut world.entities,
-            archetype,
-            table,
-            sparse_sets: &mut world.storages.sparse_sets,
-            bundle_info,
+            world,
+            archetype_id,
+            bundle_id,
+            hooks,
         }
     }
 }
@@ -87,25 +89,47 @@ where
 
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
-        let entity = self.entities.alloc();
+        let world = &mut *self.world;
+        let change_tick = world.change_tick();
+        let entity = world.entities.alloc();
+        // archetypes, tables and bundle infos are never removed, but hooks may add new ones, so they are
+        // looked up again for every entity
+        let bundle_info = world.bundles.get(self.bundle_id).unwrap();
         // SAFE: component values are immediately written to relevant storages (which have been allocated)
         unsafe {
-            let table_row = self.table.allocate(entity);
-            let location = self.archetype.allocate(entity, table_row);
-            let from_bundle = self
-                .archetype
-                .edges()
-                .get_from_bundle(self.bundle_info.id)
-                .unwrap();
-            self.bundle_info.write_components(
-                self.sparse_sets,
+            let archetype = world.archetypes.get_unchecked_mut(self.archetype_id);
+            let table = world
+                .storages
+                .tables
+                .get_unchecked_mut(archetype.table_id());
+            let table_row = table.allocate(entity);
+            let location = archetype.allocate(entity, table_row);
+            let from_bundle = archetype.edges().get_from_bundle(self.bundle_id).unwrap();
+            bundle_info.write_components(
+                &mut world.storages.sparse_sets,
                 entity,
-                self.table,
+                table,
                 table_row,
-                &from_bundle.bundle_flags,
+                &from_bundle.bundle_status,
                 bundle,
+                change_tick,
             );
-            self.entities.meta[entity.id as usize].location = location;
+            world.relations.insert_bundle(
+                &world.components,
+                &world.storages.sparse_sets,
+                table,
+                table_row,
+                bundle_info,
+                entity,
+            );
+            world.entities.meta[entity.id as usize].location = location;
+        }
+        if !self.hooks.is_empty() {
+            for (hook, component_id) in self.hooks.iter() {
+                hook(world, entity, *component_id);
+            }
+            // hooks may have reserved entities, which must be flushed before the next allocation
+            world.flush();
         }
         Some(entity)
     }
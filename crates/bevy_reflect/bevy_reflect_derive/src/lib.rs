@@ -1,6 +1,7 @@
 extern crate proc_macro;
 
 mod modules;
+mod reflect_methods;
 mod reflect_trait;
 mod type_uuid;
 
@@ -15,13 +16,14 @@ use syn::{
     parse_macro_input,
     punctuated::Punctuated,
     token::{Comma, Paren, Where},
-    Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member, Meta, NestedMeta,
-    Path,
+    Data, DataEnum, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member, Meta,
+    NestedMeta, Path,
 };
 
 #[derive(Default)]
 struct PropAttributeArgs {
     pub ignore: Option<bool>,
+    pub no_default: Option<bool>,
 }
 
 #[derive(Clone)]
@@ -41,6 +43,7 @@ enum DeriveType {
     Struct,
     TupleStruct,
     UnitStruct,
+    Enum,
     Value,
 }
 
@@ -64,47 +67,11 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             fields: Fields::Unit,
             ..
         }) => (&unit_struct_punctuated, DeriveType::UnitStruct),
-        _ => (&unit_struct_punctuated, DeriveType::Value),
+        Data::Enum(_) => (&unit_struct_punctuated, DeriveType::Enum),
+        Data::Union(_) => (&unit_struct_punctuated, DeriveType::Value),
     };
 
-    let fields_and_args = fields
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            (
-                f,
-                f.attrs
-                    .iter()
-                    .find(|a| *a.path.get_ident().as_ref().unwrap() == REFLECT_ATTRIBUTE_NAME)
-                    .map(|a| {
-                        syn::custom_keyword!(ignore);
-                        let mut attribute_args = PropAttributeArgs { ignore: None };
-                        a.parse_args_with(|input: ParseStream| {
-                            if input.parse::<Option<ignore>>()?.is_some() {
-                                attribute_args.ignore = Some(true);
-                                return Ok(());
-                            }
-                            Ok(())
-                        })
-                        .expect("Invalid 'property' attribute format.");
-
-                        attribute_args
-                    }),
-                i,
-            )
-        })
-        .collect::<Vec<(&Field, Option<PropAttributeArgs>, usize)>>();
-    let active_fields = fields_and_args
-        .iter()
-        .filter(|(_field, attrs, _i)| {
-            attrs.is_none()
-                || match attrs.as_ref().unwrap().ignore {
-                    Some(ignore) => !ignore,
-                    None => true,
-                }
-        })
-        .map(|(f, _attr, i)| (*f, *i))
-        .collect::<Vec<(&Field, usize)>>();
+    let active_fields = get_active_fields(fields);
 
     let modules = get_modules();
     let bevy_reflect_path = get_path(&modules.bevy_reflect);
@@ -153,6 +120,17 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
             &reflect_attrs,
             &active_fields,
         ),
+        DeriveType::Enum => match &ast.data {
+            Data::Enum(data_enum) => impl_enum(
+                type_name,
+                &ast.generics,
+                get_type_registration_impl,
+                &bevy_reflect_path,
+                &reflect_attrs,
+                data_enum,
+            ),
+            _ => unreachable!(),
+        },
         DeriveType::Value => impl_value(
             type_name,
             &ast.generics,
@@ -163,6 +141,53 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Parses the `#[reflect(...)]` attribute of a field.
+fn get_field_args(field: &Field) -> Option<PropAttributeArgs> {
+    field
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == REFLECT_ATTRIBUTE_NAME)
+        .map(|a| {
+            syn::custom_keyword!(ignore);
+            syn::custom_keyword!(no_default);
+            let mut attribute_args = PropAttributeArgs::default();
+            a.parse_args_with(|input: ParseStream| {
+                if input.parse::<Option<ignore>>()?.is_some() {
+                    attribute_args.ignore = Some(true);
+                    return Ok(());
+                }
+                if input.parse::<Option<no_default>>()?.is_some() {
+                    attribute_args.no_default = Some(true);
+                    return Ok(());
+                }
+                Ok(())
+            })
+            .expect("Invalid 'property' attribute format.");
+
+            attribute_args
+        })
+}
+
+/// Returns the fields that are not marked with `#[reflect(ignore)]`, with their index.
+fn get_active_fields(fields: &Punctuated<Field, Comma>) -> Vec<(&Field, usize)> {
+    let fields_and_args = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f, get_field_args(f), i))
+        .collect::<Vec<(&Field, Option<PropAttributeArgs>, usize)>>();
+    fields_and_args
+        .iter()
+        .filter(|(_field, attrs, _i)| {
+            attrs.is_none()
+                || match attrs.as_ref().unwrap().ignore {
+                    Some(ignore) => !ignore,
+                    None => true,
+                }
+        })
+        .map(|(f, _attr, i)| (*f, *i))
+        .collect::<Vec<(&Field, usize)>>()
+}
+
 fn impl_struct(
     struct_name: &Ident,
     generics: &Generics,
@@ -191,6 +216,10 @@ fn impl_struct(
                 .unwrap_or_else(|| Member::Unnamed(Index::from(*index)))
         })
         .collect::<Vec<_>>();
+    let field_types = active_fields
+        .iter()
+        .map(|(field, _index)| &field.ty)
+        .collect::<Vec<_>>();
     let field_count = active_fields.len();
     let field_indices = (0..field_count).collect::<Vec<usize>>();
 
@@ -209,6 +238,14 @@ fn impl_struct(
     TokenStream::from(quote! {
         #get_type_registration_impl
 
+        impl #impl_generics #bevy_reflect_path::Typed for #struct_name#ty_generics #where_clause {
+            fn type_info() -> #bevy_reflect_path::TypeInfo {
+                #bevy_reflect_path::TypeInfo::Struct(#bevy_reflect_path::StructInfo::new(vec![
+                    #(#bevy_reflect_path::FieldInfo::new::<#field_types>(#field_names),)*
+                ]))
+            }
+        }
+
         impl #impl_generics #bevy_reflect_path::Struct for #struct_name#ty_generics #where_clause {
             fn field(&self, name: &str) -> Option<&dyn #bevy_reflect_path::Reflect> {
                 match name {
@@ -334,8 +371,16 @@ fn impl_tuple_struct(
         .iter()
         .map(|(_field, index)| Member::Unnamed(Index::from(*index)))
         .collect::<Vec<_>>();
+    let field_types = active_fields
+        .iter()
+        .map(|(field, _index)| &field.ty)
+        .collect::<Vec<_>>();
     let field_count = active_fields.len();
     let field_indices = (0..field_count).collect::<Vec<usize>>();
+    let field_names = field_indices
+        .iter()
+        .map(|index| index.to_string())
+        .collect::<Vec<String>>();
 
     let hash_fn = reflect_attrs.get_hash_impl(&bevy_reflect_path);
     let serialize_fn = reflect_attrs.get_serialize_impl(&bevy_reflect_path);
@@ -351,6 +396,14 @@ fn impl_tuple_struct(
     TokenStream::from(quote! {
         #get_type_registration_impl
 
+        impl #impl_generics #bevy_reflect_path::Typed for #struct_name#ty_generics {
+            fn type_info() -> #bevy_reflect_path::TypeInfo {
+                #bevy_reflect_path::TypeInfo::TupleStruct(#bevy_reflect_path::TupleInfo::new(vec![
+                    #(#bevy_reflect_path::FieldInfo::new::<#field_types>(#field_names),)*
+                ]))
+            }
+        }
+
         impl #impl_generics #bevy_reflect_path::TupleStruct for #struct_name#ty_generics {
             fn field(&self, index: usize) -> Option<&dyn #bevy_reflect_path::Reflect> {
                 match index {
@@ -442,6 +495,341 @@ fn impl_tuple_struct(
     })
 }
 
+fn impl_enum(
+    enum_name: &Ident,
+    generics: &Generics,
+    get_type_registration_impl: proc_macro2::TokenStream,
+    bevy_reflect_path: &Path,
+    reflect_attrs: &ReflectAttrs,
+    data_enum: &DataEnum,
+) -> TokenStream {
+    let unit_fields = Punctuated::new();
+    let mut variant_name_arms = Vec::new();
+    let mut variant_index_arms = Vec::new();
+    let mut variant_type_arms = Vec::new();
+    let mut field_arms = Vec::new();
+    let mut field_at_arms = Vec::new();
+    let mut name_at_arms = Vec::new();
+    let mut field_len_arms = Vec::new();
+    let mut clone_dynamic_arms = Vec::new();
+    let mut new_variant_arms = Vec::new();
+    let mut variant_infos = Vec::new();
+    for (variant_index, variant) in data_enum.variants.iter().enumerate() {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let (fields, variant_type) = match &variant.fields {
+            Fields::Named(fields) => (&fields.named, quote!(Struct)),
+            Fields::Unnamed(fields) => (&fields.unnamed, quote!(Tuple)),
+            Fields::Unit => (&unit_fields, quote!(Unit)),
+        };
+        let is_struct = matches!(variant.fields, Fields::Named(_));
+        let active_fields = get_active_fields(fields);
+        let field_members = active_fields
+            .iter()
+            .map(|(field, index)| get_member(field, *index))
+            .collect::<Vec<_>>();
+        let field_names = active_fields
+            .iter()
+            .map(|(field, index)| {
+                field
+                    .ident
+                    .as_ref()
+                    .map(|i| i.to_string())
+                    .unwrap_or_else(|| index.to_string())
+            })
+            .collect::<Vec<String>>();
+        let field_count = active_fields.len();
+        let field_indices = (0..field_count).collect::<Vec<usize>>();
+        let bindings = field_indices
+            .iter()
+            .map(|index| Ident::new(&format!("__field_{}", index), Span::call_site()))
+            .collect::<Vec<_>>();
+        let field_types = active_fields
+            .iter()
+            .map(|(field, _index)| &field.ty)
+            .collect::<Vec<_>>();
+        let field_info_names = if is_struct {
+            field_names.clone()
+        } else {
+            field_indices
+                .iter()
+                .map(|index| index.to_string())
+                .collect::<Vec<String>>()
+        };
+        variant_infos.push(quote! {
+            #bevy_reflect_path::VariantInfo::new(
+                #variant_name,
+                #bevy_reflect_path::VariantType::#variant_type,
+                vec![#(#bevy_reflect_path::FieldInfo::new::<#field_types>(#field_info_names),)*],
+            )
+        });
+
+        let variant_pattern = quote!(Self::#variant_ident { .. });
+        let fields_pattern = quote!(Self::#variant_ident { #(#field_members: #bindings,)* .. });
+
+        variant_name_arms.push(quote!(#variant_pattern => #variant_name));
+        variant_index_arms.push(quote!(#variant_pattern => #variant_index));
+        variant_type_arms
+            .push(quote!(#variant_pattern => #bevy_reflect_path::VariantType::#variant_type));
+        field_len_arms.push(quote!(#variant_pattern => #field_count));
+        // fields of a new variant are taken from the applied value if it holds them as their concrete type, and
+        // are created with `Default` otherwise
+        let new_variant_fields = fields.iter().enumerate().map(|(index, field)| {
+            let member = get_member(field, index);
+            let active_index = match active_fields.iter().position(|(_, i)| *i == index) {
+                Some(active_index) => active_index,
+                None => return quote!(#member: Default::default()),
+            };
+            let ty = &field.ty;
+            let value = if is_struct {
+                let name = &field_names[active_index];
+                quote!(enum_value.field(#name))
+            } else {
+                quote!(enum_value.field_at(#active_index))
+            };
+            let fallback = match get_field_args(field).and_then(|args| args.no_default) {
+                Some(true) => quote! {
+                    panic!(
+                        "Attempted to apply a value without a concrete {} to {}::{}.",
+                        std::any::type_name::<#ty>(),
+                        std::any::type_name::<Self>(),
+                        #variant_name
+                    )
+                },
+                _ => quote!(Default::default()),
+            };
+            quote! {
+                #member: match #value.map(|value| #bevy_reflect_path::Reflect::clone_value(value).take::<#ty>()) {
+                    Some(Ok(value)) => value,
+                    _ => #fallback,
+                }
+            }
+        });
+        new_variant_arms.push(quote! {
+            #variant_name => Self::#variant_ident { #(#new_variant_fields,)* }
+        });
+
+        if is_struct && field_count > 0 {
+            field_arms.push(quote! {
+                #fields_pattern => match name {
+                    #(#field_names => Some(#bindings),)*
+                    _ => None,
+                }
+            });
+            name_at_arms.push(quote! {
+                #variant_pattern => match index {
+                    #(#field_indices => Some(#field_names),)*
+                    _ => None,
+                }
+            });
+        } else {
+            field_arms.push(quote!(#variant_pattern => None));
+            name_at_arms.push(quote!(#variant_pattern => None));
+        }
+
+        if field_count > 0 {
+            field_at_arms.push(quote! {
+                #fields_pattern => match index {
+                    #(#field_indices => Some(#bindings),)*
+                    _ => None,
+                }
+            });
+        } else {
+            field_at_arms.push(quote!(#variant_pattern => None));
+        }
+
+        let dynamic_variant = if is_struct {
+            quote! {{
+                let mut fields = #bevy_reflect_path::DynamicStruct::default();
+                #(fields.insert_boxed(#field_names, #bevy_reflect_path::Reflect::clone_value(#bindings));)*
+                #bevy_reflect_path::DynamicVariant::Struct(fields)
+            }}
+        } else if field_count > 0 || matches!(variant.fields, Fields::Unnamed(_)) {
+            quote! {{
+                let mut fields = #bevy_reflect_path::DynamicTuple::default();
+                #(fields.insert_boxed(#bevy_reflect_path::Reflect::clone_value(#bindings));)*
+                #bevy_reflect_path::DynamicVariant::Tuple(fields)
+            }}
+        } else {
+            quote!(#bevy_reflect_path::DynamicVariant::Unit)
+        };
+        clone_dynamic_arms.push(quote! {
+            #fields_pattern => dynamic.set_variant(#variant_name.to_string(), #variant_index, #dynamic_variant)
+        });
+    }
+
+    let hash_fn = reflect_attrs.get_hash_impl(bevy_reflect_path);
+    let serialize_fn = reflect_attrs.get_serialize_impl(bevy_reflect_path);
+    let partial_eq_fn = match reflect_attrs.reflect_partial_eq {
+        TraitImpl::NotImplemented => quote! {
+            use #bevy_reflect_path::Enum;
+            #bevy_reflect_path::enum_partial_eq(self, value)
+        },
+        TraitImpl::Implemented | TraitImpl::Custom(_) => reflect_attrs.get_partial_eq_impl(),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #get_type_registration_impl
+
+        impl #impl_generics #bevy_reflect_path::Typed for #enum_name#ty_generics #where_clause {
+            fn type_info() -> #bevy_reflect_path::TypeInfo {
+                #bevy_reflect_path::TypeInfo::Enum(#bevy_reflect_path::EnumInfo::new(vec![
+                    #(#variant_infos,)*
+                ]))
+            }
+        }
+
+        impl #impl_generics #bevy_reflect_path::Enum for #enum_name#ty_generics #where_clause {
+            fn variant_name(&self) -> &str {
+                match self {
+                    #(#variant_name_arms,)*
+                }
+            }
+
+            fn variant_index(&self) -> usize {
+                match self {
+                    #(#variant_index_arms,)*
+                }
+            }
+
+            fn variant_type(&self) -> #bevy_reflect_path::VariantType {
+                match self {
+                    #(#variant_type_arms,)*
+                }
+            }
+
+            fn field(&self, name: &str) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_arms,)*
+                }
+            }
+
+            fn field_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_arms,)*
+                }
+            }
+
+            fn field_at(&self, index: usize) -> Option<&dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_at_arms,)*
+                }
+            }
+
+            fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_reflect_path::Reflect> {
+                match self {
+                    #(#field_at_arms,)*
+                }
+            }
+
+            fn name_at(&self, index: usize) -> Option<&str> {
+                match self {
+                    #(#name_at_arms,)*
+                }
+            }
+
+            fn field_len(&self) -> usize {
+                match self {
+                    #(#field_len_arms,)*
+                }
+            }
+
+            fn iter_fields(&self) -> #bevy_reflect_path::VariantFieldIter {
+                #bevy_reflect_path::VariantFieldIter::new(self)
+            }
+
+            #[allow(unused_mut)]
+            fn clone_dynamic(&self) -> #bevy_reflect_path::DynamicEnum {
+                let mut dynamic = #bevy_reflect_path::DynamicEnum::default();
+                dynamic.set_name(self.type_name().to_string());
+                match self {
+                    #(#clone_dynamic_arms,)*
+                }
+                dynamic
+            }
+        }
+
+        impl #impl_generics #bevy_reflect_path::Reflect for #enum_name#ty_generics #where_clause {
+            #[inline]
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            #[inline]
+            fn any(&self) -> &dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn clone_value(&self) -> Box<dyn #bevy_reflect_path::Reflect> {
+                use #bevy_reflect_path::Enum;
+                Box::new(self.clone_dynamic())
+            }
+            #[inline]
+            fn set(&mut self, value: Box<dyn #bevy_reflect_path::Reflect>) -> Result<(), Box<dyn #bevy_reflect_path::Reflect>> {
+                *self = value.take()?;
+                Ok(())
+            }
+
+            fn apply(&mut self, value: &dyn #bevy_reflect_path::Reflect) {
+                use #bevy_reflect_path::Enum;
+                if let #bevy_reflect_path::ReflectRef::Enum(enum_value) = value.reflect_ref() {
+                    if self.variant_name() != enum_value.variant_name() {
+                        *self = match enum_value.variant_name() {
+                            #(#new_variant_arms,)*
+                            name => panic!("Enum {} has no variant {}.", std::any::type_name::<Self>(), name),
+                        };
+                    }
+                    for (i, value) in enum_value.iter_fields().enumerate() {
+                        let field = match enum_value.name_at(i) {
+                            Some(name) => self.field_mut(name),
+                            None => self.field_at_mut(i),
+                        };
+                        if let Some(v) = field {
+                            v.apply(value);
+                        }
+                    }
+                } else {
+                    panic!("Attempted to apply non-enum type to enum type.");
+                }
+            }
+
+            fn reflect_ref(&self) -> #bevy_reflect_path::ReflectRef {
+                #bevy_reflect_path::ReflectRef::Enum(self)
+            }
+
+            fn reflect_mut(&mut self) -> #bevy_reflect_path::ReflectMut {
+                #bevy_reflect_path::ReflectMut::Enum(self)
+            }
+
+            fn serializable(&self) -> Option<#bevy_reflect_path::serde::Serializable> {
+                #serialize_fn
+            }
+
+            fn reflect_hash(&self) -> Option<u64> {
+                #hash_fn
+            }
+
+            fn reflect_partial_eq(&self, value: &dyn #bevy_reflect_path::Reflect) -> Option<bool> {
+                #partial_eq_fn
+            }
+        }
+    })
+}
+
+fn get_member(field: &Field, index: usize) -> Member {
+    field
+        .ident
+        .as_ref()
+        .map(|ident| Member::Named(ident.clone()))
+        .unwrap_or_else(|| Member::Unnamed(Index::from(index)))
+}
+
 fn impl_value(
     type_name: &Ident,
     generics: &Generics,
@@ -457,6 +845,12 @@ fn impl_value(
     TokenStream::from(quote! {
         #get_type_registration_impl
 
+        impl #impl_generics #bevy_reflect_path::Typed for #type_name#ty_generics #where_clause  {
+            fn type_info() -> #bevy_reflect_path::TypeInfo {
+                #bevy_reflect_path::TypeInfo::Value
+            }
+        }
+
         impl #impl_generics #bevy_reflect_path::Reflect for #type_name#ty_generics #where_clause  {
             #[inline]
             fn type_name(&self) -> &str {
@@ -750,3 +1144,11 @@ pub fn external_type_uuid(tokens: proc_macro::TokenStream) -> proc_macro::TokenS
 pub fn reflect_trait(args: TokenStream, input: TokenStream) -> TokenStream {
     reflect_trait::reflect_trait(args, input)
 }
+
+/// Makes the `pub` methods of an impl block callable through [ReflectMethods], which is registered with
+/// `#[reflect(Methods)]`. Only one impl block of a type can have this attribute, and methods can be skipped with
+/// `#[reflect(ignore)]`. Methods with `&mut` parameters can't be reflected.
+#[proc_macro_attribute]
+pub fn reflect_methods(args: TokenStream, input: TokenStream) -> TokenStream {
+    reflect_methods::reflect_methods(args, input)
+}
@@ -0,0 +1,203 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::ParseStream, parse_macro_input, spanned::Spanned, FnArg, Ident, ImplItem,
+    ImplItemMethod, ItemImpl, Pat, Path, ReturnType, Type, Visibility,
+};
+
+use crate::modules::{get_modules, get_path};
+
+static REFLECT_ATTRIBUTE_NAME: &str = "reflect";
+
+pub fn reflect_methods(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+    let modules = get_modules();
+    let bevy_reflect_path = get_path(&modules.bevy_reflect);
+
+    let mut methods = Vec::new();
+    for item in item_impl.items.iter_mut() {
+        let method = if let ImplItem::Method(method) = item {
+            method
+        } else {
+            continue;
+        };
+        // The attribute is only meaningful to this macro, so it is removed from the method.
+        let mut is_ignored = false;
+        method.attrs.retain(|attr| {
+            if attr.path.is_ident(REFLECT_ATTRIBUTE_NAME) {
+                syn::custom_keyword!(ignore);
+                is_ignored |= attr
+                    .parse_args_with(|input: ParseStream| input.parse::<Option<ignore>>())
+                    .expect("Invalid 'reflect' attribute format.")
+                    .is_some();
+                false
+            } else {
+                true
+            }
+        });
+        if is_ignored || !matches!(method.vis, Visibility::Public(_)) {
+            continue;
+        }
+        match impl_method(method, &bevy_reflect_path) {
+            Ok(tokens) => methods.push(tokens),
+            Err(err) => return TokenStream::from(err.to_compile_error()),
+        }
+    }
+
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _ty_generics, where_clause) = item_impl.generics.split_for_impl();
+    TokenStream::from(quote! {
+        #item_impl
+
+        impl #impl_generics #bevy_reflect_path::GetReflectMethods for #self_ty #where_clause {
+            fn reflect_methods() -> Vec<#bevy_reflect_path::ReflectMethod> {
+                vec![#(#methods,)*]
+            }
+        }
+    })
+}
+
+enum ArgKind {
+    Value,
+    Ref,
+    Mut,
+}
+
+/// Returns the type a parameter or return type refers to, and whether it is a reference.
+fn referent(ty: &Type) -> (&Type, ArgKind) {
+    match ty {
+        Type::Reference(reference) if reference.mutability.is_some() => {
+            (&reference.elem, ArgKind::Mut)
+        }
+        Type::Reference(reference) => (&reference.elem, ArgKind::Ref),
+        _ => (ty, ArgKind::Value),
+    }
+}
+
+fn impl_method(
+    method: &ImplItemMethod,
+    bevy_reflect_path: &Path,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let signature = &method.sig;
+    if !signature.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            signature.generics.span(),
+            "methods with generic parameters can't be reflected, add #[reflect(ignore)] to the method",
+        ));
+    }
+    if signature.asyncness.is_some() || signature.unsafety.is_some() {
+        return Err(syn::Error::new(
+            signature.span(),
+            "async and unsafe methods can't be reflected, add #[reflect(ignore)] to the method",
+        ));
+    }
+
+    let method_ident = &signature.ident;
+    let method_name = method_ident.to_string();
+    let mut receiver = None;
+    let mut arg_infos = Vec::new();
+    let mut arg_values = Vec::new();
+    let mut arg_bindings = Vec::new();
+    for input in signature.inputs.iter() {
+        let pat_type = match input {
+            FnArg::Receiver(self_receiver) => {
+                if self_receiver.reference.is_none() {
+                    return Err(syn::Error::new(
+                        self_receiver.span(),
+                        "methods taking self by value can't be reflected, add #[reflect(ignore)] to the method",
+                    ));
+                }
+                receiver = Some(self_receiver.mutability.is_some());
+                continue;
+            }
+            FnArg::Typed(pat_type) => pat_type,
+        };
+        let index = arg_infos.len();
+        let name = match &*pat_type.pat {
+            Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+            _ => index.to_string(),
+        };
+        let binding = Ident::new(&format!("__arg_{}", index), Span::call_site());
+        let (ty, kind) = referent(&pat_type.ty);
+        if let ArgKind::Mut = kind {
+            // the arguments are owned by the caller's Vec, so changes to them would be lost
+            return Err(syn::Error::new(
+                pat_type.ty.span(),
+                "methods with &mut parameters can't be reflected, add #[reflect(ignore)] to the method",
+            ));
+        }
+        arg_infos.push(quote!(#bevy_reflect_path::FieldInfo::new::<#ty>(#name)));
+        arg_bindings.push(match kind {
+            ArgKind::Value => quote!(let #binding = args.next().unwrap().take::<#ty>().unwrap();),
+            ArgKind::Ref | ArgKind::Mut => quote!(let #binding = args.next().unwrap();),
+        });
+        arg_values.push(match kind {
+            ArgKind::Value => quote!(#binding),
+            ArgKind::Ref | ArgKind::Mut => quote!(#binding.downcast_ref::<#ty>().unwrap()),
+        });
+    }
+
+    let (call, receiver_param) = match receiver {
+        Some(false) => (
+            quote!(Self::#method_ident(receiver.downcast_ref::<Self>().unwrap(), #(#arg_values),*)),
+            quote!(receiver,),
+        ),
+        Some(true) => (
+            quote!(Self::#method_ident(receiver.downcast_mut::<Self>().unwrap(), #(#arg_values),*)),
+            quote!(receiver,),
+        ),
+        None => (quote!(Self::#method_ident(#(#arg_values),*)), quote!()),
+    };
+    let method_fn = match receiver {
+        Some(false) => quote!(Ref),
+        Some(true) => quote!(Mut),
+        None => quote!(Static),
+    };
+
+    let (return_type_name, call_and_return) = match &signature.output {
+        ReturnType::Type(_, ty) if !is_unit(ty) => {
+            let (referent_ty, kind) = referent(ty);
+            let return_value = match kind {
+                ArgKind::Value => {
+                    quote!(Box::new(__ret) as Box<dyn #bevy_reflect_path::Reflect>)
+                }
+                ArgKind::Ref | ArgKind::Mut => {
+                    quote!(#bevy_reflect_path::Reflect::clone_value(__ret))
+                }
+            };
+            (
+                quote!(Some(std::any::type_name::<#referent_ty>())),
+                quote! {
+                    let __ret = #call;
+                    Some(#return_value)
+                },
+            )
+        }
+        _ => (
+            quote!(None),
+            quote! {
+                #call;
+                None
+            },
+        ),
+    };
+
+    Ok(quote! {
+        #bevy_reflect_path::ReflectMethod::new::<Self>(
+            #method_name,
+            vec![#(#arg_infos,)*],
+            #return_type_name,
+            #bevy_reflect_path::MethodFn::#method_fn(|#receiver_param args| {
+                #[allow(unused_mut, unused_variables)]
+                let mut args = args.into_iter();
+                #(#[allow(unused_mut)] #arg_bindings)*
+                #call_and_return
+            }),
+        )
+    })
+}
+
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
+}
@@ -0,0 +1,530 @@
+use crate::{
+    DynamicEnum, DynamicList, DynamicStruct, DynamicVariant, Enum, GetPath, List, Map, Reflect,
+    ReflectDefault, ReflectMut, ReflectRef, TypeInfo, TypeRegistry,
+};
+use std::any::TypeId;
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum PatchError {
+    #[error("the path {path} can't be resolved: {error}")]
+    InvalidPath { path: String, error: String },
+    #[error("expected a list at the path {0}, but found a different reflect value")]
+    ExpectedList(String),
+    #[error("expected a map at the path {0}, but found a different reflect value")]
+    ExpectedMap(String),
+    #[error("the list at the path {path} has a length of {found}, which is shorter than {len}")]
+    InvalidListLength {
+        path: String,
+        len: usize,
+        found: usize,
+    },
+    #[error("the map at the path {0} doesn't have the given key")]
+    MissingKey(String),
+    #[error("the reflected value is not a valid patch: {0}")]
+    InvalidPatch(String),
+    #[error("a value added at the path {path} can't be converted to an item of {type_name}")]
+    UnconvertibleValue { path: String, type_name: String },
+    #[error("the map at the path {0} can't look up keys that don't support hashing")]
+    UnhashableKey(String),
+    #[error("the list or map at the path {0} doesn't support removing items")]
+    UnsupportedRemove(String),
+}
+
+/// A single change of a [Patch]. Paths use the syntax of [GetPath], and are relative to the value the patch is
+/// applied to.
+pub enum PatchOp {
+    /// Applies `value` to the value at `path`.
+    Set {
+        path: String,
+        value: Box<dyn Reflect>,
+    },
+    /// Pushes `value` to the end of the list at `path`.
+    Push {
+        path: String,
+        value: Box<dyn Reflect>,
+    },
+    /// Removes values from the end of the list at `path` until it is `len` long.
+    Truncate { path: String, len: usize },
+    /// Inserts a new entry in the map at `path`.
+    Insert {
+        path: String,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// Removes the entry with `key` from the map at `path`.
+    Remove { path: String, key: Box<dyn Reflect> },
+    /// Applies `patch` to the value of the entry with `key` in the map at `path`. Map entries can't be addressed by
+    /// paths, so changes to them are nested patches.
+    Entry {
+        path: String,
+        key: Box<dyn Reflect>,
+        patch: Patch,
+    },
+}
+
+impl PatchOp {
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOp::Set { path, .. }
+            | PatchOp::Push { path, .. }
+            | PatchOp::Truncate { path, .. }
+            | PatchOp::Insert { path, .. }
+            | PatchOp::Remove { path, .. }
+            | PatchOp::Entry { path, .. } => path,
+        }
+    }
+}
+
+/// The changes between two reflected values, see [Patch::diff].
+///
+/// A patch can be converted to a reflected value with [Patch::clone_dynamic] to be serialized with
+/// [ReflectSerializer](crate::serde::ReflectSerializer), and converted back with [Patch::from_reflect].
+#[derive(Default)]
+pub struct Patch {
+    ops: Vec<PatchOp>,
+}
+
+impl Patch {
+    /// Returns the changes needed to turn `from` into `to`.
+    ///
+    /// Structs, tuple structs, tuples, lists, maps and enums with the same variant are compared field by field.
+    /// Other values are compared with [Reflect::reflect_partial_eq], and are considered changed if they don't
+    /// support it.
+    pub fn diff(from: &dyn Reflect, to: &dyn Reflect) -> Patch {
+        let mut patch = Patch::default();
+        diff_value(&mut String::new(), from, to, &mut patch.ops);
+        patch
+    }
+
+    pub fn push(&mut self, op: PatchOp) {
+        self.ops.push(op);
+    }
+
+    pub fn ops(&self) -> &[PatchOp] {
+        &self.ops
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Applies the changes to `value`, in order. Changes applied before an error are kept.
+    ///
+    /// Values pushed to lists and inserted in maps are converted to the item types of the list or map if those are
+    /// registered in `registry` with [ReflectDefault]. Otherwise they are inserted as they are, which only works for
+    /// dynamic lists and maps, and for value types. Values that can't be inserted return
+    /// [PatchError::UnconvertibleValue].
+    pub fn apply(
+        &self,
+        value: &mut dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Result<(), PatchError> {
+        for op in self.ops.iter() {
+            let path = op.path();
+            let target = value
+                .path_mut(path)
+                .map_err(|error| PatchError::InvalidPath {
+                    path: path.to_string(),
+                    error: error.to_string(),
+                })?;
+            match op {
+                PatchOp::Set { value, .. } => target.apply(&**value),
+                PatchOp::Push { value, .. } => {
+                    let list = expect_list(target, path)?;
+                    let item_type = match registered_type_info(list.any().type_id(), registry) {
+                        Some(TypeInfo::List(info)) => Some(info.item_type_id()),
+                        _ => None,
+                    };
+                    let item = to_concrete(&**value, item_type, registry)
+                        .ok_or_else(|| unconvertible(path, list.type_name()))?;
+                    list.try_push(item)
+                        .map_err(|_| unconvertible(path, list.type_name()))?;
+                }
+                PatchOp::Truncate { len, .. } => {
+                    let list = expect_list(target, path)?;
+                    if list.len() < *len {
+                        return Err(PatchError::InvalidListLength {
+                            path: path.to_string(),
+                            len: *len,
+                            found: list.len(),
+                        });
+                    }
+                    while list.len() > *len {
+                        list.pop()
+                            .ok_or_else(|| PatchError::UnsupportedRemove(path.to_string()))?;
+                    }
+                }
+                PatchOp::Insert { key, value, .. } => {
+                    let map = expect_map(target, path)?;
+                    expect_hashable(&**key, path)?;
+                    let (key_type, value_type) =
+                        match registered_type_info(map.any().type_id(), registry) {
+                            Some(TypeInfo::Map(info)) => {
+                                (Some(info.key_type_id()), Some(info.value_type_id()))
+                            }
+                            _ => (None, None),
+                        };
+                    let key = to_concrete(&**key, key_type, registry)
+                        .ok_or_else(|| unconvertible(path, map.type_name()))?;
+                    let value = to_concrete(&**value, value_type, registry)
+                        .ok_or_else(|| unconvertible(path, map.type_name()))?;
+                    map.try_insert_boxed(key, value)
+                        .map_err(|_| unconvertible(path, map.type_name()))?;
+                }
+                PatchOp::Remove { key, .. } => {
+                    let map = expect_map(target, path)?;
+                    expect_hashable(&**key, path)?;
+                    map.remove(&**key)
+                        .ok_or_else(|| PatchError::MissingKey(path.to_string()))?;
+                }
+                PatchOp::Entry { key, patch, .. } => {
+                    let map = expect_map(target, path)?;
+                    expect_hashable(&**key, path)?;
+                    let entry = map
+                        .get_mut(&**key)
+                        .ok_or_else(|| PatchError::MissingKey(path.to_string()))?;
+                    patch.apply(entry, registry)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the patch as a [DynamicList] of enums, one per [PatchOp].
+    pub fn clone_dynamic(&self) -> DynamicList {
+        let mut list = DynamicList::default();
+        list.set_name(std::any::type_name::<Patch>().to_string());
+        for op in self.ops.iter() {
+            let mut fields = DynamicStruct::default();
+            fields.insert("path", op.path().to_string());
+            let (variant_name, variant_index) = match op {
+                PatchOp::Set { value, .. } => {
+                    fields.insert_boxed("value", value.clone_value());
+                    ("Set", 0)
+                }
+                PatchOp::Push { value, .. } => {
+                    fields.insert_boxed("value", value.clone_value());
+                    ("Push", 1)
+                }
+                PatchOp::Truncate { len, .. } => {
+                    fields.insert("len", *len);
+                    ("Truncate", 2)
+                }
+                PatchOp::Insert { key, value, .. } => {
+                    fields.insert_boxed("key", key.clone_value());
+                    fields.insert_boxed("value", value.clone_value());
+                    ("Insert", 3)
+                }
+                PatchOp::Remove { key, .. } => {
+                    fields.insert_boxed("key", key.clone_value());
+                    ("Remove", 4)
+                }
+                PatchOp::Entry { key, patch, .. } => {
+                    fields.insert_boxed("key", key.clone_value());
+                    fields.insert("patch", patch.clone_dynamic());
+                    ("Entry", 5)
+                }
+            };
+            let mut dynamic_op = DynamicEnum::new(
+                variant_name.to_string(),
+                variant_index,
+                DynamicVariant::Struct(fields),
+            );
+            dynamic_op.set_name(std::any::type_name::<PatchOp>().to_string());
+            list.push(dynamic_op);
+        }
+        list
+    }
+
+    /// Reads a patch from a value returned by [Patch::clone_dynamic], or deserialized from one.
+    pub fn from_reflect(value: &dyn Reflect) -> Result<Patch, PatchError> {
+        let list = match value.reflect_ref() {
+            ReflectRef::List(list) => list,
+            _ => return Err(PatchError::InvalidPatch("expected a list".to_string())),
+        };
+        let mut patch = Patch::default();
+        for op in list.iter() {
+            let op = match op.reflect_ref() {
+                ReflectRef::Enum(op) => op,
+                _ => {
+                    return Err(PatchError::InvalidPatch(
+                        "expected a list of enums".to_string(),
+                    ))
+                }
+            };
+            let path = op_field::<String>(op, "path")?.clone();
+            patch.push(match op.variant_name() {
+                "Set" => PatchOp::Set {
+                    path,
+                    value: op_value(op, "value")?,
+                },
+                "Push" => PatchOp::Push {
+                    path,
+                    value: op_value(op, "value")?,
+                },
+                "Truncate" => PatchOp::Truncate {
+                    path,
+                    len: *op_field::<usize>(op, "len")?,
+                },
+                "Insert" => PatchOp::Insert {
+                    path,
+                    key: op_value(op, "key")?,
+                    value: op_value(op, "value")?,
+                },
+                "Remove" => PatchOp::Remove {
+                    path,
+                    key: op_value(op, "key")?,
+                },
+                "Entry" => PatchOp::Entry {
+                    path,
+                    key: op_value(op, "key")?,
+                    patch: Patch::from_reflect(op_value(op, "patch")?.as_ref())?,
+                },
+                variant_name => {
+                    return Err(PatchError::InvalidPatch(format!(
+                        "unknown operation {}",
+                        variant_name
+                    )))
+                }
+            });
+        }
+        Ok(patch)
+    }
+}
+
+fn diff_value(path: &mut String, from: &dyn Reflect, to: &dyn Reflect, ops: &mut Vec<PatchOp>) {
+    match (from.reflect_ref(), to.reflect_ref()) {
+        (ReflectRef::Struct(from_struct), ReflectRef::Struct(to_struct)) => {
+            if from_struct.field_len() != to_struct.field_len()
+                || Iterator::any(&mut (0..to_struct.field_len()), |index| {
+                    from_struct
+                        .field(to_struct.name_at(index).unwrap())
+                        .is_none()
+                })
+            {
+                push_set(path, to, ops);
+                return;
+            }
+            for (index, to_field) in to_struct.iter_fields().enumerate() {
+                let name = to_struct.name_at(index).unwrap();
+                let from_field = from_struct.field(name).unwrap();
+                diff_field(path, &format!(".{}", name), from_field, to_field, ops);
+            }
+        }
+        (ReflectRef::TupleStruct(from_tuple_struct), ReflectRef::TupleStruct(to_tuple_struct)) => {
+            if from_tuple_struct.field_len() != to_tuple_struct.field_len() {
+                push_set(path, to, ops);
+                return;
+            }
+            for (index, (from_field, to_field)) in from_tuple_struct
+                .iter_fields()
+                .zip(to_tuple_struct.iter_fields())
+                .enumerate()
+            {
+                diff_field(path, &format!(".{}", index), from_field, to_field, ops);
+            }
+        }
+        (ReflectRef::Tuple(from_tuple), ReflectRef::Tuple(to_tuple)) => {
+            if from_tuple.field_len() != to_tuple.field_len() {
+                push_set(path, to, ops);
+                return;
+            }
+            for (index, (from_field, to_field)) in from_tuple
+                .iter_fields()
+                .zip(to_tuple.iter_fields())
+                .enumerate()
+            {
+                diff_field(path, &format!(".{}", index), from_field, to_field, ops);
+            }
+        }
+        (ReflectRef::List(from_list), ReflectRef::List(to_list)) => {
+            for (index, (from_item, to_item)) in from_list.iter().zip(to_list.iter()).enumerate() {
+                diff_field(path, &format!("[{}]", index), from_item, to_item, ops);
+            }
+            for to_item in to_list.iter().skip(from_list.len()) {
+                ops.push(PatchOp::Push {
+                    path: path.clone(),
+                    value: to_item.clone_value(),
+                });
+            }
+            if to_list.len() < from_list.len() {
+                ops.push(PatchOp::Truncate {
+                    path: path.clone(),
+                    len: to_list.len(),
+                });
+            }
+        }
+        (ReflectRef::Map(from_map), ReflectRef::Map(to_map)) => {
+            for (key, _) in from_map.iter() {
+                if to_map.get(key).is_none() {
+                    ops.push(PatchOp::Remove {
+                        path: path.clone(),
+                        key: key.clone_value(),
+                    });
+                }
+            }
+            for (key, to_value) in to_map.iter() {
+                match from_map.get(key) {
+                    Some(from_value) => {
+                        let patch = Patch::diff(from_value, to_value);
+                        if !patch.is_empty() {
+                            ops.push(PatchOp::Entry {
+                                path: path.clone(),
+                                key: key.clone_value(),
+                                patch,
+                            });
+                        }
+                    }
+                    None => ops.push(PatchOp::Insert {
+                        path: path.clone(),
+                        key: key.clone_value(),
+                        value: to_value.clone_value(),
+                    }),
+                }
+            }
+        }
+        (ReflectRef::Enum(from_enum), ReflectRef::Enum(to_enum)) => {
+            if from_enum.variant_name() != to_enum.variant_name()
+                || from_enum.variant_type() != to_enum.variant_type()
+                || from_enum.field_len() != to_enum.field_len()
+            {
+                push_set(path, to, ops);
+                return;
+            }
+            for (index, to_field) in to_enum.iter_fields().enumerate() {
+                let (from_field, segment) = match to_enum.name_at(index) {
+                    Some(name) => (from_enum.field(name), format!(".{}", name)),
+                    None => (from_enum.field_at(index), format!(".{}", index)),
+                };
+                match from_field {
+                    Some(from_field) => diff_field(path, &segment, from_field, to_field, ops),
+                    None => {
+                        push_set(path, to, ops);
+                        return;
+                    }
+                }
+            }
+        }
+        (ReflectRef::Value(_), ReflectRef::Value(_)) => {
+            if from.reflect_partial_eq(to) != Some(true) {
+                push_set(path, to, ops);
+            }
+        }
+        _ => push_set(path, to, ops),
+    }
+}
+
+fn push_set(path: &str, to: &dyn Reflect, ops: &mut Vec<PatchOp>) {
+    ops.push(PatchOp::Set {
+        path: path.to_string(),
+        value: to.clone_value(),
+    });
+}
+
+fn diff_field(
+    path: &mut String,
+    segment: &str,
+    from: &dyn Reflect,
+    to: &dyn Reflect,
+    ops: &mut Vec<PatchOp>,
+) {
+    let len = path.len();
+    path.push_str(segment);
+    diff_value(path, from, to, ops);
+    path.truncate(len);
+}
+
+fn expect_list<'a>(value: &'a mut dyn Reflect, path: &str) -> Result<&'a mut dyn List, PatchError> {
+    match value.reflect_mut() {
+        ReflectMut::List(list) => Ok(list),
+        _ => Err(PatchError::ExpectedList(path.to_string())),
+    }
+}
+
+fn expect_map<'a>(value: &'a mut dyn Reflect, path: &str) -> Result<&'a mut dyn Map, PatchError> {
+    match value.reflect_mut() {
+        ReflectMut::Map(map) => Ok(map),
+        _ => Err(PatchError::ExpectedMap(path.to_string())),
+    }
+}
+
+fn registered_type_info(type_id: TypeId, registry: &TypeRegistry) -> Option<&TypeInfo> {
+    registry
+        .get(type_id)
+        .map(|registration| registration.type_info())
+}
+
+/// Clones `value` as the type `concrete_type` if that type is registered with [ReflectDefault], so that it can be
+/// inserted in a list or map of that type. Returns `None` if the clone is not of `concrete_type` otherwise.
+fn to_concrete(
+    value: &dyn Reflect,
+    concrete_type: Option<TypeId>,
+    registry: &TypeRegistry,
+) -> Option<Box<dyn Reflect>> {
+    let type_id = match concrete_type {
+        Some(type_id) => type_id,
+        None => return Some(value.clone_value()),
+    };
+    if let Some(reflect_default) = registry.get_type_data::<ReflectDefault>(type_id) {
+        let mut concrete = reflect_default.default();
+        // values only apply values of their own type, and panic otherwise
+        if let ReflectRef::Value(_) = concrete.reflect_ref() {
+            if value.any().type_id() != type_id {
+                return None;
+            }
+        }
+        concrete.apply(value);
+        return Some(concrete);
+    }
+    let clone = value.clone_value();
+    if clone.any().type_id() == type_id {
+        Some(clone)
+    } else {
+        None
+    }
+}
+
+fn unconvertible(path: &str, type_name: &str) -> PatchError {
+    PatchError::UnconvertibleValue {
+        path: path.to_string(),
+        type_name: type_name.to_string(),
+    }
+}
+
+/// Dynamic maps look up keys by their [Reflect::reflect_hash], and panic if there is none.
+fn expect_hashable(key: &dyn Reflect, path: &str) -> Result<(), PatchError> {
+    match key.reflect_hash() {
+        Some(_) => Ok(()),
+        None => Err(PatchError::UnhashableKey(path.to_string())),
+    }
+}
+
+fn op_field<'a, T: Reflect>(op: &'a dyn Enum, name: &str) -> Result<&'a T, PatchError> {
+    op.field(name)
+        .and_then(|field| field.downcast_ref::<T>())
+        .ok_or_else(|| {
+            PatchError::InvalidPatch(format!(
+                "{} is missing the field {} of type {}",
+                op.variant_name(),
+                name,
+                std::any::type_name::<T>()
+            ))
+        })
+}
+
+fn op_value(op: &dyn Enum, name: &str) -> Result<Box<dyn Reflect>, PatchError> {
+    op.field(name)
+        .map(|field| field.clone_value())
+        .ok_or_else(|| {
+            PatchError::InvalidPatch(format!(
+                "{} is missing the field {}",
+                op.variant_name(),
+                name
+            ))
+        })
+}
@@ -0,0 +1,319 @@
+use crate::{
+    serde::Serializable, DynamicStruct, DynamicTuple, Reflect, ReflectMut, ReflectRef, Struct,
+    Tuple,
+};
+use std::any::Any;
+
+/// The kind of fields an enum variant has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariantType {
+    /// A variant with named fields, like `Foo::Bar { a: u32 }`.
+    Struct,
+    /// A variant with unnamed fields, like `Foo::Bar(u32)`.
+    Tuple,
+    /// A variant without fields, like `Foo::Bar`.
+    Unit,
+}
+
+/// A rust enum reflection. The fields are the fields of the current variant.
+///
+/// Fields of struct variants can be accessed by name or by index, fields of tuple variants only by index.
+///
+/// When `Enum` is derived, applying a value of another variant switches to that variant, then applies the fields of
+/// the value. The fields of the new variant are cloned from the value when it holds them as their concrete type, and
+/// are created with `Default` otherwise. Fields marked with `#[reflect(no_default)]` don't need to implement `Default`,
+/// applying a value that doesn't hold them as their concrete type panics instead.
+pub trait Enum: Reflect {
+    fn variant_name(&self) -> &str;
+    fn variant_index(&self) -> usize;
+    fn variant_type(&self) -> VariantType;
+    fn field(&self, name: &str) -> Option<&dyn Reflect>;
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect>;
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect>;
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
+    /// Returns the name of the field at `index`, if the current variant is a struct variant.
+    fn name_at(&self, index: usize) -> Option<&str>;
+    fn field_len(&self) -> usize;
+    fn iter_fields(&self) -> VariantFieldIter;
+    fn clone_dynamic(&self) -> DynamicEnum;
+}
+
+pub struct VariantFieldIter<'a> {
+    pub(crate) enum_value: &'a dyn Enum,
+    pub(crate) index: usize,
+}
+
+impl<'a> VariantFieldIter<'a> {
+    pub fn new(value: &'a dyn Enum) -> Self {
+        VariantFieldIter {
+            enum_value: value,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for VariantFieldIter<'a> {
+    type Item = &'a dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.enum_value.field_at(self.index);
+        self.index += 1;
+        value
+    }
+}
+
+/// The fields of the variant of a [DynamicEnum].
+pub enum DynamicVariant {
+    Unit,
+    Tuple(DynamicTuple),
+    Struct(DynamicStruct),
+}
+
+impl Default for DynamicVariant {
+    fn default() -> Self {
+        DynamicVariant::Unit
+    }
+}
+
+impl DynamicVariant {
+    pub fn variant_type(&self) -> VariantType {
+        match self {
+            DynamicVariant::Unit => VariantType::Unit,
+            DynamicVariant::Tuple(_) => VariantType::Tuple,
+            DynamicVariant::Struct(_) => VariantType::Struct,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DynamicEnum {
+    name: String,
+    variant_name: String,
+    variant_index: usize,
+    variant: DynamicVariant,
+}
+
+impl DynamicEnum {
+    pub fn new(variant_name: String, variant_index: usize, variant: DynamicVariant) -> Self {
+        DynamicEnum {
+            name: String::default(),
+            variant_name,
+            variant_index,
+            variant,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn variant(&self) -> &DynamicVariant {
+        &self.variant
+    }
+
+    /// Sets the current variant. Variants are matched by name when applied to an enum, `variant_index` is only
+    /// informative.
+    pub fn set_variant(
+        &mut self,
+        variant_name: String,
+        variant_index: usize,
+        variant: DynamicVariant,
+    ) {
+        self.variant_name = variant_name;
+        self.variant_index = variant_index;
+        self.variant = variant;
+    }
+}
+
+impl Enum for DynamicEnum {
+    #[inline]
+    fn variant_name(&self) -> &str {
+        &self.variant_name
+    }
+
+    #[inline]
+    fn variant_index(&self) -> usize {
+        self.variant_index
+    }
+
+    #[inline]
+    fn variant_type(&self) -> VariantType {
+        self.variant.variant_type()
+    }
+
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match &self.variant {
+            DynamicVariant::Struct(dynamic_struct) => dynamic_struct.field(name),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Reflect> {
+        match &mut self.variant {
+            DynamicVariant::Struct(dynamic_struct) => dynamic_struct.field_mut(name),
+            _ => None,
+        }
+    }
+
+    fn field_at(&self, index: usize) -> Option<&dyn Reflect> {
+        match &self.variant {
+            DynamicVariant::Unit => None,
+            DynamicVariant::Tuple(tuple) => tuple.field(index),
+            DynamicVariant::Struct(dynamic_struct) => dynamic_struct.field_at(index),
+        }
+    }
+
+    fn field_at_mut(&mut self, index: usize) -> Option<&mut dyn Reflect> {
+        match &mut self.variant {
+            DynamicVariant::Unit => None,
+            DynamicVariant::Tuple(tuple) => tuple.field_mut(index),
+            DynamicVariant::Struct(dynamic_struct) => dynamic_struct.field_at_mut(index),
+        }
+    }
+
+    fn name_at(&self, index: usize) -> Option<&str> {
+        match &self.variant {
+            DynamicVariant::Struct(dynamic_struct) => dynamic_struct.name_at(index),
+            _ => None,
+        }
+    }
+
+    fn field_len(&self) -> usize {
+        match &self.variant {
+            DynamicVariant::Unit => 0,
+            DynamicVariant::Tuple(tuple) => tuple.field_len(),
+            DynamicVariant::Struct(dynamic_struct) => dynamic_struct.field_len(),
+        }
+    }
+
+    #[inline]
+    fn iter_fields(&self) -> VariantFieldIter {
+        VariantFieldIter::new(self)
+    }
+
+    fn clone_dynamic(&self) -> DynamicEnum {
+        DynamicEnum {
+            name: self.name.clone(),
+            variant_name: self.variant_name.clone(),
+            variant_index: self.variant_index,
+            variant: match &self.variant {
+                DynamicVariant::Unit => DynamicVariant::Unit,
+                DynamicVariant::Tuple(tuple) => DynamicVariant::Tuple(tuple.clone_dynamic()),
+                DynamicVariant::Struct(dynamic_struct) => {
+                    DynamicVariant::Struct(dynamic_struct.clone_dynamic())
+                }
+            },
+        }
+    }
+}
+
+impl Reflect for DynamicEnum {
+    #[inline]
+    fn type_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    #[inline]
+    fn any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    #[inline]
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Enum(self)
+    }
+
+    #[inline]
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Enum(self)
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        if let ReflectRef::Enum(enum_value) = value.reflect_ref() {
+            if self.variant_name != enum_value.variant_name()
+                || self.variant_type() != enum_value.variant_type()
+            {
+                let mut dynamic = enum_value.clone_dynamic();
+                if !self.name.is_empty() {
+                    dynamic.name = std::mem::take(&mut self.name);
+                }
+                *self = dynamic;
+                return;
+            }
+            for (i, value) in enum_value.iter_fields().enumerate() {
+                let field = match enum_value.name_at(i) {
+                    Some(name) => self.field_mut(name),
+                    None => self.field_at_mut(i),
+                };
+                if let Some(v) = field {
+                    v.apply(value)
+                }
+            }
+        } else {
+            panic!("Attempted to apply non-enum type to enum type.");
+        }
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_hash(&self) -> Option<u64> {
+        None
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        enum_partial_eq(self, value)
+    }
+
+    fn serializable(&self) -> Option<Serializable> {
+        None
+    }
+}
+
+#[inline]
+pub fn enum_partial_eq<E: Enum>(a: &E, b: &dyn Reflect) -> Option<bool> {
+    let enum_value = if let ReflectRef::Enum(enum_value) = b.reflect_ref() {
+        enum_value
+    } else {
+        return Some(false);
+    };
+
+    if a.variant_name() != enum_value.variant_name()
+        || a.variant_type() != enum_value.variant_type()
+        || a.field_len() != enum_value.field_len()
+    {
+        return Some(false);
+    }
+
+    for (i, value) in enum_value.iter_fields().enumerate() {
+        let field_value = match enum_value.name_at(i) {
+            Some(name) => a.field(name),
+            None => a.field_at(i),
+        };
+        if let Some(field_value) = field_value {
+            if let Some(false) | None = field_value.reflect_partial_eq(value) {
+                return Some(false);
+            }
+        } else {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
@@ -1,7 +1,9 @@
 use smallvec::{Array, SmallVec};
 use std::any::Any;
 
-use crate::{serde::Serializable, List, ListIter, Reflect, ReflectMut, ReflectRef};
+use crate::{
+    serde::Serializable, List, ListInfo, ListIter, Reflect, ReflectMut, ReflectRef, TypeInfo, Typed,
+};
 
 impl<T: Array + Send + Sync + 'static> List for SmallVec<T>
 where
@@ -37,6 +39,15 @@ where
         SmallVec::push(self, value);
     }
 
+    fn try_push(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        SmallVec::push(self, value.take::<T::Item>()?);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        SmallVec::pop(self).map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
+
     fn iter(&self) -> ListIter {
         ListIter {
             list: self,
@@ -94,3 +105,12 @@ where
         None
     }
 }
+
+impl<T: Array + Send + Sync + 'static> Typed for SmallVec<T>
+where
+    T::Item: Reflect + Clone,
+{
+    fn type_info() -> TypeInfo {
+        TypeInfo::List(ListInfo::new::<T::Item>())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    map_partial_eq, serde::Serializable, DynamicMap, List, ListIter, Map, MapIter, Reflect,
-    ReflectDeserialize, ReflectMut, ReflectRef,
+    map_partial_eq, serde::Serializable, DynamicMap, List, ListInfo, ListIter, Map, MapInfo,
+    MapIter, Reflect, ReflectDefault, ReflectDeserialize, ReflectMut, ReflectRef, TypeInfo, Typed,
 };
 
 use bevy_reflect_derive::impl_reflect_value;
@@ -13,26 +13,26 @@ use std::{
     ops::Range,
 };
 
-impl_reflect_value!(bool(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(u8(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(u16(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(u32(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(u64(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(u128(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(usize(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(i8(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(i16(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(i32(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(i64(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(i128(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(isize(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(f32(Serialize, Deserialize));
-impl_reflect_value!(f64(Serialize, Deserialize));
-impl_reflect_value!(String(Hash, PartialEq, Serialize, Deserialize));
-impl_reflect_value!(Option<T: Serialize + Clone + for<'de> Deserialize<'de> + Reflect + 'static>(Serialize, Deserialize));
+impl_reflect_value!(bool(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(u8(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(u16(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(u32(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(u64(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(u128(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(usize(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(i8(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(i16(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(i32(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(i64(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(i128(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(isize(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(f32(PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(f64(PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(String(Hash, PartialEq, Serialize, Deserialize, Default));
+impl_reflect_value!(Option<T: Serialize + Clone + for<'de> Deserialize<'de> + Reflect + 'static>(Serialize, Deserialize, Default));
 impl_reflect_value!(HashSet<T: Serialize + Hash + Eq + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
 impl_reflect_value!(Range<T: Serialize + Clone + for<'de> Deserialize<'de> + Send + Sync + 'static>(Serialize, Deserialize));
-impl_reflect_value!(Duration);
+impl_reflect_value!(Duration(PartialEq, Default));
 
 impl<T: Reflect> List for Vec<T> {
     fn get(&self, index: usize) -> Option<&dyn Reflect> {
@@ -63,6 +63,15 @@ impl<T: Reflect> List for Vec<T> {
         });
         Vec::push(self, value);
     }
+
+    fn try_push(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        Vec::push(self, value.take::<T>()?);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        Vec::pop(self).map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
 }
 
 impl<T: Reflect> Reflect for Vec<T> {
@@ -112,6 +121,12 @@ impl<T: Reflect> Reflect for Vec<T> {
     }
 }
 
+impl<T: Reflect> Typed for Vec<T> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::List(ListInfo::new::<T>())
+    }
+}
+
 impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Map for HashMap<K, V> {
     fn get(&self, key: &dyn Reflect) -> Option<&dyn Reflect> {
         key.downcast_ref::<K>()
@@ -150,6 +165,29 @@ impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Map for HashMap<K, V> {
         }
         dynamic_map
     }
+
+    fn try_insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<(), (Box<dyn Reflect>, Box<dyn Reflect>)> {
+        let key = match key.take::<K>() {
+            Ok(key) => key,
+            Err(key) => return Err((key, value)),
+        };
+        let value = match value.take::<V>() {
+            Ok(value) => value,
+            Err(value) => return Err((Box::new(key), value)),
+        };
+        HashMap::insert(self, key, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        key.downcast_ref::<K>()
+            .and_then(|key| HashMap::remove(self, key))
+            .map(|value| Box::new(value) as Box<dyn Reflect>)
+    }
 }
 
 impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Reflect for HashMap<K, V> {
@@ -207,6 +245,12 @@ impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Reflect for HashMap<K,
     }
 }
 
+impl<K: Reflect + Clone + Eq + Hash, V: Reflect + Clone> Typed for HashMap<K, V> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::Map(MapInfo::new::<K, V>())
+    }
+}
+
 impl Reflect for Cow<'static, str> {
     fn type_name(&self) -> &str {
         std::any::type_name::<Self>()
@@ -266,3 +310,9 @@ impl Reflect for Cow<'static, str> {
         Some(Serializable::Borrowed(self))
     }
 }
+
+impl Typed for Cow<'static, str> {
+    fn type_info() -> TypeInfo {
+        TypeInfo::Value
+    }
+}
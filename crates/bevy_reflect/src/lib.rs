@@ -1,10 +1,14 @@
+mod diff;
+mod enum_trait;
 mod list;
 mod map;
+mod method;
 mod path;
 mod reflect;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
+mod type_info;
 mod type_registry;
 mod type_uuid;
 mod impls {
@@ -24,19 +28,23 @@ mod impls {
 pub mod serde;
 pub mod prelude {
     pub use crate::{
-        reflect_trait, GetField, GetTupleStructField, Reflect, ReflectDeserialize, Struct,
-        TupleStruct,
+        reflect_methods, reflect_trait, Enum, GetField, GetTupleStructField, Reflect,
+        ReflectDefault, ReflectDeserialize, ReflectMethods, Struct, TupleStruct,
     };
 }
 
+pub use diff::*;
+pub use enum_trait::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
+pub use method::*;
 pub use path::*;
 pub use reflect::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
+pub use type_info::*;
 pub use type_registry::*;
 pub use type_uuid::*;
 
@@ -53,7 +61,11 @@ mod tests {
         Deserializer,
     };
 
-    use crate::serde::{ReflectDeserializer, ReflectSerializer};
+    use crate::serde::{
+        deserialize_binary, serialize_binary, ReflectBinaryError, ReflectBinaryReader,
+        ReflectBinaryWriter, ReflectDeserializer, ReflectSerializer, BINARY_FORMAT_VERSION,
+        MAX_BINARY_DEPTH,
+    };
 
     use super::*;
 
@@ -314,6 +326,661 @@ mod tests {
         assert!(foo.reflect_partial_eq(&dynamic_struct).unwrap());
     }
 
+    #[test]
+    fn reflect_enum() {
+        #[derive(Reflect, PartialEq, Debug)]
+        enum Foo {
+            A,
+            B(u32, #[reflect(ignore)] u32),
+            C { x: f32, y: Bar },
+        }
+
+        #[derive(Reflect, PartialEq, Debug, Default)]
+        struct Bar {
+            z: u32,
+        }
+
+        let mut foo = Foo::C {
+            x: 1.0,
+            y: Bar { z: 2 },
+        };
+        assert_eq!(foo.variant_name(), "C");
+        assert_eq!(foo.variant_index(), 2);
+        assert_eq!(foo.variant_type(), VariantType::Struct);
+        assert_eq!(foo.field_len(), 2);
+        assert_eq!(foo.name_at(1), Some("y"));
+        assert_eq!(*foo.get_path::<u32>("y.z").unwrap(), 2);
+
+        *foo.field_mut("x").unwrap().downcast_mut::<f32>().unwrap() = 3.0;
+        assert_eq!(
+            foo,
+            Foo::C {
+                x: 3.0,
+                y: Bar { z: 2 }
+            }
+        );
+
+        // patching with the same variant only changes the given fields
+        let mut fields = DynamicStruct::default();
+        fields.insert("x", 4.0f32);
+        foo.apply(&DynamicEnum::new(
+            "C".to_string(),
+            2,
+            DynamicVariant::Struct(fields),
+        ));
+        assert_eq!(
+            foo,
+            Foo::C {
+                x: 4.0,
+                y: Bar { z: 2 }
+            }
+        );
+
+        // patching with another variant switches variants, ignored fields get their default value
+        let mut fields = DynamicTuple::default();
+        fields.insert(5u32);
+        foo.apply(&DynamicEnum::new(
+            "B".to_string(),
+            1,
+            DynamicVariant::Tuple(fields),
+        ));
+        assert_eq!(foo, Foo::B(5, 0));
+        assert_eq!(foo.field_len(), 1);
+        assert_eq!(*foo.get_path::<u32>("0").unwrap(), 5);
+
+        foo.apply(&Foo::A);
+        assert_eq!(foo, Foo::A);
+
+        let dynamic_enum = Foo::B(6, 7).clone_dynamic();
+        assert_eq!(dynamic_enum.type_name(), std::any::type_name::<Foo>());
+        assert!(Foo::B(6, 0).reflect_partial_eq(&dynamic_enum).unwrap());
+        assert!(!Foo::B(8, 0).reflect_partial_eq(&dynamic_enum).unwrap());
+        assert!(!Foo::A.reflect_partial_eq(&dynamic_enum).unwrap());
+    }
+
+    #[test]
+    fn reflect_enum_without_default_fields() {
+        #[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+        #[reflect_value(PartialEq)]
+        struct Id(u32);
+
+        #[derive(Reflect, PartialEq, Debug)]
+        enum Target {
+            None,
+            Single(#[reflect(no_default)] Id),
+            Pair {
+                #[reflect(no_default)]
+                first: Id,
+                second: u32,
+            },
+        }
+
+        // fields of the new variant are taken from the applied value
+        let mut target = Target::None;
+        target.apply(&Target::Single(Id(1)));
+        assert_eq!(target, Target::Single(Id(1)));
+
+        target.apply(
+            &Target::Pair {
+                first: Id(2),
+                second: 3,
+            }
+            .clone_dynamic(),
+        );
+        assert_eq!(
+            target,
+            Target::Pair {
+                first: Id(2),
+                second: 3
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn reflect_enum_without_default_fields_missing_value() {
+        #[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+        #[reflect_value(PartialEq)]
+        struct Id(u32);
+
+        #[derive(Reflect, PartialEq, Debug)]
+        enum Target {
+            None,
+            Single(#[reflect(no_default)] Id),
+        }
+
+        let mut target = Target::None;
+        target.apply(&DynamicEnum::new(
+            "Single".to_string(),
+            1,
+            DynamicVariant::Tuple(DynamicTuple::default()),
+        ));
+    }
+
+    #[test]
+    fn reflect_enum_serialize() {
+        #[derive(Reflect, PartialEq, Debug)]
+        enum State {
+            Idle,
+            Moving { speed: f32, target: (i32, i32) },
+            Waiting(u32),
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<f32>();
+        registry.register::<i32>();
+        registry.register::<u32>();
+
+        let mut value = State::Idle;
+        for state in vec![
+            State::Moving {
+                speed: 2.0,
+                target: (1, 2),
+            },
+            State::Waiting(3),
+            State::Idle,
+        ] {
+            let serializer = ReflectSerializer::new(&state, &registry);
+            let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+
+            let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+            let reflect_deserializer = ReflectDeserializer::new(&registry);
+            let deserialized = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+            let dynamic_enum = deserialized.take::<DynamicEnum>().unwrap();
+            assert!(state.reflect_partial_eq(&dynamic_enum).unwrap());
+
+            value.apply(&dynamic_enum);
+            assert_eq!(value, state);
+        }
+    }
+
+    #[test]
+    fn reflect_binary_serialize() {
+        #[derive(Reflect)]
+        enum Baz {
+            A,
+            B { x: u32 },
+        }
+
+        #[derive(Reflect)]
+        struct Bar(u32);
+
+        #[derive(Reflect)]
+        struct Foo {
+            a: u32,
+            b: String,
+            c: Vec<u32>,
+            d: HashMap<u32, String>,
+            e: (f32, bool),
+            f: Bar,
+            g: Baz,
+        }
+
+        let mut map = HashMap::default();
+        map.insert(1, "one".to_string());
+        let foo = Foo {
+            a: 1,
+            b: "hello".to_string(),
+            c: vec![2, 3],
+            d: map,
+            e: (4.0, true),
+            f: Bar(5),
+            g: Baz::B { x: 6 },
+        };
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<String>();
+        registry.register::<f32>();
+        registry.register::<bool>();
+        registry.register::<Foo>();
+        registry.add_registration(TypeRegistration::of::<Vec<u32>>());
+        registry.add_registration(TypeRegistration::of::<HashMap<u32, String>>());
+
+        let bytes = serialize_binary(&foo, &registry).unwrap();
+        // registered type names are only written as hashes
+        let type_name = std::any::type_name::<String>().as_bytes();
+        assert!(!bytes
+            .windows(type_name.len())
+            .any(|window| window == type_name));
+
+        let value = deserialize_binary(&bytes, &registry).unwrap();
+        assert_eq!(value.type_name(), std::any::type_name::<Foo>());
+        assert!(foo.reflect_partial_eq(&*value).unwrap());
+
+        // values written together share the name table
+        let mut writer = ReflectBinaryWriter::new(&registry);
+        writer.write_value(&foo).unwrap();
+        writer.write_value(&Baz::A).unwrap();
+        let bytes = writer.finish();
+        let mut reader = ReflectBinaryReader::new(&bytes, &registry).unwrap();
+        assert!(foo
+            .reflect_partial_eq(&*reader.read_value().unwrap())
+            .unwrap());
+        assert!(Baz::A
+            .reflect_partial_eq(&*reader.read_value().unwrap())
+            .unwrap());
+        assert!(reader.is_empty());
+
+        assert!(matches!(
+            deserialize_binary(&bytes, &TypeRegistry::default()),
+            Err(ReflectBinaryError::UnknownTypeHash(_))
+        ));
+
+        // malformed documents are rejected before they exhaust the stack or memory
+        assert!(matches!(
+            deserialize_binary(&[BINARY_FORMAT_VERSION, 0xff, 0x7f], &registry),
+            Err(ReflectBinaryError::InvalidLength(0x3fff))
+        ));
+        let mut nested = DynamicList::default();
+        for _ in 0..MAX_BINARY_DEPTH {
+            let mut list = DynamicList::default();
+            list.push_box(Box::new(nested));
+            nested = list;
+        }
+        let bytes = serialize_binary(&nested, &registry).unwrap();
+        assert!(matches!(
+            deserialize_binary(&bytes, &registry),
+            Err(ReflectBinaryError::DepthLimitExceeded)
+        ));
+    }
+
+    #[test]
+    fn reflect_type_info() {
+        #[derive(Reflect, Default)]
+        #[reflect(Default)]
+        struct Foo {
+            a: u32,
+            #[reflect(ignore)]
+            _b: u32,
+            c: Vec<String>,
+            d: HashMap<usize, Bar>,
+            e: Baz,
+        }
+
+        #[derive(Reflect, Default, Clone)]
+        struct Bar(f32, #[reflect(ignore)] f32, i8);
+
+        #[derive(Reflect)]
+        enum Baz {
+            A,
+            B { x: u32 },
+        }
+
+        impl Default for Baz {
+            fn default() -> Self {
+                Baz::A
+            }
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        registry.register::<Bar>();
+        registry.register::<Baz>();
+
+        let registration = registry.get(std::any::TypeId::of::<Foo>()).unwrap();
+        let info = if let TypeInfo::Struct(info) = registration.type_info() {
+            info
+        } else {
+            panic!("Expected struct info.");
+        };
+        assert_eq!(
+            info.iter().map(|field| field.name()).collect::<Vec<_>>(),
+            vec!["a", "c", "d", "e"]
+        );
+        assert!(info.field("a").unwrap().is::<u32>());
+        assert!(info.field("_b").is_none());
+        assert_eq!(
+            info.field_at(1).unwrap().type_name(),
+            std::any::type_name::<Vec<String>>()
+        );
+
+        if let TypeInfo::List(list_info) = <Vec<String> as Typed>::type_info() {
+            assert_eq!(list_info.item_type_id(), std::any::TypeId::of::<String>());
+        } else {
+            panic!("Expected list info.");
+        }
+        if let TypeInfo::Map(map_info) = <HashMap<usize, Bar> as Typed>::type_info() {
+            assert_eq!(map_info.key_type_id(), std::any::TypeId::of::<usize>());
+            assert_eq!(map_info.value_type_id(), std::any::TypeId::of::<Bar>());
+        } else {
+            panic!("Expected map info.");
+        }
+
+        let registration = registry.get(std::any::TypeId::of::<Bar>()).unwrap();
+        if let TypeInfo::TupleStruct(info) = registration.type_info() {
+            assert_eq!(info.field_len(), 2);
+            assert_eq!(info.field_at(1).unwrap().name(), "1");
+            assert!(info.field_at(1).unwrap().is::<i8>());
+        } else {
+            panic!("Expected tuple struct info.");
+        }
+
+        let registration = registry.get(std::any::TypeId::of::<Baz>()).unwrap();
+        if let TypeInfo::Enum(info) = registration.type_info() {
+            assert_eq!(info.variant_len(), 2);
+            assert_eq!(
+                info.variant_at(0).unwrap().variant_type(),
+                VariantType::Unit
+            );
+            let variant = info.variant("B").unwrap();
+            assert_eq!(variant.variant_type(), VariantType::Struct);
+            assert!(variant.field("x").unwrap().is::<u32>());
+        } else {
+            panic!("Expected enum info.");
+        }
+
+        // only types with #[reflect(Default)] can be created from their registration
+        assert!(registry
+            .get_type_data::<ReflectDefault>(std::any::TypeId::of::<Bar>())
+            .is_none());
+        let reflect_default = registry
+            .get_type_data::<ReflectDefault>(std::any::TypeId::of::<Foo>())
+            .unwrap();
+        let foo = reflect_default.default().take::<Foo>().unwrap();
+        assert_eq!(foo.a, 0);
+    }
+
+    #[test]
+    fn reflect_deserialize_validation() {
+        #[derive(Reflect)]
+        struct Foo {
+            a: u32,
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Foo>();
+        registry.register::<u32>();
+
+        let deserialize = |value: &dyn Reflect| {
+            let serializer = ReflectSerializer::new(value, &registry);
+            let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+            let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+            ReflectDeserializer::new(&registry)
+                .deserialize(&mut deserializer)
+                .map(|_| ())
+        };
+
+        assert!(deserialize(&Foo { a: 1 }).is_ok());
+
+        let mut dynamic_struct = DynamicStruct::default();
+        dynamic_struct.set_name(std::any::type_name::<Foo>().to_string());
+        dynamic_struct.insert("b", 1u32);
+        assert!(deserialize(&dynamic_struct).is_err());
+
+        let mut dynamic_tuple_struct = DynamicTupleStruct::default();
+        dynamic_tuple_struct.set_name(std::any::type_name::<Foo>().to_string());
+        dynamic_tuple_struct.insert(1u32);
+        assert!(deserialize(&dynamic_tuple_struct).is_err());
+    }
+
+    #[test]
+    fn reflect_methods() {
+        #[derive(Reflect, Debug, PartialEq)]
+        #[reflect(Methods)]
+        struct Counter {
+            count: u32,
+        }
+
+        #[reflect_methods]
+        impl Counter {
+            pub fn new(count: u32) -> Self {
+                Counter { count }
+            }
+
+            pub fn count(&self) -> &u32 {
+                &self.count
+            }
+
+            pub fn add(&mut self, amount: u32, times: &u32) {
+                self.count += amount * times;
+            }
+
+            #[reflect(ignore)]
+            #[allow(dead_code)]
+            pub fn into_count(self) -> u32 {
+                self.count
+            }
+
+            #[allow(dead_code)]
+            fn reset(&mut self) {
+                self.count = 0;
+            }
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Counter>();
+        let methods = registry
+            .get_type_data::<ReflectMethods>(std::any::TypeId::of::<Counter>())
+            .unwrap();
+        assert!(methods.get("into_count").is_none());
+        // private methods aren't reflected
+        assert!(methods.get("reset").is_none());
+        let add = methods.get("add").unwrap();
+        assert!(add.takes_self_mut());
+        assert_eq!(
+            add.args().iter().map(|arg| arg.name()).collect::<Vec<_>>(),
+            vec!["amount", "times"]
+        );
+        assert_eq!(add.return_type_name(), None);
+
+        let mut counter = methods
+            .call_static("new", vec![Box::new(1u32) as Box<dyn Reflect>])
+            .unwrap()
+            .unwrap()
+            .take::<Counter>()
+            .unwrap();
+        assert_eq!(counter, Counter { count: 1 });
+
+        let result = methods
+            .call(
+                "add",
+                &mut counter,
+                vec![Box::new(2u32) as Box<dyn Reflect>, Box::new(3u32)],
+            )
+            .unwrap();
+        assert!(result.is_none());
+        assert_eq!(counter.count, 7);
+
+        let count = methods.call_ref("count", &counter, Vec::new()).unwrap();
+        assert_eq!(*count.unwrap().downcast_ref::<u32>().unwrap(), 7);
+
+        assert_eq!(
+            methods
+                .call_ref(
+                    "add",
+                    &counter,
+                    vec![Box::new(2u32) as Box<dyn Reflect>, Box::new(3u32)]
+                )
+                .unwrap_err(),
+            MethodCallError::ImmutableReceiver("add")
+        );
+        assert_eq!(
+            methods
+                .call(
+                    "add",
+                    &mut counter,
+                    vec![Box::new(2u32) as Box<dyn Reflect>]
+                )
+                .unwrap_err(),
+            MethodCallError::ArgumentCount {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(
+            methods
+                .call(
+                    "add",
+                    &mut counter,
+                    vec![Box::new(2u32) as Box<dyn Reflect>, Box::new(3i32)]
+                )
+                .unwrap_err(),
+            MethodCallError::InvalidArgument {
+                index: 1,
+                expected: std::any::type_name::<u32>(),
+                found: std::any::type_name::<i32>().to_string(),
+            }
+        );
+        assert_eq!(
+            methods
+                .call("remove", &mut counter, Vec::new())
+                .unwrap_err(),
+            MethodCallError::NotFound("remove".to_string())
+        );
+        assert!(matches!(
+            methods.call("count", &mut 1u32, Vec::new()),
+            Err(MethodCallError::InvalidReceiver { .. })
+        ));
+    }
+
+    #[test]
+    fn reflect_diff() {
+        #[derive(Reflect, Clone, Default, Debug, PartialEq)]
+        #[reflect(Default)]
+        struct Bar {
+            x: u32,
+        }
+
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        struct Foo {
+            a: u32,
+            b: (u32, f32),
+            c: Vec<Bar>,
+            d: HashMap<u32, Bar>,
+            e: String,
+        }
+
+        let mut from_map = HashMap::default();
+        from_map.insert(1, Bar { x: 1 });
+        from_map.insert(2, Bar { x: 2 });
+        let from = Foo {
+            a: 1,
+            b: (2, 3.0),
+            c: vec![Bar { x: 1 }, Bar { x: 2 }],
+            d: from_map,
+            e: "hello".to_string(),
+        };
+
+        let mut to = from.clone();
+        to.b.1 = 4.0;
+        to.c[1].x = 5;
+        to.c.push(Bar { x: 6 });
+        to.d.remove(&1);
+        to.d.get_mut(&2).unwrap().x = 7;
+        to.d.insert(3, Bar { x: 8 });
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<u32>();
+        registry.register::<f32>();
+        registry.register::<usize>();
+        registry.register::<String>();
+        registry.register::<Bar>();
+        registry.add_registration(TypeRegistration::of::<Vec<Bar>>());
+        registry.add_registration(TypeRegistration::of::<HashMap<u32, Bar>>());
+
+        assert!(Patch::diff(&from, &from.clone()).is_empty());
+
+        let patch = Patch::diff(&from, &to);
+        let paths = patch.ops().iter().map(|op| op.path()).collect::<Vec<_>>();
+        assert_eq!(paths, vec![".b.1", ".c[1].x", ".c", ".d", ".d", ".d"]);
+
+        let mut value = from.clone();
+        patch.apply(&mut value, &registry).unwrap();
+        assert_eq!(value, to);
+
+        // lists can also shrink
+        let patch = Patch::diff(&to, &from);
+        let mut value = to.clone();
+        patch.apply(&mut value, &registry).unwrap();
+        assert_eq!(value, from);
+
+        let patch = Patch::diff(&from, &to);
+        let dynamic_patch = patch.clone_dynamic();
+        let serializer = ReflectSerializer::new(&dynamic_patch, &registry);
+        let serialized = to_string_pretty(&serializer, PrettyConfig::default()).unwrap();
+
+        let mut deserializer = Deserializer::from_str(&serialized).unwrap();
+        let reflect_deserializer = ReflectDeserializer::new(&registry);
+        let deserialized = reflect_deserializer.deserialize(&mut deserializer).unwrap();
+        let deserialized_patch = Patch::from_reflect(&*deserialized).unwrap();
+        assert_eq!(deserialized_patch.len(), patch.len());
+
+        let mut value = from.clone();
+        deserialized_patch.apply(&mut value, &registry).unwrap();
+        assert_eq!(value, to);
+
+        assert_eq!(
+            patch.apply(&mut 1u32, &registry).err(),
+            Some(PatchError::InvalidPath {
+                path: ".b.1".to_string(),
+                error: ReflectPathError::ExpectedStruct { index: 1 }.to_string(),
+            })
+        );
+
+        // items of registered lists that can't be created are rejected instead of panicking
+        #[derive(Reflect, Clone, Debug, PartialEq)]
+        struct Baz {
+            x: u32,
+        }
+        registry.add_registration(TypeRegistration::of::<Vec<Baz>>());
+        let patch = Patch::diff(&Vec::<Baz>::new(), &vec![Baz { x: 1 }]);
+        assert_eq!(
+            patch.apply(&mut Vec::<Baz>::new(), &registry).err(),
+            Some(PatchError::UnconvertibleValue {
+                path: String::new(),
+                type_name: std::any::type_name::<Vec<Baz>>().to_string(),
+            })
+        );
+
+        // so are items of lists and maps that aren't registered
+        let mut unregistered_list = vec![Baz { x: 0 }];
+        let mut push = Patch::default();
+        push.push(PatchOp::Push {
+            path: String::new(),
+            value: Box::new(Baz { x: 1 }.clone_dynamic()),
+        });
+        assert_eq!(
+            push.apply(&mut unregistered_list, &registry).err(),
+            Some(PatchError::UnconvertibleValue {
+                path: String::new(),
+                type_name: std::any::type_name::<Vec<Baz>>().to_string(),
+            })
+        );
+        assert_eq!(unregistered_list, vec![Baz { x: 0 }]);
+
+        let mut unregistered_map = HashMap::<u32, Baz>::default();
+        let mut insert = Patch::default();
+        insert.push(PatchOp::Insert {
+            path: String::new(),
+            key: Box::new("1".to_string()),
+            value: Box::new(Baz { x: 1 }),
+        });
+        assert_eq!(
+            insert.apply(&mut unregistered_map, &registry).err(),
+            Some(PatchError::UnconvertibleValue {
+                path: String::new(),
+                type_name: std::any::type_name::<HashMap<u32, Baz>>().to_string(),
+            })
+        );
+        assert!(unregistered_map.is_empty());
+
+        // values of the wrong type are rejected even if the item type is registered with ReflectDefault
+        registry.add_registration(TypeRegistration::of::<Vec<u32>>());
+        let mut wrong_type = Patch::default();
+        wrong_type.push(PatchOp::Push {
+            path: String::new(),
+            value: Box::new("1".to_string()),
+        });
+        assert_eq!(
+            wrong_type.apply(&mut vec![0u32], &registry).err(),
+            Some(PatchError::UnconvertibleValue {
+                path: String::new(),
+                type_name: std::any::type_name::<Vec<u32>>().to_string(),
+            })
+        );
+    }
+
     #[test]
     fn reflect_take() {
         #[derive(Reflect, Debug, PartialEq)]
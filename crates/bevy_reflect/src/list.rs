@@ -7,6 +7,17 @@ pub trait List: Reflect {
     fn get(&self, index: usize) -> Option<&dyn Reflect>;
     fn get_mut(&mut self, index: usize) -> Option<&mut dyn Reflect>;
     fn push(&mut self, value: Box<dyn Reflect>);
+    /// Like [List::push], except that a value of the wrong type is returned instead of panicking. The default
+    /// implementation calls [List::push], lists that can reject values should override it.
+    fn try_push(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        self.push(value);
+        Ok(())
+    }
+    /// Removes the last item and returns it. Returns `None` if the list is empty or doesn't support removing items,
+    /// which is the default.
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        None
+    }
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool {
         self.len() == 0
@@ -78,6 +89,10 @@ impl List for DynamicList {
     fn push(&mut self, value: Box<dyn Reflect>) {
         DynamicList::push_box(self, value);
     }
+
+    fn pop(&mut self) -> Option<Box<dyn Reflect>> {
+        self.values.pop()
+    }
 }
 
 impl Reflect for DynamicList {
@@ -17,6 +17,33 @@ pub trait Map: Reflect {
     }
     fn iter(&self) -> MapIter;
     fn clone_dynamic(&self) -> DynamicMap;
+    /// Inserts a key-value pair, replacing the value if the key is already present.
+    ///
+    /// # Panics
+    /// Panics if [Map::try_insert_boxed] rejects the key or the value.
+    fn insert_boxed(&mut self, key: Box<dyn Reflect>, value: Box<dyn Reflect>) {
+        if let Err((key, value)) = self.try_insert_boxed(key, value) {
+            panic!(
+                "Attempted to insert invalid key of type {} or value of type {}.",
+                key.type_name(),
+                value.type_name()
+            );
+        }
+    }
+    /// Like [Map::insert_boxed], except that the key and value are returned instead of panicking if they are of the
+    /// wrong type. The default implementation doesn't support inserting and always returns them.
+    fn try_insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<(), (Box<dyn Reflect>, Box<dyn Reflect>)> {
+        Err((key, value))
+    }
+    /// Removes the entry with `key` and returns its value. Returns `None` if there is no such entry or the map
+    /// doesn't support removing entries, which is the default.
+    fn remove(&mut self, _key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        None
+    }
 }
 
 const HASH_ERROR: &str = "the given key does not support hashing";
@@ -96,6 +123,31 @@ impl Map for DynamicMap {
             .get(index)
             .map(|(key, value)| (&**key, &**value))
     }
+
+    fn try_insert_boxed(
+        &mut self,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<(), (Box<dyn Reflect>, Box<dyn Reflect>)> {
+        if key.reflect_hash().is_none() {
+            return Err((key, value));
+        }
+        DynamicMap::insert_boxed(self, key, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+        let index = self
+            .indices
+            .remove(&key.reflect_hash().expect(HASH_ERROR))?;
+        let (_key, value) = self.values.remove(index);
+        for other_index in self.indices.values_mut() {
+            if *other_index > index {
+                *other_index -= 1;
+            }
+        }
+        Some(value)
+    }
 }
 
 impl Reflect for DynamicMap {
@@ -0,0 +1,228 @@
+use crate::{FieldInfo, FromType, Reflect};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// Implemented for a type by the `#[reflect_methods]` attribute on one of its impl blocks.
+pub trait GetReflectMethods: Reflect {
+    fn reflect_methods() -> Vec<ReflectMethod>;
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum MethodCallError {
+    #[error("the type doesn't have a reflected method named {0}")]
+    NotFound(String),
+    #[error("the receiver should be a {expected}, but it is a {found}")]
+    InvalidReceiver {
+        expected: &'static str,
+        found: String,
+    },
+    #[error("the method {0} doesn't take self")]
+    UnexpectedReceiver(&'static str),
+    #[error("the method {0} takes self, but no receiver was given")]
+    MissingReceiver(&'static str),
+    #[error("the method {0} takes self mutably, but the receiver is immutable")]
+    ImmutableReceiver(&'static str),
+    #[error("expected {expected} arguments, but {found} were given")]
+    ArgumentCount { expected: usize, found: usize },
+    #[error("the argument {index} should be a {expected}, but it is a {found}")]
+    InvalidArgument {
+        index: usize,
+        expected: &'static str,
+        found: String,
+    },
+}
+
+/// The function a [ReflectMethod] calls, depending on how the method takes `self`.
+///
+/// The receiver and the arguments are checked by [ReflectMethod] before the function is called.
+#[allow(clippy::type_complexity)]
+#[derive(Clone, Copy)]
+pub enum MethodFn {
+    Static(fn(Vec<Box<dyn Reflect>>) -> Option<Box<dyn Reflect>>),
+    Ref(fn(&dyn Reflect, Vec<Box<dyn Reflect>>) -> Option<Box<dyn Reflect>>),
+    Mut(fn(&mut dyn Reflect, Vec<Box<dyn Reflect>>) -> Option<Box<dyn Reflect>>),
+}
+
+/// A method or associated function of a reflected type that can be called with reflected arguments.
+///
+/// Arguments are passed by value and downcast to the parameter types. Parameters that are references borrow the
+/// passed value. Returned references are cloned with [Reflect::clone_value], and methods returning `()` return `None`.
+#[derive(Clone)]
+pub struct ReflectMethod {
+    name: &'static str,
+    type_id: TypeId,
+    type_name: &'static str,
+    args: Vec<FieldInfo>,
+    return_type_name: Option<&'static str>,
+    func: MethodFn,
+}
+
+impl ReflectMethod {
+    pub fn new<T: Reflect>(
+        name: &'static str,
+        args: Vec<FieldInfo>,
+        return_type_name: Option<&'static str>,
+        func: MethodFn,
+    ) -> Self {
+        ReflectMethod {
+            name,
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            args,
+            return_type_name,
+            func,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The names and types of the parameters, excluding `self`. Parameters that are references have the type they
+    /// refer to.
+    pub fn args(&self) -> &[FieldInfo] {
+        &self.args
+    }
+
+    pub fn return_type_name(&self) -> Option<&'static str> {
+        self.return_type_name
+    }
+
+    pub fn takes_self(&self) -> bool {
+        !matches!(self.func, MethodFn::Static(_))
+    }
+
+    pub fn takes_self_mut(&self) -> bool {
+        matches!(self.func, MethodFn::Mut(_))
+    }
+
+    /// Calls a method taking `&self` or `&mut self` on `receiver`.
+    pub fn call(
+        &self,
+        receiver: &mut dyn Reflect,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Option<Box<dyn Reflect>>, MethodCallError> {
+        self.check_receiver(&*receiver)?;
+        self.check_args(&args)?;
+        match self.func {
+            MethodFn::Static(_) => Err(MethodCallError::UnexpectedReceiver(self.name)),
+            MethodFn::Ref(func) => Ok(func(receiver, args)),
+            MethodFn::Mut(func) => Ok(func(receiver, args)),
+        }
+    }
+
+    /// Calls a method taking `&self` on `receiver`.
+    pub fn call_ref(
+        &self,
+        receiver: &dyn Reflect,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Option<Box<dyn Reflect>>, MethodCallError> {
+        self.check_receiver(receiver)?;
+        self.check_args(&args)?;
+        match self.func {
+            MethodFn::Static(_) => Err(MethodCallError::UnexpectedReceiver(self.name)),
+            MethodFn::Ref(func) => Ok(func(receiver, args)),
+            MethodFn::Mut(_) => Err(MethodCallError::ImmutableReceiver(self.name)),
+        }
+    }
+
+    /// Calls an associated function that doesn't take `self`.
+    pub fn call_static(
+        &self,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Option<Box<dyn Reflect>>, MethodCallError> {
+        self.check_args(&args)?;
+        match self.func {
+            MethodFn::Static(func) => Ok(func(args)),
+            MethodFn::Ref(_) | MethodFn::Mut(_) => Err(MethodCallError::MissingReceiver(self.name)),
+        }
+    }
+
+    fn check_receiver(&self, receiver: &dyn Reflect) -> Result<(), MethodCallError> {
+        if receiver.any().type_id() != self.type_id {
+            return Err(MethodCallError::InvalidReceiver {
+                expected: self.type_name,
+                found: receiver.type_name().to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_args(&self, args: &[Box<dyn Reflect>]) -> Result<(), MethodCallError> {
+        if args.len() != self.args.len() {
+            return Err(MethodCallError::ArgumentCount {
+                expected: self.args.len(),
+                found: args.len(),
+            });
+        }
+        for (index, (arg, info)) in args.iter().zip(self.args.iter()).enumerate() {
+            if arg.any().type_id() != info.type_id() {
+                return Err(MethodCallError::InvalidArgument {
+                    index,
+                    expected: info.type_name(),
+                    found: arg.type_name().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The reflected methods of a type. Registered with `#[reflect(Methods)]` for types with a `#[reflect_methods]`
+/// impl block.
+#[derive(Clone)]
+pub struct ReflectMethods {
+    methods: Vec<ReflectMethod>,
+}
+
+impl ReflectMethods {
+    pub fn get(&self, name: &str) -> Option<&ReflectMethod> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ReflectMethod> {
+        self.methods.iter()
+    }
+
+    /// Calls the method `name` on `receiver`, see [ReflectMethod::call].
+    pub fn call(
+        &self,
+        name: &str,
+        receiver: &mut dyn Reflect,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Option<Box<dyn Reflect>>, MethodCallError> {
+        self.get_method(name)?.call(receiver, args)
+    }
+
+    /// Calls the method `name` on `receiver`, see [ReflectMethod::call_ref].
+    pub fn call_ref(
+        &self,
+        name: &str,
+        receiver: &dyn Reflect,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Option<Box<dyn Reflect>>, MethodCallError> {
+        self.get_method(name)?.call_ref(receiver, args)
+    }
+
+    /// Calls the associated function `name`, see [ReflectMethod::call_static].
+    pub fn call_static(
+        &self,
+        name: &str,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Option<Box<dyn Reflect>>, MethodCallError> {
+        self.get_method(name)?.call_static(args)
+    }
+
+    fn get_method(&self, name: &str) -> Result<&ReflectMethod, MethodCallError> {
+        self.get(name)
+            .ok_or_else(|| MethodCallError::NotFound(name.to_string()))
+    }
+}
+
+impl<T: GetReflectMethods> FromType<T> for ReflectMethods {
+    fn from_type() -> Self {
+        ReflectMethods {
+            methods: T::reflect_methods(),
+        }
+    }
+}
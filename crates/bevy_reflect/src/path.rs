@@ -1,6 +1,6 @@
 use std::num::ParseIntError;
 
-use crate::{Reflect, ReflectMut, ReflectRef};
+use crate::{Reflect, ReflectMut, ReflectRef, VariantType};
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Error)]
@@ -14,6 +14,8 @@ pub enum ReflectPathError<'a> {
         index: usize,
         tuple_struct_index: usize,
     },
+    #[error("the current tuple doesn't have a field with the given index")]
+    InvalidTupleIndex { index: usize, tuple_index: usize },
     #[error("the current list doesn't have a value at the given index")]
     InvalidListIndex { index: usize, list_index: usize },
     #[error("encountered an unexpected token")]
@@ -225,6 +227,34 @@ fn read_field<'r, 'p>(
                 },
             )?)
         }
+        ReflectRef::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple
+                .field(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
+        ReflectRef::Enum(reflect_enum) => match reflect_enum.variant_type() {
+            VariantType::Tuple => {
+                let tuple_index = field.parse::<usize>()?;
+                Ok(reflect_enum.field_at(tuple_index).ok_or(
+                    ReflectPathError::InvalidTupleStructIndex {
+                        index: current_index,
+                        tuple_struct_index: tuple_index,
+                    },
+                )?)
+            }
+            VariantType::Struct | VariantType::Unit => {
+                Ok(reflect_enum
+                    .field(field)
+                    .ok_or(ReflectPathError::InvalidField {
+                        index: current_index,
+                        field,
+                    })?)
+            }
+        },
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
@@ -254,6 +284,34 @@ fn read_field_mut<'r, 'p>(
                 },
             )?)
         }
+        ReflectMut::Tuple(reflect_tuple) => {
+            let tuple_index = field.parse::<usize>()?;
+            Ok(reflect_tuple
+                .field_mut(tuple_index)
+                .ok_or(ReflectPathError::InvalidTupleIndex {
+                    index: current_index,
+                    tuple_index,
+                })?)
+        }
+        ReflectMut::Enum(reflect_enum) => {
+            match reflect_enum.variant_type() {
+                VariantType::Tuple => {
+                    let tuple_index = field.parse::<usize>()?;
+                    Ok(reflect_enum.field_at_mut(tuple_index).ok_or(
+                        ReflectPathError::InvalidTupleStructIndex {
+                            index: current_index,
+                            tuple_struct_index: tuple_index,
+                        },
+                    )?)
+                }
+                VariantType::Struct | VariantType::Unit => Ok(reflect_enum
+                    .field_mut(field)
+                    .ok_or(ReflectPathError::InvalidField {
+                        index: current_index,
+                        field,
+                    })?),
+            }
+        }
         _ => Err(ReflectPathError::ExpectedStruct {
             index: current_index,
         }),
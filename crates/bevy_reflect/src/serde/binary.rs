@@ -0,0 +1,466 @@
+use crate::{
+    DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct,
+    DynamicVariant, Reflect, ReflectDeserialize, ReflectRef, TypeRegistry, VariantType,
+};
+use bevy_utils::HashMap;
+use bincode::Options;
+use thiserror::Error;
+
+/// The version of the binary format, written at the start of every document.
+pub const BINARY_FORMAT_VERSION: u8 = 0;
+
+const STRUCT: u8 = 0;
+const TUPLE_STRUCT: u8 = 1;
+const TUPLE: u8 = 2;
+const LIST: u8 = 3;
+const MAP: u8 = 4;
+const ENUM: u8 = 5;
+const VALUE: u8 = 6;
+
+const NAME_STRING: u8 = 0;
+const NAME_HASH: u8 = 1;
+
+const VARIANT_STRUCT: u8 = 0;
+const VARIANT_TUPLE: u8 = 1;
+const VARIANT_UNIT: u8 = 2;
+
+/// How deeply values can be nested in a document read by a [ReflectBinaryReader].
+pub const MAX_BINARY_DEPTH: usize = 128;
+
+#[derive(Debug, Error)]
+pub enum ReflectBinaryError {
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    #[error("unsupported binary format version {0}")]
+    UnsupportedVersion(u8),
+    #[error("invalid tag {0}")]
+    InvalidTag(u8),
+    #[error("invalid variable-length integer")]
+    InvalidVarint,
+    #[error("length {0} exceeds the remaining input")]
+    InvalidLength(u64),
+    #[error("values are nested more than {} levels deep", MAX_BINARY_DEPTH)]
+    DepthLimitExceeded,
+    #[error("invalid name index {0}")]
+    InvalidNameIndex(usize),
+    #[error("no registered type has the type hash {0:016x}")]
+    UnknownTypeHash(u64),
+    #[error("several registered types have the type hash {0:016x}")]
+    AmbiguousTypeHash(u64),
+    #[error("invalid UTF-8 in a name")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+    #[error("type '{0}' does not support ReflectValue serialization")]
+    NotSerializable(String),
+    #[error("type '{0}' is not registered with ReflectDeserialize")]
+    NotDeserializable(String),
+    #[error("failed to encode or decode a value: {0}")]
+    Value(#[from] bincode::Error),
+}
+
+/// A stable hash of a type name, used to refer to registered types in the binary format.
+pub fn type_name_hash(type_name: &str) -> u64 {
+    // FNV-1a, which unlike the std hashers is guaranteed to give the same result across builds.
+    type_name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Maps the [type_name_hash] of every registered type to its name, or to `None` if several types have that hash.
+fn registered_type_hashes(registry: &TypeRegistry) -> HashMap<u64, Option<&str>> {
+    let mut type_names = HashMap::default();
+    for registration in registry.iter() {
+        type_names
+            .entry(type_name_hash(registration.name()))
+            .and_modify(|name| *name = None)
+            .or_insert_with(|| Some(registration.name()));
+    }
+    type_names
+}
+
+fn value_options() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+/// Writes reflected values in a compact binary format.
+///
+/// Type, field and variant names are written once in a name table at the start of the document, and values refer to
+/// them by index. Names of types in the registry are written as a [type_name_hash], unless another registered type
+/// has the same hash. Value types are encoded with bincode, using the serde implementations registered for
+/// [ReflectRef::Value] types.
+pub struct ReflectBinaryWriter<'a> {
+    registry: &'a TypeRegistry,
+    names: Vec<String>,
+    name_indices: HashMap<String, usize>,
+    body: Vec<u8>,
+}
+
+impl<'a> ReflectBinaryWriter<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        ReflectBinaryWriter {
+            registry,
+            names: Vec::new(),
+            name_indices: HashMap::default(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Writes a count, such as the number of values that follow. Counts are read with
+    /// [ReflectBinaryReader::read_len].
+    pub fn write_len(&mut self, len: usize) {
+        self.write_varint(len as u64);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.body.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(crate) fn write_varint(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.body.push(byte);
+                return;
+            }
+            self.body.push(byte | 0x80);
+        }
+    }
+
+    pub fn write_value(&mut self, value: &dyn Reflect) -> Result<(), ReflectBinaryError> {
+        let type_index = self.name_index(value.type_name());
+        match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                self.write_header(STRUCT, type_index, value.field_len());
+                for (index, field) in value.iter_fields().enumerate() {
+                    let name_index = self.name_index(value.name_at(index).unwrap());
+                    self.write_varint(name_index as u64);
+                    self.write_value(field)?;
+                }
+            }
+            ReflectRef::TupleStruct(value) => {
+                self.write_header(TUPLE_STRUCT, type_index, value.field_len());
+                for field in value.iter_fields() {
+                    self.write_value(field)?;
+                }
+            }
+            ReflectRef::Tuple(value) => {
+                self.write_header(TUPLE, type_index, value.field_len());
+                for field in value.iter_fields() {
+                    self.write_value(field)?;
+                }
+            }
+            ReflectRef::List(value) => {
+                self.write_header(LIST, type_index, value.len());
+                for item in value.iter() {
+                    self.write_value(item)?;
+                }
+            }
+            ReflectRef::Map(value) => {
+                self.write_header(MAP, type_index, value.len());
+                for (key, value) in value.iter() {
+                    self.write_value(key)?;
+                    self.write_value(value)?;
+                }
+            }
+            ReflectRef::Enum(value) => {
+                self.write_header(ENUM, type_index, value.field_len());
+                let variant_name_index = self.name_index(value.variant_name());
+                self.write_varint(variant_name_index as u64);
+                self.write_varint(value.variant_index() as u64);
+                let variant_type = value.variant_type();
+                self.body.push(match variant_type {
+                    VariantType::Struct => VARIANT_STRUCT,
+                    VariantType::Tuple => VARIANT_TUPLE,
+                    VariantType::Unit => VARIANT_UNIT,
+                });
+                for (index, field) in value.iter_fields().enumerate() {
+                    if variant_type == VariantType::Struct {
+                        let name_index = self.name_index(value.name_at(index).unwrap());
+                        self.write_varint(name_index as u64);
+                    }
+                    self.write_value(field)?;
+                }
+            }
+            ReflectRef::Value(value) => {
+                let serializable = value.serializable().ok_or_else(|| {
+                    ReflectBinaryError::NotSerializable(value.type_name().to_string())
+                })?;
+                let bytes = value_options().serialize(serializable.borrow())?;
+                self.write_header(VALUE, type_index, bytes.len());
+                self.body.extend_from_slice(&bytes);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the document: the format version, the name table, then everything written so far.
+    pub fn finish(self) -> Vec<u8> {
+        let type_names = registered_type_hashes(self.registry);
+        let mut table = ReflectBinaryWriter::new(self.registry);
+        table.body.push(BINARY_FORMAT_VERSION);
+        table.write_varint(self.names.len() as u64);
+        for name in self.names.iter() {
+            if type_names.get(&type_name_hash(name)) == Some(&Some(name.as_str())) {
+                table.body.push(NAME_HASH);
+                table
+                    .body
+                    .extend_from_slice(&type_name_hash(name).to_le_bytes());
+            } else {
+                table.body.push(NAME_STRING);
+                table.write_varint(name.len() as u64);
+                table.body.extend_from_slice(name.as_bytes());
+            }
+        }
+        let mut bytes = table.body;
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+
+    fn write_header(&mut self, tag: u8, type_index: usize, len: usize) {
+        self.body.push(tag);
+        self.write_varint(type_index as u64);
+        self.write_varint(len as u64);
+    }
+
+    fn name_index(&mut self, name: &str) -> usize {
+        if let Some(index) = self.name_indices.get(name) {
+            return *index;
+        }
+        let index = self.names.len();
+        self.names.push(name.to_string());
+        self.name_indices.insert(name.to_string(), index);
+        index
+    }
+}
+
+/// Reads reflected values written by a [ReflectBinaryWriter], in the order they were written.
+///
+/// Values are read as the dynamic types [ReflectDeserializer](crate::serde::ReflectDeserializer) returns, except
+/// value types, which are deserialized with their [ReflectDeserialize] registration.
+///
+/// Reading fails if values are nested more than [MAX_BINARY_DEPTH] levels deep, or if a count or length is larger
+/// than the rest of the input, so malformed documents can't exhaust the stack or memory.
+pub struct ReflectBinaryReader<'a> {
+    registry: &'a TypeRegistry,
+    names: Vec<String>,
+    bytes: &'a [u8],
+    depth: usize,
+}
+
+impl<'a> ReflectBinaryReader<'a> {
+    /// Reads the header of a document.
+    pub fn new(bytes: &'a [u8], registry: &'a TypeRegistry) -> Result<Self, ReflectBinaryError> {
+        let mut reader = ReflectBinaryReader {
+            registry,
+            names: Vec::new(),
+            bytes,
+            depth: 0,
+        };
+        let version = reader.read_u8()?;
+        if version != BINARY_FORMAT_VERSION {
+            return Err(ReflectBinaryError::UnsupportedVersion(version));
+        }
+
+        let mut type_names = None;
+        for _ in 0..reader.read_len()? {
+            let name = match reader.read_u8()? {
+                NAME_STRING => {
+                    let len = reader.read_len()?;
+                    std::str::from_utf8(reader.read_bytes(len)?)?.to_string()
+                }
+                NAME_HASH => {
+                    let mut hash = [0; 8];
+                    hash.copy_from_slice(reader.read_bytes(8)?);
+                    let hash = u64::from_le_bytes(hash);
+                    let type_names =
+                        type_names.get_or_insert_with(|| registered_type_hashes(registry));
+                    type_names
+                        .get(&hash)
+                        .ok_or(ReflectBinaryError::UnknownTypeHash(hash))?
+                        .ok_or(ReflectBinaryError::AmbiguousTypeHash(hash))?
+                        .to_string()
+                }
+                tag => return Err(ReflectBinaryError::InvalidTag(tag)),
+            };
+            reader.names.push(name);
+        }
+        Ok(reader)
+    }
+
+    /// Returns true if everything in the document has been read.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Reads a count written with [ReflectBinaryWriter::write_len]. Every counted item takes up at least a byte, so
+    /// counts larger than the rest of the input are rejected.
+    pub fn read_len(&mut self) -> Result<usize, ReflectBinaryError> {
+        let len = self.read_varint()?;
+        if len > self.bytes.len() as u64 {
+            return Err(ReflectBinaryError::InvalidLength(len));
+        }
+        Ok(len as usize)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, ReflectBinaryError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn read_varint(&mut self) -> Result<u64, ReflectBinaryError> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            if shift >= 64 {
+                return Err(ReflectBinaryError::InvalidVarint);
+            }
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    pub fn read_value(&mut self) -> Result<Box<dyn Reflect>, ReflectBinaryError> {
+        if self.depth == MAX_BINARY_DEPTH {
+            return Err(ReflectBinaryError::DepthLimitExceeded);
+        }
+        self.depth += 1;
+        let value = self.read_nested_value();
+        self.depth -= 1;
+        value
+    }
+
+    fn read_nested_value(&mut self) -> Result<Box<dyn Reflect>, ReflectBinaryError> {
+        let tag = self.read_u8()?;
+        let type_name = self.read_name()?.to_string();
+        let len = self.read_len()?;
+        Ok(match tag {
+            STRUCT => {
+                let mut dynamic_struct = DynamicStruct::default();
+                dynamic_struct.set_name(type_name);
+                for _ in 0..len {
+                    let name = self.read_name()?.to_string();
+                    let value = self.read_value()?;
+                    dynamic_struct.insert_boxed(&name, value);
+                }
+                Box::new(dynamic_struct)
+            }
+            TUPLE_STRUCT => {
+                let mut tuple_struct = DynamicTupleStruct::default();
+                tuple_struct.set_name(type_name);
+                for _ in 0..len {
+                    tuple_struct.insert_boxed(self.read_value()?);
+                }
+                Box::new(tuple_struct)
+            }
+            TUPLE => {
+                let mut tuple = DynamicTuple::default();
+                tuple.set_name(type_name);
+                for _ in 0..len {
+                    tuple.insert_boxed(self.read_value()?);
+                }
+                Box::new(tuple)
+            }
+            LIST => {
+                let mut list = DynamicList::default();
+                list.set_name(type_name);
+                for _ in 0..len {
+                    list.push_box(self.read_value()?);
+                }
+                Box::new(list)
+            }
+            MAP => {
+                let mut map = DynamicMap::default();
+                map.set_name(type_name);
+                for _ in 0..len {
+                    let key = self.read_value()?;
+                    let value = self.read_value()?;
+                    map.insert_boxed(key, value);
+                }
+                Box::new(map)
+            }
+            ENUM => {
+                let variant_name = self.read_name()?.to_string();
+                let variant_index = self.read_varint()? as usize;
+                let variant = match self.read_u8()? {
+                    VARIANT_STRUCT => {
+                        let mut fields = DynamicStruct::default();
+                        for _ in 0..len {
+                            let name = self.read_name()?.to_string();
+                            let value = self.read_value()?;
+                            fields.insert_boxed(&name, value);
+                        }
+                        DynamicVariant::Struct(fields)
+                    }
+                    VARIANT_TUPLE => {
+                        let mut fields = DynamicTuple::default();
+                        for _ in 0..len {
+                            fields.insert_boxed(self.read_value()?);
+                        }
+                        DynamicVariant::Tuple(fields)
+                    }
+                    VARIANT_UNIT => DynamicVariant::Unit,
+                    tag => return Err(ReflectBinaryError::InvalidTag(tag)),
+                };
+                let mut dynamic_enum = DynamicEnum::new(variant_name, variant_index, variant);
+                dynamic_enum.set_name(type_name);
+                Box::new(dynamic_enum)
+            }
+            VALUE => {
+                let bytes = self.read_bytes(len)?;
+                let reflect_deserialize = self
+                    .registry
+                    .get_with_name(&type_name)
+                    .and_then(|registration| registration.data::<ReflectDeserialize>())
+                    .ok_or(ReflectBinaryError::NotDeserializable(type_name))?;
+                let options = value_options().with_limit(bytes.len() as u64);
+                let mut deserializer = bincode::Deserializer::from_slice(bytes, options);
+                reflect_deserialize.deserialize(&mut deserializer)?
+            }
+            tag => return Err(ReflectBinaryError::InvalidTag(tag)),
+        })
+    }
+
+    fn read_name(&mut self) -> Result<&str, ReflectBinaryError> {
+        let index = self.read_varint()? as usize;
+        self.names
+            .get(index)
+            .map(|name| name.as_str())
+            .ok_or(ReflectBinaryError::InvalidNameIndex(index))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, ReflectBinaryError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ReflectBinaryError> {
+        if self.bytes.len() < len {
+            return Err(ReflectBinaryError::UnexpectedEof);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+}
+
+/// Writes `value` as a binary document, see [ReflectBinaryWriter].
+pub fn serialize_binary(
+    value: &dyn Reflect,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, ReflectBinaryError> {
+    let mut writer = ReflectBinaryWriter::new(registry);
+    writer.write_value(value)?;
+    Ok(writer.finish())
+}
+
+/// Reads a binary document written by [serialize_binary].
+pub fn deserialize_binary(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, ReflectBinaryError> {
+    ReflectBinaryReader::new(bytes, registry)?.read_value()
+}
@@ -1,6 +1,7 @@
 use crate::{
-    serde::type_fields, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct,
-    Reflect, ReflectDeserialize, TypeRegistry,
+    serde::type_fields, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
+    DynamicTupleStruct, DynamicVariant, Reflect, ReflectDeserialize, ReflectRef, TypeInfo,
+    TypeRegistry,
 };
 use erased_serde::Deserializer;
 use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
@@ -164,6 +165,7 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                         registry: self.registry,
                     })?;
                     dynamic_struct.set_name(type_name);
+                    validate(self.registry, &dynamic_struct)?;
                     return Ok(Box::new(dynamic_struct));
                 }
                 type_fields::TUPLE_STRUCT => {
@@ -174,6 +176,7 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                         registry: self.registry,
                     })?;
                     tuple_struct.set_name(type_name);
+                    validate(self.registry, &tuple_struct)?;
                     return Ok(Box::new(tuple_struct));
                 }
                 type_fields::TUPLE => {
@@ -194,6 +197,17 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
                     })?;
                     return Ok(Box::new(list));
                 }
+                type_fields::ENUM => {
+                    let type_name = type_name
+                        .take()
+                        .ok_or_else(|| de::Error::missing_field(type_fields::TYPE))?;
+                    let mut dynamic_enum = map.next_value_seed(EnumDeserializer {
+                        registry: self.registry,
+                    })?;
+                    dynamic_enum.set_name(type_name);
+                    validate(self.registry, &dynamic_enum)?;
+                    return Ok(Box::new(dynamic_enum));
+                }
                 type_fields::VALUE => {
                     let type_name = type_name
                         .take()
@@ -222,6 +236,77 @@ impl<'a, 'de> Visitor<'de> for ReflectVisitor<'a> {
     }
 }
 
+/// Checks the fields of a deserialized value against the [TypeInfo] of its type, if the type is registered.
+fn validate<E: de::Error>(registry: &TypeRegistry, value: &dyn Reflect) -> Result<(), E> {
+    let type_name = value.type_name();
+    let type_info = match registry.get_with_name(type_name) {
+        Some(registration) => registration.type_info(),
+        None => return Ok(()),
+    };
+    match (value.reflect_ref(), type_info) {
+        (ReflectRef::Struct(value), TypeInfo::Struct(info)) => {
+            for index in 0..value.field_len() {
+                let name = value.name_at(index).unwrap();
+                if info.field(name).is_none() {
+                    return Err(de::Error::custom(format!(
+                        "struct {} has no field named {}",
+                        type_name, name
+                    )));
+                }
+            }
+        }
+        (ReflectRef::TupleStruct(value), TypeInfo::TupleStruct(info)) => {
+            if value.field_len() > info.field_len() {
+                return Err(de::Error::invalid_length(
+                    value.field_len(),
+                    &format!("at most {} fields for {}", info.field_len(), type_name).as_str(),
+                ));
+            }
+        }
+        (ReflectRef::Enum(value), TypeInfo::Enum(info)) => {
+            let variant_name = value.variant_name();
+            let variant = info.variant(variant_name).ok_or_else(|| {
+                de::Error::custom(format!(
+                    "enum {} has no variant named {}",
+                    type_name, variant_name
+                ))
+            })?;
+            if variant.variant_type() != value.variant_type() {
+                return Err(de::Error::custom(format!(
+                    "{}::{} is a {:?} variant, found a {:?} variant",
+                    type_name,
+                    variant_name,
+                    variant.variant_type(),
+                    value.variant_type()
+                )));
+            }
+            for index in 0..value.field_len() {
+                let is_valid = match value.name_at(index) {
+                    Some(name) => variant.field(name).is_some(),
+                    None => index < variant.field_len(),
+                };
+                if !is_valid {
+                    let field = value
+                        .name_at(index)
+                        .map(|name| name.to_string())
+                        .unwrap_or_else(|| index.to_string());
+                    return Err(de::Error::custom(format!(
+                        "variant {}::{} has no field {}",
+                        type_name, variant_name, field
+                    )));
+                }
+            }
+        }
+        _ => {
+            return Err(de::Error::custom(format!(
+                "{} was serialized as a different kind of type than it is",
+                type_name
+            )))
+        }
+    }
+    Ok(())
+}
+
 struct DeserializeReflectDeserializer<'a> {
     reflect_deserialize: &'a ReflectDeserialize,
 }
@@ -452,3 +537,72 @@ impl<'a, 'de> Visitor<'de> for TupleVisitor<'a> {
         Ok(tuple)
     }
 }
+
+struct EnumDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EnumDeserializer<'a> {
+    type Value = DynamicEnum;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EnumVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct EnumVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
+    type Value = DynamicEnum;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("enum value")
+    }
+
+    fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+    where
+        V: MapAccess<'de>,
+    {
+        let mut variant_name: Option<String> = None;
+        let mut variant = DynamicVariant::Unit;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                type_fields::VARIANT => {
+                    variant_name = Some(map.next_value()?);
+                }
+                type_fields::STRUCT => {
+                    variant = DynamicVariant::Struct(map.next_value_seed(StructDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                type_fields::TUPLE => {
+                    variant = DynamicVariant::Tuple(map.next_value_seed(TupleDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                _ => {
+                    return Err(de::Error::unknown_field(
+                        key.as_str(),
+                        &[
+                            type_fields::VARIANT,
+                            type_fields::STRUCT,
+                            type_fields::TUPLE,
+                        ],
+                    ))
+                }
+            }
+        }
+
+        let variant_name =
+            variant_name.ok_or_else(|| de::Error::missing_field(type_fields::VARIANT))?;
+        // Only the variant name is serialized, enums are matched by name when the DynamicEnum is applied.
+        Ok(DynamicEnum::new(variant_name, 0, variant))
+    }
+}
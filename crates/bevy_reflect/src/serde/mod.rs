@@ -1,6 +1,8 @@
+mod binary;
 mod de;
 mod ser;
 
+pub use binary::*;
 pub use de::*;
 pub use ser::*;
 
@@ -11,5 +13,7 @@ pub(crate) mod type_fields {
     pub const TUPLE_STRUCT: &str = "tuple_struct";
     pub const TUPLE: &str = "tuple";
     pub const LIST: &str = "list";
+    pub const ENUM: &str = "enum";
+    pub const VARIANT: &str = "variant";
     pub const VALUE: &str = "value";
 }
@@ -1,5 +1,6 @@
 use crate::{
-    serde::type_fields, List, Map, Reflect, ReflectRef, Struct, Tuple, TupleStruct, TypeRegistry,
+    serde::type_fields, Enum, List, Map, Reflect, ReflectRef, Struct, Tuple, TupleStruct,
+    TypeRegistry, VariantType,
 };
 use serde::{
     ser::{SerializeMap, SerializeSeq},
@@ -72,6 +73,11 @@ impl<'a> Serialize for ReflectSerializer<'a> {
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Enum(value) => EnumSerializer {
+                enum_value: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Value(value) => ReflectValueSerializer {
                 registry: self.registry,
                 value,
@@ -228,6 +234,90 @@ impl<'a> Serialize for TupleValueSerializer<'a> {
     }
 }
 
+pub struct EnumSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(2))?;
+
+        state.serialize_entry(type_fields::TYPE, self.enum_value.type_name())?;
+        state.serialize_entry(
+            type_fields::ENUM,
+            &EnumValueSerializer {
+                enum_value: self.enum_value,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// Serializes the variant name and, unless it is a unit variant, the fields of the current variant under the
+/// `struct` or `tuple` key.
+pub struct EnumValueSerializer<'a> {
+    pub enum_value: &'a dyn Enum,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EnumValueSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant_type = self.enum_value.variant_type();
+        let len = if variant_type == VariantType::Unit {
+            1
+        } else {
+            2
+        };
+        let mut state = serializer.serialize_map(Some(len))?;
+        state.serialize_entry(type_fields::VARIANT, self.enum_value.variant_name())?;
+        let fields = VariantFieldsSerializer {
+            enum_value: self.enum_value,
+            registry: self.registry,
+        };
+        match variant_type {
+            VariantType::Struct => state.serialize_entry(type_fields::STRUCT, &fields)?,
+            VariantType::Tuple => state.serialize_entry(type_fields::TUPLE, &fields)?,
+            VariantType::Unit => {}
+        }
+        state.end()
+    }
+}
+
+struct VariantFieldsSerializer<'a> {
+    enum_value: &'a dyn Enum,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for VariantFieldsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.enum_value.variant_type() == VariantType::Struct {
+            let mut state = serializer.serialize_map(Some(self.enum_value.field_len()))?;
+            for (index, value) in self.enum_value.iter_fields().enumerate() {
+                let key = self.enum_value.name_at(index).unwrap();
+                state.serialize_entry(key, &ReflectSerializer::new(value, self.registry))?;
+            }
+            state.end()
+        } else {
+            let mut state = serializer.serialize_seq(Some(self.enum_value.field_len()))?;
+            for value in self.enum_value.iter_fields() {
+                state.serialize_element(&ReflectSerializer::new(value, self.registry))?;
+            }
+            state.end()
+        }
+    }
+}
+
 pub struct MapSerializer<'a> {
     pub map: &'a dyn Map,
     pub registry: &'a TypeRegistry,
@@ -1,6 +1,8 @@
 use std::any::Any;
 
-use crate::{serde::Serializable, Reflect, ReflectMut, ReflectRef};
+use crate::{
+    serde::Serializable, FieldInfo, Reflect, ReflectMut, ReflectRef, TupleInfo, TypeInfo, Typed,
+};
 
 pub trait Tuple: Reflect {
     fn field(&self, index: usize) -> Option<&dyn Reflect>;
@@ -320,6 +322,14 @@ macro_rules! impl_reflect_tuple {
                 None
             }
         }
+
+        impl<$($name: Reflect),*> Typed for ($($name,)*) {
+            fn type_info() -> TypeInfo {
+                TypeInfo::Tuple(TupleInfo::new(vec![
+                    $(FieldInfo::new::<$name>(stringify!($index)),)*
+                ]))
+            }
+        }
     }
 }
 
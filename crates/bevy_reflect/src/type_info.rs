@@ -0,0 +1,231 @@
+use crate::{Reflect, VariantType};
+use std::any::TypeId;
+
+/// A reflected type that can describe itself without an instance. Implemented by the `Reflect` derive.
+pub trait Typed: Reflect {
+    fn type_info() -> TypeInfo;
+}
+
+/// Static information about a reflected type. The variant matches the [ReflectRef](crate::ReflectRef) kind of the
+/// type's values.
+#[derive(Clone, Debug)]
+pub enum TypeInfo {
+    Struct(StructInfo),
+    TupleStruct(TupleInfo),
+    Tuple(TupleInfo),
+    List(ListInfo),
+    Map(MapInfo),
+    Enum(EnumInfo),
+    Value,
+}
+
+/// The name and type of a reflected field. Fields of tuples and tuple structs are named by their index.
+#[derive(Clone, Debug)]
+pub struct FieldInfo {
+    name: &'static str,
+    type_name: &'static str,
+    type_id: TypeId,
+}
+
+impl FieldInfo {
+    pub fn new<T: Reflect>(name: &'static str) -> Self {
+        FieldInfo {
+            name,
+            type_name: std::any::type_name::<T>(),
+            type_id: TypeId::of::<T>(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub fn is<T: Reflect>(&self) -> bool {
+        self.type_id == TypeId::of::<T>()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StructInfo {
+    fields: Vec<FieldInfo>,
+}
+
+impl StructInfo {
+    pub fn new(fields: Vec<FieldInfo>) -> Self {
+        StructInfo { fields }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn field_at(&self, index: usize) -> Option<&FieldInfo> {
+        self.fields.get(index)
+    }
+
+    pub fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &FieldInfo> {
+        self.fields.iter()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TupleInfo {
+    fields: Vec<FieldInfo>,
+}
+
+impl TupleInfo {
+    pub fn new(fields: Vec<FieldInfo>) -> Self {
+        TupleInfo { fields }
+    }
+
+    pub fn field_at(&self, index: usize) -> Option<&FieldInfo> {
+        self.fields.get(index)
+    }
+
+    pub fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &FieldInfo> {
+        self.fields.iter()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ListInfo {
+    item_type_name: &'static str,
+    item_type_id: TypeId,
+}
+
+impl ListInfo {
+    pub fn new<T: Reflect>() -> Self {
+        ListInfo {
+            item_type_name: std::any::type_name::<T>(),
+            item_type_id: TypeId::of::<T>(),
+        }
+    }
+
+    pub fn item_type_name(&self) -> &'static str {
+        self.item_type_name
+    }
+
+    pub fn item_type_id(&self) -> TypeId {
+        self.item_type_id
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MapInfo {
+    key_type_name: &'static str,
+    key_type_id: TypeId,
+    value_type_name: &'static str,
+    value_type_id: TypeId,
+}
+
+impl MapInfo {
+    pub fn new<K: Reflect, V: Reflect>() -> Self {
+        MapInfo {
+            key_type_name: std::any::type_name::<K>(),
+            key_type_id: TypeId::of::<K>(),
+            value_type_name: std::any::type_name::<V>(),
+            value_type_id: TypeId::of::<V>(),
+        }
+    }
+
+    pub fn key_type_name(&self) -> &'static str {
+        self.key_type_name
+    }
+
+    pub fn key_type_id(&self) -> TypeId {
+        self.key_type_id
+    }
+
+    pub fn value_type_name(&self) -> &'static str {
+        self.value_type_name
+    }
+
+    pub fn value_type_id(&self) -> TypeId {
+        self.value_type_id
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EnumInfo {
+    variants: Vec<VariantInfo>,
+}
+
+impl EnumInfo {
+    pub fn new(variants: Vec<VariantInfo>) -> Self {
+        EnumInfo { variants }
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&VariantInfo> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+
+    pub fn variant_at(&self, index: usize) -> Option<&VariantInfo> {
+        self.variants.get(index)
+    }
+
+    pub fn variant_len(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &VariantInfo> {
+        self.variants.iter()
+    }
+}
+
+/// The name and fields of an enum variant. Fields of tuple variants are named by their index.
+#[derive(Clone, Debug)]
+pub struct VariantInfo {
+    name: &'static str,
+    variant_type: VariantType,
+    fields: Vec<FieldInfo>,
+}
+
+impl VariantInfo {
+    pub fn new(name: &'static str, variant_type: VariantType, fields: Vec<FieldInfo>) -> Self {
+        VariantInfo {
+            name,
+            variant_type,
+            fields,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn variant_type(&self) -> VariantType {
+        self.variant_type
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn field_at(&self, index: usize) -> Option<&FieldInfo> {
+        self.fields.get(index)
+    }
+
+    pub fn field_len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &FieldInfo> {
+        self.fields.iter()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Reflect;
+use crate::{Reflect, TypeInfo, Typed};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -106,6 +106,7 @@ pub struct TypeRegistration {
     type_id: TypeId,
     short_name: String,
     name: &'static str,
+    type_info: TypeInfo,
     data: HashMap<TypeId, Box<dyn TypeData>>,
 }
 
@@ -131,7 +132,7 @@ impl TypeRegistration {
         self.data.insert(TypeId::of::<T>(), Box::new(data));
     }
 
-    pub fn of<T: Reflect>() -> Self {
+    pub fn of<T: Typed>() -> Self {
         let ty = TypeId::of::<T>();
         let type_name = std::any::type_name::<T>();
         Self {
@@ -139,9 +140,15 @@ impl TypeRegistration {
             data: HashMap::default(),
             name: type_name,
             short_name: Self::get_short_name(type_name),
+            type_info: T::type_info(),
         }
     }
 
+    /// Returns the fields and other static information of the type.
+    pub fn type_info(&self) -> &TypeInfo {
+        &self.type_info
+    }
+
     pub fn short_name(&self) -> &str {
         &self.short_name
     }
@@ -200,6 +207,7 @@ impl Clone for TypeRegistration {
             name: self.name,
             short_name: self.short_name.clone(),
             type_id: self.type_id,
+            type_info: self.type_info.clone(),
         }
     }
 }
@@ -250,6 +258,26 @@ impl<T: for<'a> Deserialize<'a> + Reflect> FromType<T> for ReflectDeserialize {
     }
 }
 
+/// Creates the default value of a type. Registered with `#[reflect(Default)]`.
+#[derive(Clone)]
+pub struct ReflectDefault {
+    default: fn() -> Box<dyn Reflect>,
+}
+
+impl ReflectDefault {
+    pub fn default(&self) -> Box<dyn Reflect> {
+        (self.default)()
+    }
+}
+
+impl<T: Reflect + Default> FromType<T> for ReflectDefault {
+    fn from_type() -> Self {
+        ReflectDefault {
+            default: || Box::new(T::default()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::TypeRegistration;
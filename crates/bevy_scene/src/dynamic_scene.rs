@@ -1,11 +1,14 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{
+    serde::{serialize_binary_scene, SceneSerializer},
+    Scene, SceneSpawnError,
+};
 use anyhow::Result;
 use bevy_ecs::{
     entity::EntityMap,
     reflect::{ReflectComponent, ReflectMapEntities},
     world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_reflect::{serde::ReflectBinaryError, Reflect, TypeRegistryArc, TypeUuid};
 use serde::Serialize;
 
 #[derive(Default, TypeUuid)]
@@ -107,6 +110,14 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    // TODO: move to AssetSaver when it is implemented
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, ReflectBinaryError> {
+        serialize_binary_scene(self, &*registry.read())
+    }
 }
 
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
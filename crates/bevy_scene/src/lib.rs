@@ -37,6 +37,7 @@ impl Plugin for ScenePlugin {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
             .add_stage_after(
                 CoreStage::Event,
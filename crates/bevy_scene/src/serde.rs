@@ -1,7 +1,10 @@
 use crate::{DynamicScene, Entity};
 use anyhow::Result;
 use bevy_reflect::{
-    serde::{ReflectDeserializer, ReflectSerializer},
+    serde::{
+        ReflectBinaryError, ReflectBinaryReader, ReflectBinaryWriter, ReflectDeserializer,
+        ReflectSerializer,
+    },
     Reflect, TypeRegistry, TypeRegistryArc,
 };
 use serde::{
@@ -249,3 +252,39 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+/// Writes a scene in the binary format of [ReflectBinaryWriter]: the number of entities, then the id, the number of
+/// components and the components of each entity.
+pub fn serialize_binary_scene(
+    scene: &DynamicScene,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, ReflectBinaryError> {
+    let mut writer = ReflectBinaryWriter::new(registry);
+    writer.write_len(scene.entities.len());
+    for entity in scene.entities.iter() {
+        writer.write_u32(entity.entity);
+        writer.write_len(entity.components.len());
+        for component in entity.components.iter() {
+            writer.write_value(&**component)?;
+        }
+    }
+    Ok(writer.finish())
+}
+
+/// Reads a scene written by [serialize_binary_scene].
+pub fn deserialize_binary_scene(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<DynamicScene, ReflectBinaryError> {
+    let mut reader = ReflectBinaryReader::new(bytes, registry)?;
+    let mut scene = DynamicScene::default();
+    for _ in 0..reader.read_len()? {
+        let entity = reader.read_u32()?;
+        let mut components = Vec::new();
+        for _ in 0..reader.read_len()? {
+            components.push(reader.read_value()?);
+        }
+        scene.entities.push(Entity { entity, components });
+    }
+    Ok(scene)
+}